@@ -3,11 +3,18 @@ use std::io::Result;
 fn main() -> Result<()> {
   #[cfg(any(feature = "protoc", feature = "protoc-generated"))]
   {
+    #[cfg(not(feature = "mvt3"))]
+    let proto = "vector-tile-spec/2.1/vector_tile.proto";
+    #[cfg(feature = "mvt3")]
+    let proto = "draft/vector_tile.proto";
+
     let mut prost_build = prost_build::Config::new();
     prost_build.btree_map(["."]);
-    #[cfg(feature = "protoc-generated")]
+    #[cfg(all(feature = "protoc-generated", not(feature = "mvt3")))]
     prost_build.out_dir("src/generated");
-    prost_build.compile_protos(&["vector-tile-spec/2.1/vector_tile.proto"], &["."])?;
+    #[cfg(all(feature = "protoc-generated", feature = "mvt3"))]
+    prost_build.out_dir("src/draft");
+    prost_build.compile_protos(&[proto], &["."])?;
   }
   Ok(())
 }
@@ -0,0 +1,181 @@
+//! This module decodes the inlined attributes of the MVT 3 draft specification.
+//!
+//! Inlined attributes are stored on a feature as pairs of a key index into the `keys` of the layer and a
+//! complex value. The lowest four bits of a complex value describe its type, the remaining bits a parameter:
+//!
+//! | Type | Value | Parameter |
+//! |------|-------|-----------|
+//! | 0 | string | index into `string_values` |
+//! | 1 | float | index into `float_values` |
+//! | 2 | double | index into `double_values` |
+//! | 3 | integer | index into `int_values` |
+//! | 4 | unsigned integer | the value itself |
+//! | 5 | signed integer | the zigzag encoded value itself |
+//! | 6 | boolean or null | `0` for false, `1` for true, `2` for null |
+//! | 7 | list | the number of complex values that follow |
+//! | 8 | map | the number of key index and complex value pairs that follow |
+//! | 9 | number list | the number of values that follow after the index of their scaling |
+//!
+//! The values of a number list are delta encoded and scaled with one of the `attribute_scalings` of the
+//! layer. Each value is stored as its zigzag encoded delta plus one, `0` stands for null.
+//!
+//! Geometric attributes store a number list with one value per vertex for each key, the knots of a spline
+//! are stored as a single number list without nulls.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::error;
+use crate::feature::Value;
+use crate::vector_tile::tile::{Layer, Scaling};
+
+/// Decodes the inlined attributes of a feature into pairs of keys and values in encoding order.
+pub(crate) fn parse_attributes(
+  attributes: &[u64],
+  layer: &Layer,
+) -> Result<Vec<(String, Value)>, error::ParserError> {
+  let mut pairs = Vec::new();
+  let mut stream = attributes.iter().copied();
+  while let Some(key_index) = stream.next() {
+    let key = key(layer, key_index)?;
+    let value = parse_value(&mut stream, layer)?;
+    pairs.push((key, value));
+  }
+  Ok(pairs)
+}
+
+/// Decodes the geometric attributes of a feature into one value per vertex for each key.
+pub(crate) fn parse_geometric_attributes(
+  attributes: &[u64],
+  layer: &Layer,
+) -> Result<HashMap<String, Vec<Option<f64>>>, error::ParserError> {
+  let mut result = HashMap::new();
+  let mut stream = attributes.iter().copied();
+  while let Some(key_index) = stream.next() {
+    let key = key(layer, key_index)?;
+    let complex_value = next(&mut stream)?;
+    if complex_value & 0x0f != 9 {
+      return Err(error::ParserError::new(error::TagsError::new()));
+    }
+    let values = parse_number_list(&mut stream, layer, complex_value >> 4)?;
+    result.insert(key, values);
+  }
+  Ok(result)
+}
+
+/// Decodes the knot vector of a spline.
+pub(crate) fn parse_knots(knots: &[u64], layer: &Layer) -> Result<Vec<f64>, error::ParserError> {
+  let mut stream = knots.iter().copied();
+  let complex_value = next(&mut stream)?;
+  if complex_value & 0x0f != 9 {
+    return Err(error::ParserError::new(error::GeometryError::new()));
+  }
+  parse_number_list(&mut stream, layer, complex_value >> 4)?
+    .into_iter()
+    .collect::<Option<Vec<f64>>>()
+    .ok_or_else(|| error::ParserError::new(error::GeometryError::new()))
+}
+
+/// Applies a scaling to an encoded integer.
+pub(crate) fn scale(scaling: Option<&Scaling>, value: i64) -> f64 {
+  match scaling {
+    Some(scaling) => {
+      scaling.base() + scaling.multiplier() * (scaling.offset() as f64 + value as f64)
+    }
+    None => value as f64,
+  }
+}
+
+fn next<I>(stream: &mut I) -> Result<u64, error::ParserError>
+where
+  I: Iterator<Item = u64>,
+{
+  stream
+    .next()
+    .ok_or_else(|| error::ParserError::new(error::TagsError::new()))
+}
+
+fn parse_number_list<I>(
+  stream: &mut I,
+  layer: &Layer,
+  length: u64,
+) -> Result<Vec<Option<f64>>, error::ParserError>
+where
+  I: Iterator<Item = u64>,
+{
+  let scaling_index = next(stream)?;
+  let scaling = layer
+    .attribute_scalings
+    .get(scaling_index as usize)
+    .ok_or_else(|| error::ParserError::new(error::TagsError::new()))?;
+  let mut cursor: i64 = 0;
+  (0..length)
+    .map(|_| match next(stream)? {
+      0 => Ok(None),
+      value => {
+        let delta = value - 1;
+        cursor = cursor.wrapping_add(((delta >> 1) as i64) ^ -((delta & 1) as i64));
+        Ok(Some(scale(Some(scaling), cursor)))
+      }
+    })
+    .collect()
+}
+
+fn key(layer: &Layer, key_index: u64) -> Result<String, error::ParserError> {
+  layer
+    .keys
+    .get(key_index as usize)
+    .cloned()
+    .ok_or_else(|| error::ParserError::new(error::TagsError::new()))
+}
+
+fn lookup<T: Clone>(values: &[T], index: u64) -> Result<T, error::ParserError> {
+  values
+    .get(index as usize)
+    .cloned()
+    .ok_or_else(|| error::ParserError::new(error::TagsError::new()))
+}
+
+fn parse_value<I>(stream: &mut I, layer: &Layer) -> Result<Value, error::ParserError>
+where
+  I: Iterator<Item = u64>,
+{
+  let complex_value = next(stream)?;
+  let parameter = complex_value >> 4;
+  match complex_value & 0x0f {
+    0 => lookup(&layer.string_values, parameter).map(Value::String),
+    1 => lookup(&layer.float_values, parameter).map(Value::Float),
+    2 => lookup(&layer.double_values, parameter).map(Value::Double),
+    3 => lookup(&layer.int_values, parameter).map(Value::Int),
+    4 => Ok(Value::UInt(parameter)),
+    5 => Ok(Value::SInt(
+      ((parameter >> 1) as i64) ^ -((parameter & 1) as i64),
+    )),
+    6 => match parameter {
+      0 => Ok(Value::Bool(false)),
+      1 => Ok(Value::Bool(true)),
+      2 => Ok(Value::Null),
+      _ => Err(error::ParserError::new(error::TagsError::new())),
+    },
+    7 => (0..parameter)
+      .map(|_| parse_value(stream, layer))
+      .collect::<Result<Vec<_>, _>>()
+      .map(Value::List),
+    8 => {
+      let mut map = BTreeMap::new();
+      for _ in 0..parameter {
+        let key_index = next(stream)?;
+        map.insert(key(layer, key_index)?, parse_value(stream, layer)?);
+      }
+      Ok(Value::Map(map))
+    }
+    9 => parse_number_list(stream, layer, parameter).map(|values| {
+      Value::List(
+        values
+          .into_iter()
+          .map(|value| value.map_or(Value::Null, Value::Double))
+          .collect(),
+      )
+    }),
+    _ => Err(error::ParserError::new(error::TagsError::new())),
+  }
+}
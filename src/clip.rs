@@ -0,0 +1,274 @@
+//! This module provides clipping of decoded geometries to an axis-aligned square in tile coordinates.
+//!
+//! Points outside of the square are dropped, lines are cut into separate parts where they leave the
+//! square and polygon rings are clipped with the Sutherland–Hodgman algorithm. Since the clip region
+//! is convex, every ring can be clipped on its own, which keeps the holes of a polygon intact.
+
+use geo_types::{
+  Coord, CoordNum, Geometry, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon,
+};
+
+/// Clips a geometry to the square `[min, max]` on both axes.
+///
+/// The clipping is computed in `f64`, so geometries may have coordinates beyond the range of `i32`, e.g.
+/// after scaling. Only the clipped geometry is rounded to tile coordinates.
+///
+/// Returns `None` if nothing of the geometry is left.
+pub(crate) fn clip_geometry<T: CoordNum>(
+  geometry: Geometry<T>,
+  min: i32,
+  max: i32,
+) -> Option<Geometry<i32>> {
+  let bounds = Bounds {
+    min: min as f64,
+    max: max as f64,
+  };
+  match geometry {
+    Geometry::Point(point) => bounds
+      .contains(point.0)
+      .then(|| Geometry::Point(Point(round(to_f64(point.0))))),
+    Geometry::MultiPoint(points) => {
+      let points: Vec<Point<i32>> = points
+        .into_iter()
+        .filter(|point| bounds.contains(point.0))
+        .map(|point| Point(round(to_f64(point.0))))
+        .collect();
+      (!points.is_empty()).then(|| MultiPoint(points).into())
+    }
+    Geometry::LineString(linestring) => {
+      lines_to_geometry(clip_linestring(&linestring, &bounds), false)
+    }
+    Geometry::MultiLineString(linestrings) => lines_to_geometry(
+      linestrings
+        .iter()
+        .flat_map(|linestring| clip_linestring(linestring, &bounds))
+        .collect(),
+      true,
+    ),
+    Geometry::Polygon(polygon) => {
+      polygons_to_geometry(clip_polygon(&polygon, &bounds).into_iter().collect(), false)
+    }
+    Geometry::MultiPolygon(polygons) => polygons_to_geometry(
+      polygons
+        .iter()
+        .filter_map(|polygon| clip_polygon(polygon, &bounds))
+        .collect(),
+      true,
+    ),
+    // lines, rectangles, triangles and collections do not occur in decoded tiles
+    _ => None,
+  }
+}
+
+fn lines_to_geometry(mut lines: Vec<LineString<i32>>, multi: bool) -> Option<Geometry<i32>> {
+  match lines.len() {
+    0 => None,
+    1 if !multi => Some(lines.remove(0).into()),
+    _ => Some(MultiLineString::new(lines).into()),
+  }
+}
+
+fn polygons_to_geometry(mut polygons: Vec<Polygon<i32>>, multi: bool) -> Option<Geometry<i32>> {
+  match polygons.len() {
+    0 => None,
+    1 if !multi => Some(polygons.remove(0).into()),
+    _ => Some(MultiPolygon::new(polygons).into()),
+  }
+}
+
+struct Bounds {
+  min: f64,
+  max: f64,
+}
+
+impl Bounds {
+  fn contains<T: CoordNum>(&self, coord: Coord<T>) -> bool {
+    let (x, y) = to_f64(coord);
+    x >= self.min && x <= self.max && y >= self.min && y <= self.max
+  }
+}
+
+fn to_f64<T: CoordNum>(coord: Coord<T>) -> (f64, f64) {
+  (
+    coord.x.to_f64().unwrap_or(f64::NAN),
+    coord.y.to_f64().unwrap_or(f64::NAN),
+  )
+}
+
+fn round((x, y): (f64, f64)) -> Coord<i32> {
+  Coord {
+    x: x.round() as i32,
+    y: y.round() as i32,
+  }
+}
+
+/// Pushes a coordinate unless it repeats the previous one.
+fn push_distinct(coords: &mut Vec<Coord<i32>>, coord: Coord<i32>) {
+  if coords.last() != Some(&coord) {
+    coords.push(coord);
+  }
+}
+
+/// Clips a segment with the Liang–Barsky algorithm, returning the parameters of the visible part.
+fn clip_segment((x0, y0): (f64, f64), (x1, y1): (f64, f64), bounds: &Bounds) -> Option<(f64, f64)> {
+  let (dx, dy) = (x1 - x0, y1 - y0);
+  let mut t0: f64 = 0.0;
+  let mut t1: f64 = 1.0;
+  for (p, q) in [
+    (-dx, x0 - bounds.min),
+    (dx, bounds.max - x0),
+    (-dy, y0 - bounds.min),
+    (dy, bounds.max - y0),
+  ] {
+    if p == 0.0 {
+      if q < 0.0 {
+        return None;
+      }
+    } else {
+      let t = q / p;
+      if p < 0.0 {
+        t0 = t0.max(t);
+      } else {
+        t1 = t1.min(t);
+      }
+      if t0 > t1 {
+        return None;
+      }
+    }
+  }
+  Some((t0, t1))
+}
+
+/// Clips a linestring, splitting it into several parts where it leaves the bounds.
+fn clip_linestring<T: CoordNum>(
+  linestring: &LineString<T>,
+  bounds: &Bounds,
+) -> Vec<LineString<i32>> {
+  let mut parts = Vec::new();
+  let mut current: Vec<Coord<i32>> = Vec::new();
+
+  if linestring.0.len() == 1 && bounds.contains(linestring.0[0]) {
+    return vec![LineString::new(vec![round(to_f64(linestring.0[0]))])];
+  }
+
+  for line in linestring.lines() {
+    let (start, end) = (to_f64(line.start), to_f64(line.end));
+    match clip_segment(start, end, bounds) {
+      Some((t0, t1)) => {
+        let (x0, y0) = start;
+        let (dx, dy) = (end.0 - x0, end.1 - y0);
+        push_distinct(&mut current, round((x0 + t0 * dx, y0 + t0 * dy)));
+        push_distinct(&mut current, round((x0 + t1 * dx, y0 + t1 * dy)));
+        if t1 < 1.0 {
+          // the line leaves the bounds
+          finish_part(&mut parts, &mut current);
+        }
+      }
+      None => finish_part(&mut parts, &mut current),
+    }
+  }
+  finish_part(&mut parts, &mut current);
+  parts
+}
+
+fn finish_part(parts: &mut Vec<LineString<i32>>, current: &mut Vec<Coord<i32>>) {
+  if current.len() >= 2 {
+    parts.push(LineString::new(std::mem::take(current)));
+  } else {
+    current.clear();
+  }
+}
+
+#[derive(Clone, Copy)]
+enum Edge {
+  Left,
+  Right,
+  Top,
+  Bottom,
+}
+
+impl Edge {
+  fn inside(&self, (x, y): (f64, f64), bounds: &Bounds) -> bool {
+    match self {
+      Edge::Left => x >= bounds.min,
+      Edge::Right => x <= bounds.max,
+      Edge::Top => y >= bounds.min,
+      Edge::Bottom => y <= bounds.max,
+    }
+  }
+
+  fn intersect(&self, (x0, y0): (f64, f64), (x1, y1): (f64, f64), bounds: &Bounds) -> (f64, f64) {
+    match self {
+      Edge::Left | Edge::Right => {
+        let x = if matches!(self, Edge::Left) {
+          bounds.min
+        } else {
+          bounds.max
+        };
+        (x, y0 + (y1 - y0) * (x - x0) / (x1 - x0))
+      }
+      Edge::Top | Edge::Bottom => {
+        let y = if matches!(self, Edge::Top) {
+          bounds.min
+        } else {
+          bounds.max
+        };
+        (x0 + (x1 - x0) * (y - y0) / (y1 - y0), y)
+      }
+    }
+  }
+}
+
+/// Clips a closed ring with the Sutherland–Hodgman algorithm.
+fn clip_ring<T: CoordNum>(ring: &LineString<T>, bounds: &Bounds) -> Option<LineString<i32>> {
+  // the closing coordinate is restored at the end
+  let mut points: Vec<(f64, f64)> = ring
+    .0
+    .iter()
+    .take(ring.0.len().saturating_sub(1))
+    .map(|coord| to_f64(*coord))
+    .collect();
+
+  for edge in [Edge::Left, Edge::Right, Edge::Top, Edge::Bottom] {
+    if points.is_empty() {
+      break;
+    }
+    let mut output = Vec::with_capacity(points.len() + 4);
+    let mut previous = points[points.len() - 1];
+    for current in points {
+      let current_inside = edge.inside(current, bounds);
+      if current_inside != edge.inside(previous, bounds) {
+        output.push(edge.intersect(previous, current, bounds));
+      }
+      if current_inside {
+        output.push(current);
+      }
+      previous = current;
+    }
+    points = output;
+  }
+
+  let mut coords: Vec<Coord<i32>> = Vec::with_capacity(points.len() + 1);
+  for point in points {
+    push_distinct(&mut coords, round(point));
+  }
+  while coords.len() > 1 && coords.first() == coords.last() {
+    coords.pop();
+  }
+  if coords.len() < 3 {
+    // collapsed ring
+    return None;
+  }
+  coords.push(coords[0]);
+  Some(LineString::new(coords))
+}
+
+fn clip_polygon<T: CoordNum>(polygon: &Polygon<T>, bounds: &Bounds) -> Option<Polygon<i32>> {
+  let exterior = clip_ring(polygon.exterior(), bounds)?;
+  let interiors = polygon
+    .interiors()
+    .iter()
+    .filter_map(|interior| clip_ring(interior, bounds))
+    .collect();
+  Some(Polygon::new(exterior, interiors))
+}
@@ -0,0 +1,234 @@
+//! This module provides a serde `Deserializer` over the properties of a feature.
+//!
+//! The properties of a feature can be deserialized into any type implementing `Deserialize`, e.g. a struct
+//! with one field per property key. Missing keys are reported as missing fields, `Value::Null` and absent
+//! optional fields deserialize to `None`, and integer values are range checked against the target type.
+//!
+//! # Functions
+//!
+//! The `de` module defines the following functions:
+//!
+//! - `from_properties`: Deserializes a type from the keys and values of a feature.
+//! - `from_value`: Deserializes a type from a single property value.
+//!
+//! [`Reader::deserialize_features`](crate::Reader::deserialize_features) deserializes the properties of all
+//! features of a layer and attaches the layer name and the feature to the error.
+
+use serde::de::value::{Error, MapDeserializer, SeqDeserializer};
+use serde::de::{
+  self, DeserializeOwned, DeserializeSeed, Deserializer, Error as _, IntoDeserializer, MapAccess,
+  Visitor,
+};
+
+use crate::feature::Value;
+
+/// Deserializes a type from the keys and values of a feature.
+///
+/// Type mismatches name the property key, e.g. ``property `lanes`: invalid type: string "two", expected u8``.
+///
+/// # Arguments
+///
+/// * `properties` - The keys and values of the feature.
+///
+/// # Returns
+///
+/// A result containing the deserialized value, or a serde error describing the mismatch.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use mvt_reader::{de::from_properties, feature::Value};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Road {
+///   class: String,
+///   lanes: Option<u8>,
+/// }
+///
+/// let properties = HashMap::from([(String::from("class"), Value::String(String::from("primary")))]);
+/// let road: Road = from_properties(properties).unwrap();
+/// assert_eq!(road.class, "primary");
+/// assert_eq!(road.lanes, None);
+/// ```
+pub fn from_properties<R, I>(properties: I) -> Result<R, Error>
+where
+  R: DeserializeOwned,
+  I: IntoIterator<Item = (String, Value)>,
+{
+  R::deserialize(PropertiesDeserializer {
+    properties: properties.into_iter(),
+    key: None,
+  })
+}
+
+/// Deserializes a type from a single property value.
+///
+/// # Arguments
+///
+/// * `value` - The property value.
+///
+/// # Returns
+///
+/// A result containing the deserialized value, or a serde error describing the mismatch.
+///
+/// # Examples
+///
+/// ```
+/// use mvt_reader::{de::from_value, feature::Value};
+///
+/// let lanes: u8 = from_value(Value::UInt(2)).unwrap();
+/// assert_eq!(lanes, 2);
+/// ```
+pub fn from_value<R: DeserializeOwned>(value: Value) -> Result<R, Error> {
+  R::deserialize(value)
+}
+
+/// A deserializer presenting the properties of a feature as a map.
+struct PropertiesDeserializer<I> {
+  properties: I,
+
+  /// The key of the value that is deserialized next, used to name the property in errors.
+  key: Option<(String, Value)>,
+}
+
+impl<'de, I> Deserializer<'de> for PropertiesDeserializer<I>
+where
+  I: Iterator<Item = (String, Value)>,
+{
+  type Error = Error;
+
+  fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+    visitor.visit_map(self)
+  }
+
+  serde::forward_to_deserialize_any! {
+    bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+    bytes byte_buf option unit unit_struct newtype_struct seq tuple
+    tuple_struct map struct enum identifier ignored_any
+  }
+}
+
+impl<'de, I> MapAccess<'de> for PropertiesDeserializer<I>
+where
+  I: Iterator<Item = (String, Value)>,
+{
+  type Error = Error;
+
+  fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Error> {
+    match self.properties.next() {
+      Some((key, value)) => {
+        let result = seed.deserialize(key.as_str().into_deserializer()).map(Some);
+        self.key = Some((key, value));
+        result
+      }
+      None => Ok(None),
+    }
+  }
+
+  fn next_value_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<S::Value, Error> {
+    let (key, value) = self
+      .key
+      .take()
+      .ok_or_else(|| Error::custom("value is missing"))?;
+    seed
+      .deserialize(value)
+      .map_err(|error| Error::custom(format!("property `{}`: {}", key, error)))
+  }
+
+  fn size_hint(&self) -> Option<usize> {
+    self.properties.size_hint().1
+  }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Value {
+  type Deserializer = Self;
+
+  fn into_deserializer(self) -> Self {
+    self
+  }
+}
+
+impl<'de> Deserializer<'de> for Value {
+  type Error = Error;
+
+  fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+    match self {
+      Value::String(s) => visitor.visit_string(s),
+      Value::Float(f) => visitor.visit_f32(f),
+      Value::Double(d) => visitor.visit_f64(d),
+      Value::Int(i) | Value::SInt(i) => visitor.visit_i64(i),
+      Value::UInt(u) => visitor.visit_u64(u),
+      Value::Bool(b) => visitor.visit_bool(b),
+      Value::Null => visitor.visit_unit(),
+      Value::List(values) => visitor.visit_seq(SeqDeserializer::new(values.into_iter())),
+      Value::Map(map) => visitor.visit_map(MapDeserializer::new(map.into_iter())),
+    }
+  }
+
+  fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+    match self {
+      Value::Null => visitor.visit_none(),
+      value => visitor.visit_some(value),
+    }
+  }
+
+  fn deserialize_newtype_struct<V: Visitor<'de>>(
+    self,
+    _name: &'static str,
+    visitor: V,
+  ) -> Result<V::Value, Error> {
+    visitor.visit_newtype_struct(self)
+  }
+
+  fn deserialize_enum<V: Visitor<'de>>(
+    self,
+    name: &'static str,
+    variants: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value, Error> {
+    match self {
+      // unit variants are stored as strings
+      Value::String(variant) => visitor.visit_enum(variant.into_deserializer()),
+      value => value.deserialize_any(EnumVisitor {
+        name,
+        variants,
+        visitor,
+      }),
+    }
+  }
+
+  serde::forward_to_deserialize_any! {
+    bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+    bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+    identifier ignored_any
+  }
+}
+
+/// Forwards a value that is not a string to the visitor of an enum, reporting the mismatch.
+struct EnumVisitor<V> {
+  name: &'static str,
+  variants: &'static [&'static str],
+  visitor: V,
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for EnumVisitor<V> {
+  type Value = V::Value;
+
+  fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    write!(
+      f,
+      "a variant of enum {} ({})",
+      self.name,
+      self.variants.join(", ")
+    )
+  }
+
+  fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+    // externally tagged variants with data are stored as single entry maps
+    self
+      .visitor
+      .visit_enum(de::value::MapAccessDeserializer::new(map))
+  }
+}
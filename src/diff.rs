@@ -0,0 +1,465 @@
+//! This module provides a semantic comparison of two vector tiles.
+//!
+//! Layers are matched by name. Within a layer, features are matched by id and features without an id by a
+//! hash of their geometry, so that reordering features or re-encoding a tile does not show up as a change.
+//! Features with the same id are matched in layer order. Geometries are compared in the tile coordinates of
+//! the first tile, geometries of a layer with a different extent are scaled accordingly. Property values
+//! are compared by what they represent, so a number stored as `Int(5)` in one tile and as `UInt(5)` in the
+//! other is not a change.
+//!
+//! The `Display` implementation of `TileDiff` renders a line based report, e.g. for a command line tool:
+//!
+//! ```text
+//! + layer water
+//! ~ layer roads
+//!   extent 4096 -> 8192
+//!   + feature id 12
+//!   ~ feature id 7
+//!     class: primary -> secondary
+//!     + lanes: 2
+//!     geometry moved by up to 3.5
+//! ```
+//!
+//! # Types
+//!
+//! The `diff` module defines the following types:
+//!
+//! - `TileDiff`: Represents the differences between two tiles, as returned by [`diff`].
+//! - `LayerDiff`: Represents the differences of a layer present in both tiles.
+//! - `FeatureKey`: Represents how a feature was matched.
+//! - `FeatureChange`: Represents the differences of a feature present in both tiles.
+//! - `PropertyChange`: Represents an added, removed or changed property.
+//! - `GeometryChange`: Represents how a geometry changed.
+
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use geo_types::{Coord, Geometry, LineString, Polygon};
+
+use crate::Reader;
+use crate::error::ParserError;
+use crate::feature::{Feature, Value, ValueKey};
+use crate::layer::Layer;
+
+/// A structure representing the differences between two vector tiles.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TileDiff {
+  /// The names of the layers only present in the second tile.
+  pub added_layers: Vec<String>,
+
+  /// The names of the layers only present in the first tile.
+  pub removed_layers: Vec<String>,
+
+  /// The layers present in both tiles that differ, in the order of the first tile.
+  pub layers: Vec<LayerDiff>,
+}
+
+impl TileDiff {
+  /// Returns `true` if the tiles do not differ.
+  pub fn is_empty(&self) -> bool {
+    self.added_layers.is_empty() && self.removed_layers.is_empty() && self.layers.is_empty()
+  }
+}
+
+/// A structure representing the differences of a layer that is present in both tiles.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LayerDiff {
+  /// The name of the layer.
+  pub name: String,
+
+  /// The extents of the layer in the first and second tile, if they differ.
+  pub extent: Option<(u32, u32)>,
+
+  /// The versions of the layer in the first and second tile, if they differ.
+  pub version: Option<(u32, u32)>,
+
+  /// The features only present in the second tile.
+  pub added: Vec<FeatureKey>,
+
+  /// The features only present in the first tile.
+  pub removed: Vec<FeatureKey>,
+
+  /// The features present in both tiles that differ.
+  pub modified: Vec<FeatureChange>,
+}
+
+impl LayerDiff {
+  /// Returns `true` if the layer does not differ.
+  pub fn is_empty(&self) -> bool {
+    self.extent.is_none()
+      && self.version.is_none()
+      && self.added.is_empty()
+      && self.removed.is_empty()
+      && self.modified.is_empty()
+  }
+}
+
+/// An enumeration describing how a feature was matched between the tiles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FeatureKey {
+  /// The feature has the given id.
+  Id(u64),
+
+  /// The feature has no id and a geometry with the given hash.
+  Geometry(u64),
+}
+
+impl core::fmt::Display for FeatureKey {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self {
+      FeatureKey::Id(id) => write!(f, "id {}", id),
+      FeatureKey::Geometry(hash) => write!(f, "geometry #{:016x}", hash),
+    }
+  }
+}
+
+/// A structure representing the differences of a feature that is present in both tiles.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureChange {
+  /// The key the feature was matched by.
+  pub key: FeatureKey,
+
+  /// The changed properties, sorted by key.
+  pub properties: Vec<PropertyChange>,
+
+  /// The change of the geometry, if it changed beyond the tolerance.
+  pub geometry: Option<GeometryChange>,
+}
+
+/// A structure representing a property that was added, removed or changed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyChange {
+  /// The key of the property.
+  pub key: String,
+
+  /// The value in the first tile, `None` if the property was added.
+  pub before: Option<Value>,
+
+  /// The value in the second tile, `None` if the property was removed.
+  pub after: Option<Value>,
+}
+
+/// An enumeration describing how the geometry of a feature changed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeometryChange {
+  /// The geometry type changed, e.g. from `"LineString"` to `"MultiLineString"`.
+  Type {
+    before: &'static str,
+    after: &'static str,
+  },
+
+  /// The number of vertices changed.
+  Vertices { before: usize, after: usize },
+
+  /// The vertices moved, by at most the given distance in tile coordinates.
+  Moved { max_distance: f64 },
+}
+
+/// Compares two vector tiles, treating any geometry change as a change.
+///
+/// # Arguments
+///
+/// * `a` - The reader of the first, e.g. the old, tile.
+/// * `b` - The reader of the second, e.g. the new, tile.
+///
+/// # Returns
+///
+/// A result containing the differences if successful, or a `ParserError` if there is an error parsing one of the tiles.
+///
+/// # Examples
+///
+/// ```
+/// use mvt_reader::Reader;
+///
+/// let old = Reader::new(vec![/* Vector tile data */]).unwrap();
+/// let new = Reader::new(vec![/* Vector tile data */]).unwrap();
+///
+/// let diff = mvt_reader::diff(&old, &new).unwrap();
+/// if !diff.is_empty() {
+///   print!("{}", diff);
+/// }
+/// ```
+pub fn diff(a: &Reader, b: &Reader) -> Result<TileDiff, ParserError> {
+  diff_with_tolerance(a, b, 0.0)
+}
+
+/// Compares two vector tiles, ignoring vertices that moved by at most the given tolerance.
+///
+/// # Arguments
+///
+/// * `a` - The reader of the first, e.g. the old, tile.
+/// * `b` - The reader of the second, e.g. the new, tile.
+/// * `tolerance` - The distance in tile coordinates of the first tile a vertex may move without being reported.
+///
+/// # Returns
+///
+/// A result containing the differences if successful, or a `ParserError` if there is an error parsing one of the tiles.
+pub fn diff_with_tolerance(
+  a: &Reader,
+  b: &Reader,
+  tolerance: f64,
+) -> Result<TileDiff, ParserError> {
+  let layers_a = a.get_layer_metadata()?;
+  let layers_b = b.get_layer_metadata()?;
+  let find = |layers: &[Layer], name: &str| -> Option<usize> {
+    layers.iter().position(|layer| layer.name == name)
+  };
+
+  let mut result = TileDiff::default();
+  for layer_a in &layers_a {
+    match find(&layers_b, &layer_a.name) {
+      Some(index) => {
+        let layer_diff = diff_layers(a, layer_a, b, &layers_b[index], tolerance)?;
+        if !layer_diff.is_empty() {
+          result.layers.push(layer_diff);
+        }
+      }
+      None => result.removed_layers.push(layer_a.name.clone()),
+    }
+  }
+  for layer_b in &layers_b {
+    if find(&layers_a, &layer_b.name).is_none() {
+      result.added_layers.push(layer_b.name.clone());
+    }
+  }
+  Ok(result)
+}
+
+fn diff_layers(
+  a: &Reader,
+  layer_a: &Layer,
+  b: &Reader,
+  layer_b: &Layer,
+  tolerance: f64,
+) -> Result<LayerDiff, ParserError> {
+  let features_a = a.get_features_as::<f64>(layer_a.layer_index)?;
+  let scale = layer_a.extent as f64 / layer_b.extent.max(1) as f64;
+  let features_b = b
+    .get_features_as::<f64>(layer_b.layer_index)?
+    .into_iter()
+    .map(|feature| Feature {
+      geometry: crate::map_coords(&feature.geometry, &|coord: Coord<f64>| Coord {
+        x: coord.x * scale,
+        y: coord.y * scale,
+      }),
+      ..feature
+    })
+    .collect::<Vec<_>>();
+
+  let mut layer_diff = LayerDiff {
+    name: layer_a.name.clone(),
+    extent: (layer_a.extent != layer_b.extent).then_some((layer_a.extent, layer_b.extent)),
+    version: (layer_a.version != layer_b.version).then_some((layer_a.version, layer_b.version)),
+    ..LayerDiff::default()
+  };
+
+  // the features of the second tile by key, consumed in layer order as they are matched
+  let mut unmatched: HashMap<FeatureKey, VecDeque<&Feature<f64>>> = HashMap::new();
+  let mut keys_b = Vec::with_capacity(features_b.len());
+  for feature in &features_b {
+    let key = feature_key(feature);
+    unmatched.entry(key).or_default().push_back(feature);
+    keys_b.push(key);
+  }
+
+  for feature_a in &features_a {
+    let key = feature_key(feature_a);
+    match unmatched.get_mut(&key).and_then(VecDeque::pop_front) {
+      Some(feature_b) => {
+        if let Some(change) = diff_features(key, feature_a, feature_b, tolerance) {
+          layer_diff.modified.push(change);
+        }
+      }
+      None => layer_diff.removed.push(key),
+    }
+  }
+  // report the remaining features of the second tile in layer order
+  for key in keys_b {
+    if let Some(features) = unmatched.get_mut(&key)
+      && features.pop_front().is_some()
+    {
+      layer_diff.added.push(key);
+    }
+  }
+  Ok(layer_diff)
+}
+
+fn diff_features(
+  key: FeatureKey,
+  a: &Feature<f64>,
+  b: &Feature<f64>,
+  tolerance: f64,
+) -> Option<FeatureChange> {
+  let empty = HashMap::new();
+  let properties_a = a.properties.as_ref().unwrap_or(&empty);
+  let properties_b = b.properties.as_ref().unwrap_or(&empty);
+  let properties = properties_a
+    .keys()
+    .chain(properties_b.keys())
+    .collect::<BTreeSet<_>>()
+    .into_iter()
+    .filter_map(|property| {
+      let before = properties_a.get(property);
+      let after = properties_b.get(property);
+      // numeric variants of the same number are equal, e.g. after re-encoding a tile
+      let changed = before.map(ValueKey::from) != after.map(ValueKey::from);
+      changed.then(|| PropertyChange {
+        key: property.clone(),
+        before: before.cloned(),
+        after: after.cloned(),
+      })
+    })
+    .collect::<Vec<_>>();
+  let geometry = diff_geometries(&a.geometry, &b.geometry, tolerance);
+
+  if properties.is_empty() && geometry.is_none() {
+    None
+  } else {
+    Some(FeatureChange {
+      key,
+      properties,
+      geometry,
+    })
+  }
+}
+
+fn diff_geometries(a: &Geometry<f64>, b: &Geometry<f64>, tolerance: f64) -> Option<GeometryChange> {
+  let (type_a, type_b) = (geometry_type(a), geometry_type(b));
+  if type_a != type_b {
+    return Some(GeometryChange::Type {
+      before: type_a,
+      after: type_b,
+    });
+  }
+  let (coords_a, coords_b) = (coordinates(a), coordinates(b));
+  if coords_a.len() != coords_b.len() {
+    return Some(GeometryChange::Vertices {
+      before: coords_a.len(),
+      after: coords_b.len(),
+    });
+  }
+  let max_distance = coords_a
+    .iter()
+    .zip(&coords_b)
+    .map(|(a, b)| (a.x - b.x).hypot(a.y - b.y))
+    .fold(0.0, f64::max);
+  (max_distance > tolerance).then_some(GeometryChange::Moved { max_distance })
+}
+
+fn feature_key(feature: &Feature<f64>) -> FeatureKey {
+  match feature.id {
+    Some(id) => FeatureKey::Id(id),
+    None => {
+      let mut hasher = DefaultHasher::new();
+      geometry_type(&feature.geometry).hash(&mut hasher);
+      for coord in coordinates(&feature.geometry) {
+        // scaled coordinates are rounded back onto the grid of the first tile
+        (coord.x.round() as i64, coord.y.round() as i64).hash(&mut hasher);
+      }
+      FeatureKey::Geometry(hasher.finish())
+    }
+  }
+}
+
+fn geometry_type(geometry: &Geometry<f64>) -> &'static str {
+  match geometry {
+    Geometry::Point(_) => "Point",
+    Geometry::MultiPoint(_) => "MultiPoint",
+    Geometry::Line(_) => "Line",
+    Geometry::LineString(_) => "LineString",
+    Geometry::MultiLineString(_) => "MultiLineString",
+    Geometry::Polygon(_) => "Polygon",
+    Geometry::MultiPolygon(_) => "MultiPolygon",
+    Geometry::Rect(_) => "Rect",
+    Geometry::Triangle(_) => "Triangle",
+    Geometry::GeometryCollection(_) => "GeometryCollection",
+  }
+}
+
+/// Returns the vertices of a decoded geometry, including the interior rings of polygons.
+fn coordinates(geometry: &Geometry<f64>) -> Vec<Coord<f64>> {
+  fn polygon_coords(polygon: &Polygon<f64>) -> impl Iterator<Item = &Coord<f64>> {
+    std::iter::once(polygon.exterior())
+      .chain(polygon.interiors())
+      .flat_map(|ring: &LineString<f64>| ring.0.iter())
+  }
+
+  match geometry {
+    Geometry::Point(point) => vec![point.0],
+    Geometry::MultiPoint(points) => points.iter().map(|point| point.0).collect(),
+    Geometry::LineString(linestring) => linestring.0.clone(),
+    Geometry::MultiLineString(linestrings) => linestrings
+      .iter()
+      .flat_map(|linestring| linestring.0.iter().copied())
+      .collect(),
+    Geometry::Polygon(polygon) => polygon_coords(polygon).copied().collect(),
+    Geometry::MultiPolygon(polygons) => polygons
+      .iter()
+      .flat_map(|polygon| polygon_coords(polygon).copied())
+      .collect(),
+    // other geometries are never decoded from a tile
+    _ => Vec::new(),
+  }
+}
+
+impl core::fmt::Display for TileDiff {
+  /// Formats the differences as a line based report.
+  ///
+  /// Added entries are prefixed with `+`, removed entries with `-` and changed entries with `~`.
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    for name in &self.added_layers {
+      writeln!(f, "+ layer {}", name)?;
+    }
+    for name in &self.removed_layers {
+      writeln!(f, "- layer {}", name)?;
+    }
+    for layer in &self.layers {
+      write!(f, "{}", layer)?;
+    }
+    Ok(())
+  }
+}
+
+impl core::fmt::Display for LayerDiff {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    writeln!(f, "~ layer {}", self.name)?;
+    if let Some((before, after)) = self.extent {
+      writeln!(f, "  extent {} -> {}", before, after)?;
+    }
+    if let Some((before, after)) = self.version {
+      writeln!(f, "  version {} -> {}", before, after)?;
+    }
+    for key in &self.added {
+      writeln!(f, "  + feature {}", key)?;
+    }
+    for key in &self.removed {
+      writeln!(f, "  - feature {}", key)?;
+    }
+    for change in &self.modified {
+      writeln!(f, "  ~ feature {}", change.key)?;
+      for property in &change.properties {
+        match (&property.before, &property.after) {
+          (Some(before), Some(after)) => {
+            writeln!(f, "    {}: {} -> {}", property.key, before, after)?
+          }
+          (None, Some(after)) => writeln!(f, "    + {}: {}", property.key, after)?,
+          (Some(before), None) => writeln!(f, "    - {}: {}", property.key, before)?,
+          (None, None) => (),
+        }
+      }
+      match &change.geometry {
+        Some(GeometryChange::Type { before, after }) => {
+          writeln!(f, "    geometry type {} -> {}", before, after)?
+        }
+        Some(GeometryChange::Vertices { before, after }) => {
+          writeln!(f, "    geometry vertices {} -> {}", before, after)?
+        }
+        Some(GeometryChange::Moved { max_distance }) => {
+          writeln!(f, "    geometry moved by up to {}", max_distance)?
+        }
+        None => (),
+      }
+    }
+    Ok(())
+  }
+}
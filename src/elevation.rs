@@ -0,0 +1,147 @@
+//! This module provides the three-dimensional feature types of the MVT 3 draft specification.
+//!
+//! The draft specification keeps the `x` and `y` coordinates in the geometry of a feature and adds a
+//! separate stream with the elevation of each vertex, as well as geometric attributes with one value per
+//! vertex. The types of this module combine them, as `geo_types` only supports two dimensions.
+//!
+//! # Types
+//!
+//! The `elevation` module defines the following types:
+//!
+//! - `Coord3D`: Represents a vertex with tile coordinates and an elevation.
+//! - `Geometry3D`: Represents a geometry built from `Coord3D` vertices.
+//! - `Feature3D`: Represents a feature with a three-dimensional geometry and per-vertex attributes.
+
+use std::collections::HashMap;
+
+use geo_types::{Coord, CoordNum, Geometry, LineString, Polygon};
+
+use crate::feature::Value;
+
+/// A structure representing a vertex with an elevation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Coord3D<T: CoordNum = f32> {
+  /// The x coordinate in tile coordinates.
+  pub x: T,
+
+  /// The y coordinate in tile coordinates.
+  pub y: T,
+
+  /// The elevation after applying the elevation scaling of the layer, `0` if the feature has no elevations.
+  pub z: f64,
+}
+
+/// An enumeration representing a geometry with three-dimensional vertices.
+///
+/// Rings of polygons are closed, the first ring of a polygon is its exterior ring.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Geometry3D<T: CoordNum = f32> {
+  Point(Coord3D<T>),
+  MultiPoint(Vec<Coord3D<T>>),
+  LineString(Vec<Coord3D<T>>),
+  MultiLineString(Vec<Vec<Coord3D<T>>>),
+  Polygon(Vec<Vec<Coord3D<T>>>),
+  MultiPolygon(Vec<Vec<Vec<Coord3D<T>>>>),
+}
+
+/// A structure representing a feature with a three-dimensional geometry.
+#[derive(Debug, Clone)]
+pub struct Feature3D<T: CoordNum = f32> {
+  /// The geometry of the feature.
+  pub geometry: Geometry3D<T>,
+
+  /// Optional identifier for the feature.
+  pub id: Option<u64>,
+
+  /// Optional properties associated with the feature.
+  pub properties: Option<HashMap<String, Value>>,
+
+  /// The geometric attributes of the feature, with one value per encoded vertex for each key.
+  ///
+  /// Vertices are counted in encoding order, the closing vertices of polygon rings are not encoded.
+  pub geometric_attributes: HashMap<String, Vec<Option<f64>>>,
+}
+
+/// Counts the encoded vertices of a geometry, excluding the closing vertices of polygon rings.
+pub(crate) fn vertex_count<T: CoordNum>(geometry: &Geometry<T>) -> usize {
+  let ring_count = |polygon: &Polygon<T>| {
+    polygon.exterior().0.len() - 1
+      + polygon
+        .interiors()
+        .iter()
+        .map(|ring| ring.0.len() - 1)
+        .sum::<usize>()
+  };
+  match geometry {
+    Geometry::Point(_) => 1,
+    Geometry::MultiPoint(points) => points.0.len(),
+    Geometry::LineString(linestring) => linestring.0.len(),
+    Geometry::MultiLineString(linestrings) => linestrings.iter().map(|line| line.0.len()).sum(),
+    Geometry::Polygon(polygon) => ring_count(polygon),
+    Geometry::MultiPolygon(polygons) => polygons.iter().map(ring_count).sum(),
+    _ => 0,
+  }
+}
+
+/// Combines a decoded geometry with the elevation of each encoded vertex.
+///
+/// An empty list of elevations places all vertices at elevation `0`. Returns `None` for geometry types
+/// that cannot be decoded from a vector tile.
+pub(crate) fn elevate<T: CoordNum>(
+  geometry: Geometry<T>,
+  elevations: &[f64],
+) -> Option<Geometry3D<T>> {
+  let mut elevations = elevations.iter().copied();
+  let mut next = |coord: &Coord<T>| Coord3D {
+    x: coord.x,
+    y: coord.y,
+    z: elevations.next().unwrap_or(0.0),
+  };
+  Some(match geometry {
+    Geometry::Point(point) => Geometry3D::Point(next(&point.0)),
+    Geometry::MultiPoint(points) => {
+      Geometry3D::MultiPoint(points.iter().map(|point| next(&point.0)).collect())
+    }
+    Geometry::LineString(linestring) => {
+      Geometry3D::LineString(elevate_line(&linestring, &mut next))
+    }
+    Geometry::MultiLineString(linestrings) => Geometry3D::MultiLineString(
+      linestrings
+        .iter()
+        .map(|linestring| elevate_line(linestring, &mut next))
+        .collect(),
+    ),
+    Geometry::Polygon(polygon) => Geometry3D::Polygon(elevate_polygon(&polygon, &mut next)),
+    Geometry::MultiPolygon(polygons) => Geometry3D::MultiPolygon(
+      polygons
+        .iter()
+        .map(|polygon| elevate_polygon(polygon, &mut next))
+        .collect(),
+    ),
+    _ => return None,
+  })
+}
+
+fn elevate_line<T, F>(linestring: &LineString<T>, next: &mut F) -> Vec<Coord3D<T>>
+where
+  T: CoordNum,
+  F: FnMut(&Coord<T>) -> Coord3D<T>,
+{
+  linestring.0.iter().map(next).collect()
+}
+
+fn elevate_polygon<T, F>(polygon: &Polygon<T>, next: &mut F) -> Vec<Vec<Coord3D<T>>>
+where
+  T: CoordNum,
+  F: FnMut(&Coord<T>) -> Coord3D<T>,
+{
+  std::iter::once(polygon.exterior())
+    .chain(polygon.interiors())
+    .map(|ring| {
+      // the closing vertex is not encoded and repeats the first one
+      let mut coords: Vec<Coord3D<T>> = ring.0[..ring.0.len() - 1].iter().map(&mut *next).collect();
+      coords.push(coords[0]);
+      coords
+    })
+    .collect()
+}
@@ -0,0 +1,107 @@
+//! This module provides encoding of geometries into the command stream of the vector tile specification.
+//!
+//! Polygon rings are written as given, so the winding order of the rings must already follow the
+//! specification: exterior rings have a positive area and interior rings a negative area in tile
+//! coordinates.
+
+use geo_types::{Coord, Geometry, LineString, Point, Polygon};
+
+const MOVE_TO: u32 = 1;
+const LINE_TO: u32 = 2;
+const CLOSE_PATH: u32 = 7;
+
+/// Encodes a geometry into geometry commands.
+///
+/// Geometries that have no representation in a vector tile, like a `GeometryCollection`, result in an empty command stream.
+pub(crate) fn encode_geometry(geometry: &Geometry<i32>) -> Vec<u32> {
+  let mut encoder = Encoder::default();
+  match geometry {
+    Geometry::Point(point) => encoder.points(&[*point]),
+    Geometry::MultiPoint(points) => encoder.points(&points.0),
+    Geometry::LineString(linestring) => encoder.linestring(linestring),
+    Geometry::MultiLineString(linestrings) => {
+      for linestring in linestrings {
+        encoder.linestring(linestring);
+      }
+    }
+    Geometry::Polygon(polygon) => encoder.polygon(polygon),
+    Geometry::MultiPolygon(polygons) => {
+      for polygon in polygons {
+        encoder.polygon(polygon);
+      }
+    }
+    _ => (),
+  }
+  encoder.data
+}
+
+fn command(id: u32, count: usize) -> u32 {
+  (id & 0x7) | ((count as u32) << 3)
+}
+
+fn zigzag(value: i32) -> u32 {
+  ((value << 1) ^ (value >> 31)) as u32
+}
+
+#[derive(Default)]
+struct Encoder {
+  data: Vec<u32>,
+  cursor: Coord<i32>,
+}
+
+impl Encoder {
+  fn parameter(&mut self, coord: Coord<i32>) {
+    self.data.push(zigzag(coord.x.wrapping_sub(self.cursor.x)));
+    self.data.push(zigzag(coord.y.wrapping_sub(self.cursor.y)));
+    self.cursor = coord;
+  }
+
+  fn points(&mut self, points: &[Point<i32>]) {
+    if points.is_empty() {
+      return;
+    }
+    self.data.push(command(MOVE_TO, points.len()));
+    for point in points {
+      self.parameter(point.0);
+    }
+  }
+
+  fn linestring(&mut self, linestring: &LineString<i32>) {
+    let Some((first, rest)) = linestring.0.split_first() else {
+      return;
+    };
+    self.data.push(command(MOVE_TO, 1));
+    self.parameter(*first);
+    if !rest.is_empty() {
+      self.data.push(command(LINE_TO, rest.len()));
+      for coord in rest {
+        self.parameter(*coord);
+      }
+    }
+  }
+
+  fn ring(&mut self, ring: &LineString<i32>) {
+    // the closing coordinate is replaced by the ClosePath command
+    let coords = match ring.0.split_last() {
+      Some((last, coords)) if Some(last) == ring.0.first() => coords,
+      _ => &ring.0[..],
+    };
+    if coords.len() < 3 {
+      return;
+    }
+    self.data.push(command(MOVE_TO, 1));
+    self.parameter(coords[0]);
+    self.data.push(command(LINE_TO, coords.len() - 1));
+    for coord in &coords[1..] {
+      self.parameter(*coord);
+    }
+    self.data.push(command(CLOSE_PATH, 1));
+  }
+
+  fn polygon(&mut self, polygon: &Polygon<i32>) {
+    self.ring(polygon.exterior());
+    for interior in polygon.interiors() {
+      self.ring(interior);
+    }
+  }
+}
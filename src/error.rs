@@ -9,15 +9,76 @@
 //! - `TagsError`: Represents an error related to the tags of a feature in a vector tile.
 //! - `VersionError`: Represents an error related to the version of a vector tile.
 //! - `DecodeError`: Represents an error indicating a decoding failure during the parsing of a vector tile.
+//! - `FilterError`: Represents an error in a style-spec filter expression.
+//! - `OverzoomError`: Represents an error in the tile coordinates passed to an overzoom.
+//! - `ValueError`: Represents a property value that cannot be converted to the requested type.
+//! - `DeserializeError`: Represents properties of a feature that cannot be deserialized into the requested type.
+//!
+//! The `ErrorKind` enumeration classifies a `ParserError` by its source without downcasting.
 //!
 //! # Utilities
 //!
 //! The `error` module also provides utility functions and traits for working with errors, such as formatting and error chaining.
 
 /// A structure representing a parser error.
-#[derive(Debug)]
 pub struct ParserError {
   source: Box<dyn core::error::Error>,
+
+  /// The index of the layer in which the error occurred, if known.
+  layer_index: Option<usize>,
+
+  /// The index of the feature in which the error occurred, if known.
+  feature_index: Option<usize>,
+}
+
+/// An enumeration classifying the source of a `ParserError`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+  /// The vector tile data could not be decoded, see `DecodeError`.
+  Decode,
+
+  /// A layer has an unsupported version, see `VersionError`.
+  Version,
+
+  /// The tags of a feature are invalid, see `TagsError`.
+  Tags,
+
+  /// The geometry of a feature is invalid, see `GeometryError`.
+  Geometry,
+
+  /// A filter expression is invalid, see `FilterError`.
+  Filter,
+
+  /// The tile coordinates of an overzoom are invalid, see `OverzoomError`.
+  Overzoom,
+
+  /// The properties of a feature cannot be deserialized, see `DeserializeError`.
+  Deserialize,
+
+  /// Any other error source.
+  Other,
+}
+
+impl ErrorKind {
+  /// Returns the name of the kind in lower case, e.g. `"geometry"`.
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      ErrorKind::Decode => "decode",
+      ErrorKind::Version => "version",
+      ErrorKind::Tags => "tags",
+      ErrorKind::Geometry => "geometry",
+      ErrorKind::Filter => "filter",
+      ErrorKind::Overzoom => "overzoom",
+      ErrorKind::Deserialize => "deserialize",
+      ErrorKind::Other => "other",
+    }
+  }
+}
+
+impl core::fmt::Display for ErrorKind {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.write_str(self.as_str())
+  }
 }
 
 impl ParserError {
@@ -38,8 +99,72 @@ impl ParserError {
   pub fn new<T: core::error::Error + 'static>(source: T) -> Self {
     Self {
       source: Box::new(source),
+      layer_index: None,
+      feature_index: None,
+    }
+  }
+
+  /// Returns the kind of the underlying error source.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::error::{ErrorKind, GeometryError, ParserError};
+  ///
+  /// let parser_error = ParserError::new(GeometryError::new());
+  /// assert_eq!(parser_error.kind(), ErrorKind::Geometry);
+  /// ```
+  pub fn kind(&self) -> ErrorKind {
+    let source = self.source.as_ref();
+    if source.is::<DecodeError>() {
+      ErrorKind::Decode
+    } else if source.is::<VersionError>() {
+      ErrorKind::Version
+    } else if source.is::<TagsError>() {
+      ErrorKind::Tags
+    } else if source.is::<GeometryError>() {
+      ErrorKind::Geometry
+    } else if source.is::<FilterError>() {
+      ErrorKind::Filter
+    } else if source.is::<OverzoomError>() {
+      ErrorKind::Overzoom
+    } else if source.is::<DeserializeError>() {
+      ErrorKind::Deserialize
+    } else {
+      ErrorKind::Other
     }
   }
+
+  /// Returns the index of the layer in which the error occurred, if known.
+  pub fn layer_index(&self) -> Option<usize> {
+    self.layer_index
+  }
+
+  /// Returns the index of the feature in which the error occurred, if known.
+  pub fn feature_index(&self) -> Option<usize> {
+    self.feature_index
+  }
+
+  /// Attaches the position of the layer and optionally the feature in which the error occurred.
+  pub(crate) fn with_context(mut self, layer_index: usize, feature_index: Option<usize>) -> Self {
+    self.layer_index = Some(layer_index);
+    self.feature_index = feature_index.or(self.feature_index);
+    self
+  }
+}
+
+impl core::fmt::Debug for ParserError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut debug = f.debug_struct("ParserError");
+    debug.field("source", &self.source);
+    if let Some(layer_index) = self.layer_index {
+      debug.field("layer_index", &layer_index);
+    }
+    if let Some(feature_index) = self.feature_index {
+      debug.field("feature_index", &feature_index);
+    }
+    debug.finish()
+  }
 }
 
 impl core::fmt::Display for ParserError {
@@ -60,7 +185,14 @@ impl core::fmt::Display for ParserError {
   /// println!("{}", parser_error);
   /// ```
   fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-    self.source.fmt(f)
+    self.source.fmt(f)?;
+    match (self.layer_index, self.feature_index) {
+      (Some(layer_index), Some(feature_index)) => {
+        write!(f, " (layer {}, feature {})", layer_index, feature_index)
+      }
+      (Some(layer_index), None) => write!(f, " (layer {})", layer_index),
+      _ => Ok(()),
+    }
   }
 }
 
@@ -255,3 +387,238 @@ impl core::fmt::Display for DecodeError {
 }
 
 impl core::error::Error for DecodeError {}
+
+/// An error indicating that a filter expression could not be parsed.
+#[derive(Debug)]
+pub struct FilterError {
+  message: String,
+}
+
+impl FilterError {
+  /// Creates a new `FilterError` instance with the provided message.
+  ///
+  /// # Arguments
+  ///
+  /// * `message` - A description of what is wrong with the filter expression.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::error::FilterError;
+  ///
+  /// let filter_error = FilterError::new(String::from("unknown operator `foo`"));
+  /// ```
+  pub fn new(message: String) -> Self {
+    Self { message }
+  }
+}
+
+impl core::fmt::Display for FilterError {
+  /// Formats the error message associated with the `FilterError`.
+  ///
+  /// # Arguments
+  ///
+  /// * `f` - The formatter to write the output to.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::error::FilterError;
+  ///
+  /// let filter_error = FilterError::new(String::from("unknown operator `foo`"));
+  /// println!("{}", filter_error);
+  /// ```
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    write!(f, "Filter expression contains errors: {}", self.message)
+  }
+}
+
+impl core::error::Error for FilterError {}
+
+/// An error indicating that a child tile cannot be derived from a parent tile.
+#[derive(Debug)]
+pub struct OverzoomError {
+  message: String,
+}
+
+impl OverzoomError {
+  /// Creates a new `OverzoomError` instance with the provided message.
+  ///
+  /// # Arguments
+  ///
+  /// * `message` - A description of what is wrong with the tile coordinates.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::error::OverzoomError;
+  ///
+  /// let overzoom_error = OverzoomError::new(String::from("tile 3/0/0 is not a descendant of tile 2/1/1"));
+  /// ```
+  pub fn new(message: String) -> Self {
+    Self { message }
+  }
+}
+
+impl core::fmt::Display for OverzoomError {
+  /// Formats the error message associated with the `OverzoomError`.
+  ///
+  /// # Arguments
+  ///
+  /// * `f` - The formatter to write the output to.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::error::OverzoomError;
+  ///
+  /// let overzoom_error = OverzoomError::new(String::from("tile 3/0/0 is not a descendant of tile 2/1/1"));
+  /// println!("{}", overzoom_error);
+  /// ```
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    write!(f, "Tile cannot be overzoomed: {}", self.message)
+  }
+}
+
+impl core::error::Error for OverzoomError {}
+
+/// An error indicating that a property value cannot be converted to the requested type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValueError {
+  expected: &'static str,
+  value: crate::feature::Value,
+}
+
+impl ValueError {
+  /// Creates a new `ValueError` instance for a value and the name of the requested type.
+  ///
+  /// # Arguments
+  ///
+  /// * `expected` - The name of the requested type, e.g. `"i64"`.
+  /// * `value` - The value that cannot be converted.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::{error::ValueError, feature::Value};
+  ///
+  /// let value_error = ValueError::new("bool", Value::UInt(1));
+  /// ```
+  pub fn new(expected: &'static str, value: crate::feature::Value) -> Self {
+    Self { expected, value }
+  }
+
+  /// Returns the name of the requested type.
+  pub fn expected(&self) -> &'static str {
+    self.expected
+  }
+
+  /// Returns the value that cannot be converted.
+  pub fn into_value(self) -> crate::feature::Value {
+    self.value
+  }
+}
+
+impl core::fmt::Display for ValueError {
+  /// Formats the error message associated with the `ValueError`.
+  ///
+  /// # Arguments
+  ///
+  /// * `f` - The formatter to write the output to.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::{error::ValueError, feature::Value};
+  ///
+  /// let value_error = ValueError::new("bool", Value::UInt(1));
+  /// println!("{}", value_error);
+  /// ```
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    write!(
+      f,
+      "Value {} cannot be converted to {}",
+      self.value, self.expected
+    )
+  }
+}
+
+impl core::error::Error for ValueError {}
+
+/// An error indicating that the properties of a feature cannot be deserialized into the requested type.
+#[derive(Debug)]
+pub struct DeserializeError {
+  layer_name: String,
+
+  feature_id: Option<u64>,
+
+  message: String,
+}
+
+impl DeserializeError {
+  /// Creates a new `DeserializeError` instance with the provided layer name, feature id and message.
+  ///
+  /// # Arguments
+  ///
+  /// * `layer_name` - The name of the layer.
+  /// * `feature_id` - The id of the feature, if it has one.
+  /// * `message` - A description of the mismatch, e.g. as reported by serde.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::error::DeserializeError;
+  ///
+  /// let deserialize_error = DeserializeError::new(String::from("roads"), Some(42), String::from("missing field `class`"));
+  /// ```
+  pub fn new(layer_name: String, feature_id: Option<u64>, message: String) -> Self {
+    Self {
+      layer_name,
+      feature_id,
+      message,
+    }
+  }
+
+  /// Returns the name of the layer.
+  pub fn layer_name(&self) -> &str {
+    &self.layer_name
+  }
+
+  /// Returns the id of the feature, if it has one.
+  pub fn feature_id(&self) -> Option<u64> {
+    self.feature_id
+  }
+}
+
+impl core::fmt::Display for DeserializeError {
+  /// Formats the error message associated with the `DeserializeError`.
+  ///
+  /// # Arguments
+  ///
+  /// * `f` - The formatter to write the output to.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::error::DeserializeError;
+  ///
+  /// let deserialize_error = DeserializeError::new(String::from("roads"), Some(42), String::from("missing field `class`"));
+  /// println!("{}", deserialize_error);
+  /// ```
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self.feature_id {
+      Some(feature_id) => write!(
+        f,
+        "Properties of feature {} in layer `{}` cannot be deserialized: {}",
+        feature_id, self.layer_name, self.message
+      ),
+      None => write!(
+        f,
+        "Properties of a feature in layer `{}` cannot be deserialized: {}",
+        self.layer_name, self.message
+      ),
+    }
+  }
+}
+
+impl core::error::Error for DeserializeError {}
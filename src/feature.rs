@@ -6,12 +6,26 @@
 //!
 //! The `feature` module defines the following types:
 //!
+//! - `PropertyLookup`: Looks up properties by key with typed accessors, for features and their properties.
 //! - `Feature`: Represents a feature with geometry, an optional id and optional properties.
+//! - `OrderedFeature`: Represents a feature whose properties keep their encoding order and duplicate keys.
+//! - `OrderedProperties`: Represents the properties of a feature in encoding order, including duplicate keys.
+//! - `SharedFeature`: Represents a feature whose properties reference the keys and values of its layer.
+//! - `LayerTable`: Represents the keys and values of a layer shared by the properties of its features.
+//! - `SharedProperties`: Represents the properties of a feature as references into a `LayerTable`.
+//! - `PropertyView`: A lazy view of the properties of a feature that has not been decoded yet.
 
-use std::collections::HashMap;
-use geo_types::{CoordNum, Geometry};
+use geo_types::{Coord, CoordNum, Geometry, Rect};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use crate::error::ValueError;
+use crate::vector_tile::tile;
 
 /// An enumeration representing the value of a property associated with a feature.
+///
+/// The `List` and `Map` variants were added in version 3.0.0. They are part of the enum regardless of the
+/// `mvt3` feature, so that enabling the feature does not break exhaustive matches.
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub enum Value {
   String(String),
@@ -22,9 +36,317 @@ pub enum Value {
   SInt(i64),
   Bool(bool),
   Null,
+
+  /// A list of values, as introduced by the MVT 3 draft specification.
+  List(Vec<Value>),
+
+  /// A map of keys to values, as introduced by the MVT 3 draft specification.
+  Map(BTreeMap<String, Value>),
+}
+
+/// The properties of a feature as pairs of keys and values in the order they were encoded.
+///
+/// Unlike a map, duplicate keys are kept, see [`OrderedFeature::duplicate_keys`].
+pub type OrderedProperties = Vec<(String, Value)>;
+
+impl Value {
+  /// Returns the string if the value is a string.
+  pub fn as_str(&self) -> Option<&str> {
+    match self {
+      Value::String(s) => Some(s),
+      _ => None,
+    }
+  }
+
+  /// Returns the value as `f64` if it is numeric, regardless of the numeric variant.
+  ///
+  /// Integers beyond 2^53 are rounded to the nearest representable `f64`.
+  pub fn as_f64(&self) -> Option<f64> {
+    match self {
+      Value::Float(f) => Some(*f as f64),
+      Value::Double(d) => Some(*d),
+      Value::Int(i) | Value::SInt(i) => Some(*i as f64),
+      Value::UInt(u) => Some(*u as f64),
+      _ => None,
+    }
+  }
+
+  /// Returns the value as `i64` if it is numeric and an integer within the range of `i64`.
+  ///
+  /// Floating point values are only converted if they have no fractional part.
+  pub fn as_i64(&self) -> Option<i64> {
+    match self {
+      Value::Int(i) | Value::SInt(i) => Some(*i),
+      Value::UInt(u) => i64::try_from(*u).ok(),
+      Value::Float(f) => float_to_i64(*f as f64),
+      Value::Double(d) => float_to_i64(*d),
+      _ => None,
+    }
+  }
+
+  /// Returns the value as `u64` if it is numeric and an integer within the range of `u64`.
+  ///
+  /// Floating point values are only converted if they have no fractional part.
+  pub fn as_u64(&self) -> Option<u64> {
+    match self {
+      Value::UInt(u) => Some(*u),
+      Value::Int(i) | Value::SInt(i) => u64::try_from(*i).ok(),
+      Value::Float(f) => float_to_u64(*f as f64),
+      Value::Double(d) => float_to_u64(*d),
+      _ => None,
+    }
+  }
+
+  /// Returns the boolean if the value is a boolean.
+  pub fn as_bool(&self) -> Option<bool> {
+    match self {
+      Value::Bool(b) => Some(*b),
+      _ => None,
+    }
+  }
+
+  /// Returns `true` if the value is null.
+  pub fn is_null(&self) -> bool {
+    matches!(self, Value::Null)
+  }
+}
+
+// 2^63 and 2^64 are exactly representable, the largest integers below them are not
+const I64_BOUND: f64 = 9223372036854775808.0;
+const U64_BOUND: f64 = 18446744073709551616.0;
+
+fn float_to_i64(value: f64) -> Option<i64> {
+  (value.fract() == 0.0 && (-I64_BOUND..I64_BOUND).contains(&value)).then_some(value as i64)
+}
+
+fn float_to_u64(value: f64) -> Option<u64> {
+  (value.fract() == 0.0 && (0.0..U64_BOUND).contains(&value)).then_some(value as u64)
+}
+
+/// A hashable form of a property value in which all numeric variants of the same number are equal.
+///
+/// Integers are compared exactly, e.g. `Value::Int(5)`, `Value::UInt(5)` and `Value::Double(5.0)` have the
+/// same key, while integers beyond 2^53 are not rounded to their nearest `f64`. All NaNs have the same key.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub(crate) enum ValueKey {
+  String(String),
+  Integer(i128),
+  Float(u64),
+  Bool(bool),
+  Null,
+  List(Vec<ValueKey>),
+  Map(Vec<(String, ValueKey)>),
+}
+
+// 2^127 is exactly representable, so every float below it in magnitude fits into an i128
+const I128_BOUND: f64 = 170141183460469231731687303715884105728.0;
+
+impl ValueKey {
+  fn float(value: f64) -> Self {
+    if value.fract() == 0.0 && (-I128_BOUND..I128_BOUND).contains(&value) {
+      // -0.0 and 0.0 as well as integral floats and integers are the same key
+      ValueKey::Integer(value as i128)
+    } else if value.is_nan() {
+      ValueKey::Float(f64::NAN.to_bits())
+    } else {
+      ValueKey::Float(value.to_bits())
+    }
+  }
+}
+
+impl From<&Value> for ValueKey {
+  fn from(value: &Value) -> Self {
+    match value {
+      Value::String(s) => ValueKey::String(s.clone()),
+      Value::Float(f) => ValueKey::float(*f as f64),
+      Value::Double(d) => ValueKey::float(*d),
+      Value::Int(i) | Value::SInt(i) => ValueKey::Integer(*i as i128),
+      Value::UInt(u) => ValueKey::Integer(*u as i128),
+      Value::Bool(b) => ValueKey::Bool(*b),
+      Value::List(values) => ValueKey::List(values.iter().map(ValueKey::from).collect()),
+      Value::Map(map) => ValueKey::Map(
+        map
+          .iter()
+          .map(|(key, value)| (key.clone(), ValueKey::from(value)))
+          .collect(),
+      ),
+      Value::Null => ValueKey::Null,
+    }
+  }
+}
+
+impl core::fmt::Display for Value {
+  /// Formats the value without its variant, e.g. `3` for `Value::UInt(3)` and `cafe` for a string.
+  ///
+  /// Strings within lists and maps are quoted.
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn nested(value: &Value, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+      match value {
+        Value::String(s) => write!(f, "{:?}", s),
+        value => write!(f, "{}", value),
+      }
+    }
+
+    match self {
+      Value::String(s) => f.write_str(s),
+      Value::Float(v) => write!(f, "{}", v),
+      Value::Double(v) => write!(f, "{}", v),
+      Value::Int(v) | Value::SInt(v) => write!(f, "{}", v),
+      Value::UInt(v) => write!(f, "{}", v),
+      Value::Bool(v) => write!(f, "{}", v),
+      Value::Null => f.write_str("null"),
+      Value::List(values) => {
+        f.write_str("[")?;
+        for (index, value) in values.iter().enumerate() {
+          if index > 0 {
+            f.write_str(", ")?;
+          }
+          nested(value, f)?;
+        }
+        f.write_str("]")
+      }
+      Value::Map(map) => {
+        f.write_str("{")?;
+        for (index, (key, value)) in map.iter().enumerate() {
+          if index > 0 {
+            f.write_str(", ")?;
+          }
+          write!(f, "{:?}: ", key)?;
+          nested(value, f)?;
+        }
+        f.write_str("}")
+      }
+    }
+  }
+}
+
+macro_rules! impl_try_from_value {
+  ($type:ty, $name:literal, $convert:expr) => {
+    impl TryFrom<Value> for $type {
+      type Error = ValueError;
+
+      fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let convert: fn(&Value) -> Option<$type> = $convert;
+        convert(&value).ok_or_else(|| ValueError::new($name, value))
+      }
+    }
+  };
+}
+
+impl TryFrom<Value> for String {
+  type Error = ValueError;
+
+  fn try_from(value: Value) -> Result<Self, Self::Error> {
+    match value {
+      Value::String(s) => Ok(s),
+      value => Err(ValueError::new("String", value)),
+    }
+  }
+}
+
+impl_try_from_value!(f64, "f64", Value::as_f64);
+impl_try_from_value!(f32, "f32", |value| value.as_f64().map(|v| v as f32));
+impl_try_from_value!(i64, "i64", Value::as_i64);
+impl_try_from_value!(i32, "i32", |value| value
+  .as_i64()
+  .and_then(|v| i32::try_from(v).ok()));
+impl_try_from_value!(u64, "u64", Value::as_u64);
+impl_try_from_value!(u32, "u32", |value| value
+  .as_u64()
+  .and_then(|v| u32::try_from(v).ok()));
+impl_try_from_value!(bool, "bool", Value::as_bool);
+
+/// A trait for looking up the properties of a feature by key, with typed accessors that coerce the numeric
+/// variants of `Value`.
+///
+/// It is implemented by the features and property containers of this module. If a key occurs more than
+/// once, the last value wins.
+pub trait PropertyLookup {
+  /// Retrieves the value of a property.
+  ///
+  /// # Arguments
+  ///
+  /// * `key` - The property key.
+  ///
+  /// # Returns
+  ///
+  /// The value if the property exists, `None` otherwise.
+  fn get_value(&self, key: &str) -> Option<&Value>;
+
+  /// Retrieves the value of a string property.
+  ///
+  /// # Returns
+  ///
+  /// The string if the property exists and is a string, `None` otherwise.
+  fn get_str(&self, key: &str) -> Option<&str> {
+    self.get_value(key)?.as_str()
+  }
+
+  /// Retrieves the value of a numeric property as `f64`, regardless of the numeric variant.
+  ///
+  /// # Returns
+  ///
+  /// The number if the property exists and is numeric, `None` otherwise.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::feature::{Feature, PropertyLookup, Value};
+  /// use geo_types::{Geometry, Point};
+  /// use std::collections::HashMap;
+  ///
+  /// let feature = Feature {
+  ///   geometry: Geometry::Point(Point::new(0.0, 0.0)),
+  ///   id: None,
+  ///   properties: Some(HashMap::from([(String::from("height"), Value::UInt(12))])),
+  /// };
+  ///
+  /// assert_eq!(feature.get_f64("height"), Some(12.0));
+  /// assert_eq!(feature.get_i64("height"), Some(12));
+  /// ```
+  fn get_f64(&self, key: &str) -> Option<f64> {
+    self.get_value(key)?.as_f64()
+  }
+
+  /// Retrieves the value of a numeric property as `i64`, regardless of the numeric variant.
+  ///
+  /// # Returns
+  ///
+  /// The integer if the property exists and is an integer within the range of `i64`, `None` otherwise.
+  fn get_i64(&self, key: &str) -> Option<i64> {
+    self.get_value(key)?.as_i64()
+  }
+
+  /// Retrieves the value of a boolean property.
+  ///
+  /// # Returns
+  ///
+  /// The boolean if the property exists and is a boolean, `None` otherwise.
+  fn get_bool(&self, key: &str) -> Option<bool> {
+    self.get_value(key)?.as_bool()
+  }
+}
+
+impl PropertyLookup for HashMap<String, Value> {
+  fn get_value(&self, key: &str) -> Option<&Value> {
+    self.get(key)
+  }
+}
+
+impl PropertyLookup for OrderedProperties {
+  fn get_value(&self, key: &str) -> Option<&Value> {
+    self
+      .iter()
+      .rev()
+      .find(|(k, _)| k == key)
+      .map(|(_, value)| value)
+  }
 }
 
 /// A structure representing a feature in a vector tile.
+///
+/// The properties are stored in a `HashMap`, in which the last value of a duplicate key wins. Use
+/// [`OrderedFeature`] to keep their order and duplicate keys.
 #[derive(Debug, Clone)]
 pub struct Feature<T: CoordNum = f32> {
   /// The geometry of the feature.
@@ -62,4 +384,436 @@ impl<T: CoordNum> Feature<T> {
   pub fn get_geometry(&self) -> &Geometry<T> {
     &self.geometry
   }
+
+  /// Computes the bounding box of the feature.
+  ///
+  /// # Returns
+  ///
+  /// The smallest rectangle containing all coordinates of the geometry, or `None` if the geometry is empty.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::feature::Feature;
+  /// use geo_types::{Geometry, LineString};
+  ///
+  /// let feature = Feature {
+  ///   geometry: Geometry::LineString(LineString::from(vec![(0.0, 4.0), (2.0, 1.0)])),
+  ///   id: None,
+  ///   properties: None,
+  /// };
+  ///
+  /// let bbox = feature.bbox().unwrap();
+  /// assert_eq!(bbox.min().y, 1.0);
+  /// assert_eq!(bbox.max().x, 2.0);
+  /// ```
+  pub fn bbox(&self) -> Option<Rect<T>> {
+    let mut bounds: Option<(Coord<T>, Coord<T>)> = None;
+    visit_coords(&self.geometry, &mut |coord| {
+      bounds = Some(match bounds {
+        Some((min, max)) => (
+          Coord {
+            x: if coord.x < min.x { coord.x } else { min.x },
+            y: if coord.y < min.y { coord.y } else { min.y },
+          },
+          Coord {
+            x: if coord.x > max.x { coord.x } else { max.x },
+            y: if coord.y > max.y { coord.y } else { max.y },
+          },
+        ),
+        None => (coord, coord),
+      });
+    });
+    bounds.map(|(min, max)| Rect::new(min, max))
+  }
+}
+
+impl<T: CoordNum> PropertyLookup for Feature<T> {
+  fn get_value(&self, key: &str) -> Option<&Value> {
+    self.properties.as_ref()?.get(key)
+  }
+}
+
+/// A structure representing a feature in a vector tile whose properties keep their encoding order.
+///
+/// Unlike the `HashMap` of [`Feature`], duplicate keys are kept with all of their values.
+#[derive(Debug, Clone)]
+pub struct OrderedFeature<T: CoordNum = f32> {
+  /// The geometry of the feature.
+  pub geometry: Geometry<T>,
+
+  /// Optional identifier for the feature.
+  pub id: Option<u64>,
+
+  /// Optional properties associated with the feature, in encoding order.
+  pub properties: Option<OrderedProperties>,
+}
+
+impl<T: CoordNum> OrderedFeature<T> {
+  /// Returns the keys that occur more than once in the properties of the feature.
+  ///
+  /// # Returns
+  ///
+  /// Each duplicate key once, in the order of its first occurrence.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::feature::{OrderedFeature, Value};
+  /// use geo_types::{Geometry, Point};
+  ///
+  /// let feature = OrderedFeature {
+  ///   geometry: Geometry::Point(Point::new(0.0, 0.0)),
+  ///   id: None,
+  ///   properties: Some(vec![
+  ///     (String::from("name"), Value::String(String::from("a"))),
+  ///     (String::from("name"), Value::String(String::from("b"))),
+  ///   ]),
+  /// };
+  ///
+  /// assert_eq!(feature.duplicate_keys(), vec!["name"]);
+  /// ```
+  pub fn duplicate_keys(&self) -> Vec<&str> {
+    duplicates(
+      self
+        .properties
+        .iter()
+        .flatten()
+        .map(|(key, _)| key.as_str()),
+    )
+  }
+}
+
+impl<T: CoordNum> PropertyLookup for OrderedFeature<T> {
+  fn get_value(&self, key: &str) -> Option<&Value> {
+    self.properties.as_ref()?.get_value(key)
+  }
+}
+
+/// A structure representing a feature in a vector tile whose properties reference the keys and values of
+/// its layer.
+#[derive(Debug, Clone)]
+pub struct SharedFeature<T: CoordNum = f32> {
+  /// The geometry of the feature.
+  pub geometry: Geometry<T>,
+
+  /// Optional identifier for the feature.
+  pub id: Option<u64>,
+
+  /// Optional properties associated with the feature.
+  pub properties: Option<SharedProperties>,
+}
+
+impl<T: CoordNum> SharedFeature<T> {
+  /// Converts the feature into a feature with its own `HashMap` of properties.
+  pub fn into_owned(self) -> Feature<T> {
+    Feature {
+      geometry: self.geometry,
+      id: self.id,
+      properties: self.properties.map(|properties| properties.to_hash_map()),
+    }
+  }
+}
+
+impl<T: CoordNum> PropertyLookup for SharedFeature<T> {
+  fn get_value(&self, key: &str) -> Option<&Value> {
+    self.properties.as_ref()?.get(key)
+  }
+}
+
+/// A structure representing the keys and values of a layer, shared by the properties of its features.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerTable {
+  keys: Vec<Arc<str>>,
+  values: Vec<Value>,
+}
+
+impl LayerTable {
+  pub(crate) fn new(keys: Vec<Arc<str>>, values: Vec<Value>) -> Self {
+    Self { keys, values }
+  }
+
+  /// Returns the keys of the layer.
+  pub fn keys(&self) -> &[Arc<str>] {
+    &self.keys
+  }
+
+  /// Returns the values of the layer.
+  pub fn values(&self) -> &[Value] {
+    &self.values
+  }
+}
+
+/// A structure representing the properties of a feature as tags referencing a shared `LayerTable`.
+///
+/// If a key occurs more than once, the last value wins, matching the behaviour of the decoded `HashMap`
+/// properties. Inlined attributes of the MVT 3 draft specification are stored with the feature and follow
+/// the tags.
+#[derive(Debug, Clone)]
+pub struct SharedProperties {
+  table: Arc<LayerTable>,
+  tags: Vec<u32>,
+  attributes: Vec<(Arc<str>, Value)>,
+}
+
+impl SharedProperties {
+  /// Creates properties from already validated tags.
+  pub(crate) fn new(
+    table: Arc<LayerTable>,
+    tags: Vec<u32>,
+    attributes: Vec<(Arc<str>, Value)>,
+  ) -> Self {
+    Self {
+      table,
+      tags,
+      attributes,
+    }
+  }
+
+  /// Returns the table of the layer the properties reference.
+  pub fn table(&self) -> &Arc<LayerTable> {
+    &self.table
+  }
+
+  /// Returns the number of tags and attributes of the feature.
+  pub fn len(&self) -> usize {
+    self.tags.len() / 2 + self.attributes.len()
+  }
+
+  /// Returns `true` if the feature has no tags and attributes.
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Retrieves the value of a property.
+  ///
+  /// # Arguments
+  ///
+  /// * `key` - The property key.
+  ///
+  /// # Returns
+  ///
+  /// The value if the feature has the property, `None` otherwise.
+  pub fn get(&self, key: &str) -> Option<&Value> {
+    self
+      .iter()
+      .rev()
+      .find(|(k, _)| *k == key)
+      .map(|(_, value)| value)
+  }
+
+  /// Returns `true` if the feature has a property with the given key.
+  pub fn contains_key(&self, key: &str) -> bool {
+    self.iter().any(|(k, _)| k == key)
+  }
+
+  /// Returns an iterator over the keys and values of the properties in encoding order.
+  pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&str, &Value)> {
+    let table = &*self.table;
+    self
+      .tags
+      .chunks_exact(2)
+      .map(move |pair| {
+        (
+          &*table.keys[pair[0] as usize],
+          &table.values[pair[1] as usize],
+        )
+      })
+      .chain(self.attributes.iter().map(|(key, value)| (&**key, value)))
+  }
+
+  /// Copies the properties into a `HashMap`.
+  pub fn to_hash_map(&self) -> HashMap<String, Value> {
+    self
+      .iter()
+      .map(|(key, value)| (key.to_string(), value.clone()))
+      .collect()
+  }
+}
+
+impl PropertyLookup for SharedProperties {
+  fn get_value(&self, key: &str) -> Option<&Value> {
+    self.get(key)
+  }
+}
+
+impl From<SharedProperties> for HashMap<String, Value> {
+  fn from(properties: SharedProperties) -> Self {
+    properties.to_hash_map()
+  }
+}
+
+fn duplicates<'a>(keys: impl Iterator<Item = &'a str>) -> Vec<&'a str> {
+  let mut seen = HashMap::new();
+  let mut result = Vec::new();
+  for key in keys {
+    let count = seen.entry(key).or_insert(0);
+    *count += 1;
+    if *count == 2 {
+      result.push(key);
+    }
+  }
+  result
+}
+
+fn visit_coords<T: CoordNum, F: FnMut(Coord<T>)>(geometry: &Geometry<T>, visitor: &mut F) {
+  match geometry {
+    Geometry::Point(point) => visitor(point.0),
+    Geometry::MultiPoint(points) => points.iter().for_each(|point| visitor(point.0)),
+    Geometry::Line(line) => {
+      visitor(line.start);
+      visitor(line.end);
+    }
+    Geometry::LineString(linestring) => linestring.0.iter().for_each(|coord| visitor(*coord)),
+    Geometry::MultiLineString(linestrings) => linestrings
+      .iter()
+      .flat_map(|linestring| linestring.0.iter())
+      .for_each(|coord| visitor(*coord)),
+    Geometry::Polygon(polygon) => polygon
+      .exterior()
+      .0
+      .iter()
+      .for_each(|coord| visitor(*coord)),
+    Geometry::MultiPolygon(polygons) => polygons
+      .iter()
+      .flat_map(|polygon| polygon.exterior().0.iter())
+      .for_each(|coord| visitor(*coord)),
+    Geometry::Rect(rect) => {
+      visitor(rect.min());
+      visitor(rect.max());
+    }
+    Geometry::Triangle(triangle) => triangle.to_array().into_iter().for_each(visitor),
+    Geometry::GeometryCollection(collection) => collection
+      .iter()
+      .for_each(|geometry| visit_coords(geometry, visitor)),
+  }
+}
+
+/// A borrowed view of the properties of a feature, backed by the key and value tables of its layer.
+///
+/// Values are only looked up when they are accessed. If a key occurs more than once, the last value wins,
+/// matching the behaviour of the decoded `HashMap` properties. Inlined attributes of the MVT 3 draft
+/// specification are decoded up front and follow the tags, so they win over tags with the same key.
+#[derive(Debug, Clone, Copy)]
+pub struct PropertyView<'a> {
+  tags: &'a [u32],
+  keys: &'a [String],
+  values: &'a [tile::Value],
+  attributes: &'a [(String, Value)],
+}
+
+impl<'a> PropertyView<'a> {
+  /// Creates a view over already validated tags and the decoded inlined attributes of a feature.
+  pub(crate) fn new(
+    tags: &'a [u32],
+    keys: &'a [String],
+    values: &'a [tile::Value],
+    attributes: &'a [(String, Value)],
+  ) -> Self {
+    Self {
+      tags,
+      keys,
+      values,
+      attributes,
+    }
+  }
+
+  fn pairs(&self) -> impl DoubleEndedIterator<Item = (&'a str, &'a tile::Value)> + 'a {
+    let keys = self.keys;
+    let values = self.values;
+    self
+      .tags
+      .chunks_exact(2)
+      .map(move |pair| (keys[pair[0] as usize].as_str(), &values[pair[1] as usize]))
+  }
+
+  fn find(&self, key: &str) -> Option<&'a tile::Value> {
+    self
+      .pairs()
+      .rev()
+      .find(|(k, _)| *k == key)
+      .map(|(_, value)| value)
+  }
+
+  fn find_attribute(&self, key: &str) -> Option<&'a Value> {
+    self
+      .attributes
+      .iter()
+      .rev()
+      .find(|(k, _)| k == key)
+      .map(|(_, value)| value)
+  }
+
+  /// Returns the number of properties of the feature, counting keys that occur more than once.
+  pub fn len(&self) -> usize {
+    self.tags.len() / 2 + self.attributes.len()
+  }
+
+  /// Returns `true` if the feature has no properties.
+  pub fn is_empty(&self) -> bool {
+    self.tags.is_empty() && self.attributes.is_empty()
+  }
+
+  /// Returns `true` if the feature has a property with the given key.
+  pub fn contains_key(&self, key: &str) -> bool {
+    self.find_attribute(key).is_some() || self.find(key).is_some()
+  }
+
+  /// Retrieves a copy of the value of a property.
+  ///
+  /// # Arguments
+  ///
+  /// * `key` - The property key.
+  ///
+  /// # Returns
+  ///
+  /// The value if the feature has the property, `None` otherwise.
+  pub fn get(&self, key: &str) -> Option<Value> {
+    match self.find_attribute(key) {
+      Some(value) => Some(value.clone()),
+      None => self.find(key).map(|value| crate::map_value(value.clone())),
+    }
+  }
+
+  /// Retrieves the value of a string property without copying it.
+  ///
+  /// # Arguments
+  ///
+  /// * `key` - The property key.
+  ///
+  /// # Returns
+  ///
+  /// The string if the feature has the property and it is a string, `None` otherwise.
+  pub fn get_str(&self, key: &str) -> Option<&'a str> {
+    match self.find_attribute(key) {
+      Some(Value::String(value)) => Some(value),
+      Some(_) => None,
+      None => self
+        .find(key)
+        .and_then(|value| value.string_value.as_deref()),
+    }
+  }
+
+  /// Returns the keys that occur more than once in the properties of the feature, in the order of their first occurrence.
+  pub fn duplicate_keys(&self) -> Vec<&'a str> {
+    duplicates(
+      self
+        .pairs()
+        .map(|(key, _)| key)
+        .chain(self.attributes.iter().map(|(key, _)| key.as_str())),
+    )
+  }
+
+  /// Returns an iterator over the keys and values of the properties in encoding order.
+  pub fn iter(&self) -> impl Iterator<Item = (&'a str, Value)> + 'a {
+    self
+      .pairs()
+      .map(|(key, value)| (key, crate::map_value(value.clone())))
+      .chain(
+        self
+          .attributes
+          .iter()
+          .map(|(key, value)| (key.as_str(), value.clone())),
+      )
+  }
 }
@@ -0,0 +1,605 @@
+//! This module provides filtering of features with Mapbox GL / MapLibre style-spec filter expressions.
+//!
+//! Filters are parsed from JSON and can be evaluated against decoded features or passed to
+//! [`Reader::get_features_filtered`](crate::Reader::get_features_filtered), which skips geometry
+//! decoding for rejected features.
+//!
+//! Both the expression syntax (`["==", ["get", "class"], "primary"]`) and the legacy filter syntax
+//! (`["==", "class", "primary"]`, `["in", "class", "primary", "secondary"]`, `["has", "name"]`) are supported.
+//!
+//! Integers, i.e. feature ids, integer property values and integer literals, are compared exactly, so ids
+//! above 2^53 are not confused with their neighbours.
+//!
+//! # Types
+//!
+//! The `filter` module defines the following types:
+//!
+//! - `Filter`: Represents a parsed filter expression.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use geo_types::{CoordNum, Geometry};
+use serde_json::Value as JsonValue;
+
+use crate::error::{FilterError, ParserError};
+use crate::feature::{Feature, PropertyView, Value};
+use crate::vector_tile::tile::GeomType;
+
+/// A parsed style-spec filter expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter {
+  expression: Expression,
+}
+
+impl Filter {
+  /// Parses a filter from a JSON string.
+  ///
+  /// # Arguments
+  ///
+  /// * `json` - The filter expression as JSON text.
+  ///
+  /// # Returns
+  ///
+  /// A result containing the `Filter` if successful, or a `ParserError` if the JSON or the expression is invalid.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::filter::Filter;
+  ///
+  /// let filter = Filter::parse(r#"["all", ["==", ["get", "class"], "primary"], [">=", ["zoom"], 10]]"#).unwrap();
+  /// ```
+  pub fn parse(json: &str) -> Result<Self, ParserError> {
+    match serde_json::from_str::<JsonValue>(json) {
+      Ok(value) => Self::from_json(&value),
+      Err(error) => Err(ParserError::new(FilterError::new(error.to_string()))),
+    }
+  }
+
+  /// Creates a filter from an already parsed JSON value, e.g. the `filter` member of a style layer.
+  ///
+  /// # Arguments
+  ///
+  /// * `value` - The filter expression as a `serde_json::Value`.
+  ///
+  /// # Returns
+  ///
+  /// A result containing the `Filter` if successful, or a `ParserError` if the expression is invalid.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::filter::Filter;
+  ///
+  /// let value = serde_json::json!(["in", "class", "primary", "secondary"]);
+  /// let filter = Filter::from_json(&value).unwrap();
+  /// ```
+  pub fn from_json(value: &JsonValue) -> Result<Self, ParserError> {
+    match parse_filter(value) {
+      Ok(expression) => Ok(Self { expression }),
+      Err(message) => Err(ParserError::new(FilterError::new(message))),
+    }
+  }
+
+  /// Evaluates the filter against a decoded feature.
+  ///
+  /// # Arguments
+  ///
+  /// * `feature` - The feature to test.
+  /// * `zoom` - The zoom level used for `["zoom"]` expressions.
+  ///
+  /// # Returns
+  ///
+  /// `true` if the feature passes the filter, `false` otherwise.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::{feature::{Feature, Value}, filter::Filter};
+  /// use geo_types::{Geometry, Point};
+  /// use std::collections::HashMap;
+  ///
+  /// let feature = Feature {
+  ///   geometry: Geometry::Point(Point::new(0.0, 0.0)),
+  ///   id: Some(1),
+  ///   properties: Some(HashMap::from([(String::from("class"), Value::String(String::from("primary")))])),
+  /// };
+  ///
+  /// let filter = Filter::parse(r#"["==", "class", "primary"]"#).unwrap();
+  /// assert!(filter.evaluate(&feature, 14.0));
+  /// ```
+  pub fn evaluate<T: CoordNum>(&self, feature: &Feature<T>, zoom: f64) -> bool {
+    let empty = HashMap::new();
+    self.evaluate_with(&Context {
+      id: feature.id,
+      geometry_type: geometry_type_name(&feature.geometry),
+      properties: feature.properties.as_ref().unwrap_or(&empty),
+      zoom,
+    })
+  }
+
+  pub(crate) fn evaluate_with(&self, context: &Context) -> bool {
+    matches!(self.expression.evaluate(context), Datum::Bool(true))
+  }
+}
+
+/// The data a filter is evaluated against.
+pub(crate) struct Context<'a> {
+  pub(crate) id: Option<u64>,
+  pub(crate) geometry_type: &'static str,
+  pub(crate) properties: &'a dyn Properties,
+  pub(crate) zoom: f64,
+}
+
+/// Property lookup shared by decoded and not yet decoded features.
+pub(crate) trait Properties {
+  fn lookup(&self, key: &str) -> Option<Datum>;
+  fn has(&self, key: &str) -> bool;
+}
+
+impl Properties for HashMap<String, Value> {
+  fn lookup(&self, key: &str) -> Option<Datum> {
+    self.get(key).map(Datum::from)
+  }
+
+  fn has(&self, key: &str) -> bool {
+    self.contains_key(key)
+  }
+}
+
+impl Properties for PropertyView<'_> {
+  fn lookup(&self, key: &str) -> Option<Datum> {
+    self.get(key).as_ref().map(Datum::from)
+  }
+
+  fn has(&self, key: &str) -> bool {
+    self.contains_key(key)
+  }
+}
+
+pub(crate) fn geom_type_name(geom_type: GeomType) -> &'static str {
+  match geom_type {
+    GeomType::Point => "Point",
+    GeomType::Linestring => "LineString",
+    #[cfg(feature = "mvt3")]
+    GeomType::Spline => "LineString",
+    GeomType::Polygon => "Polygon",
+    GeomType::Unknown => "Unknown",
+  }
+}
+
+fn geometry_type_name<T: CoordNum>(geometry: &Geometry<T>) -> &'static str {
+  match geometry {
+    Geometry::Point(_) | Geometry::MultiPoint(_) => "Point",
+    Geometry::Line(_) | Geometry::LineString(_) | Geometry::MultiLineString(_) => "LineString",
+    Geometry::Polygon(_)
+    | Geometry::MultiPolygon(_)
+    | Geometry::Rect(_)
+    | Geometry::Triangle(_) => "Polygon",
+    Geometry::GeometryCollection(_) => "Unknown",
+  }
+}
+
+/// An evaluated expression value.
+#[derive(Debug, Clone)]
+pub(crate) enum Datum {
+  Null,
+  Bool(bool),
+  Integer(i128),
+  Number(f64),
+  String(String),
+  Array(Vec<Datum>),
+}
+
+impl From<&Value> for Datum {
+  fn from(value: &Value) -> Self {
+    match value {
+      Value::String(s) => Datum::String(s.clone()),
+      Value::Float(f) => Datum::Number(*f as f64),
+      Value::Double(d) => Datum::Number(*d),
+      Value::Int(i) | Value::SInt(i) => Datum::Integer((*i).into()),
+      Value::UInt(u) => Datum::Integer((*u).into()),
+      Value::Bool(b) => Datum::Bool(*b),
+      Value::Null => Datum::Null,
+      Value::List(values) => Datum::Array(values.iter().map(Datum::from).collect()),
+      Value::Map(_) => Datum::Null,
+    }
+  }
+}
+
+impl From<&JsonValue> for Datum {
+  fn from(value: &JsonValue) -> Self {
+    match value {
+      JsonValue::Null => Datum::Null,
+      JsonValue::Bool(b) => Datum::Bool(*b),
+      JsonValue::Number(n) => match (n.as_i64(), n.as_u64()) {
+        (Some(i), _) => Datum::Integer(i.into()),
+        (_, Some(u)) => Datum::Integer(u.into()),
+        _ => Datum::Number(n.as_f64().unwrap_or(f64::NAN)),
+      },
+      JsonValue::String(s) => Datum::String(s.clone()),
+      JsonValue::Array(items) => Datum::Array(items.iter().map(Datum::from).collect()),
+      JsonValue::Object(_) => Datum::Null,
+    }
+  }
+}
+
+impl PartialEq for Datum {
+  fn eq(&self, other: &Self) -> bool {
+    match (self, other) {
+      (Datum::Null, Datum::Null) => true,
+      (Datum::Bool(l), Datum::Bool(r)) => l == r,
+      (Datum::String(l), Datum::String(r)) => l == r,
+      (Datum::Array(l), Datum::Array(r)) => l == r,
+      (l, r) => compare_numbers(l, r).is_some_and(Ordering::is_eq),
+    }
+  }
+}
+
+/// Compares two numbers, comparing integers exactly.
+fn compare_numbers(left: &Datum, right: &Datum) -> Option<Ordering> {
+  match (left, right) {
+    (Datum::Integer(l), Datum::Integer(r)) => Some(l.cmp(r)),
+    (Datum::Number(l), Datum::Number(r)) => l.partial_cmp(r),
+    (Datum::Integer(l), Datum::Number(r)) => compare_integer(*l, *r),
+    (Datum::Number(l), Datum::Integer(r)) => compare_integer(*r, *l).map(Ordering::reverse),
+    _ => None,
+  }
+}
+
+/// Compares an integer with a float without rounding the integer.
+fn compare_integer(integer: i128, number: f64) -> Option<Ordering> {
+  if number.is_nan() {
+    return None;
+  }
+  // the conversion saturates, which keeps the order for floats beyond the range of the integers
+  let truncated = number.trunc();
+  match integer.cmp(&(truncated as i128)) {
+    Ordering::Equal => truncated.partial_cmp(&number),
+    ordering => Some(ordering),
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Comparison {
+  Equal,
+  NotEqual,
+  Less,
+  LessOrEqual,
+  Greater,
+  GreaterOrEqual,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expression {
+  Literal(Datum),
+  Get(String),
+  Has(String),
+  Id,
+  GeometryType,
+  Zoom,
+  Not(Box<Expression>),
+  All(Vec<Expression>),
+  Any(Vec<Expression>),
+  Compare(Comparison, Box<Expression>, Box<Expression>),
+  In(Box<Expression>, Box<Expression>),
+  Match(
+    Box<Expression>,
+    Vec<(Vec<Datum>, Expression)>,
+    Box<Expression>,
+  ),
+  Case(Vec<(Expression, Expression)>, Box<Expression>),
+  Coalesce(Vec<Expression>),
+}
+
+impl Expression {
+  fn evaluate(&self, context: &Context) -> Datum {
+    match self {
+      Expression::Literal(datum) => datum.clone(),
+      Expression::Get(key) => context.properties.lookup(key).unwrap_or(Datum::Null),
+      Expression::Has(key) => Datum::Bool(context.properties.has(key)),
+      Expression::Id => context
+        .id
+        .map(|id| Datum::Integer(id.into()))
+        .unwrap_or(Datum::Null),
+      Expression::GeometryType => Datum::String(context.geometry_type.to_string()),
+      Expression::Zoom => Datum::Number(context.zoom),
+      Expression::Not(expression) => match expression.evaluate(context) {
+        Datum::Bool(b) => Datum::Bool(!b),
+        _ => Datum::Bool(false),
+      },
+      Expression::All(expressions) => Datum::Bool(
+        expressions
+          .iter()
+          .all(|expression| expression.evaluate(context) == Datum::Bool(true)),
+      ),
+      Expression::Any(expressions) => Datum::Bool(
+        expressions
+          .iter()
+          .any(|expression| expression.evaluate(context) == Datum::Bool(true)),
+      ),
+      Expression::Compare(comparison, left, right) => Datum::Bool(compare(
+        *comparison,
+        &left.evaluate(context),
+        &right.evaluate(context),
+      )),
+      Expression::In(needle, haystack) => {
+        let needle = needle.evaluate(context);
+        Datum::Bool(match haystack.evaluate(context) {
+          Datum::Array(items) => items.contains(&needle),
+          Datum::String(haystack) => match needle {
+            Datum::String(needle) => haystack.contains(&needle),
+            _ => false,
+          },
+          _ => false,
+        })
+      }
+      Expression::Match(input, branches, fallback) => {
+        let input = input.evaluate(context);
+        for (labels, output) in branches {
+          if labels.contains(&input) {
+            return output.evaluate(context);
+          }
+        }
+        fallback.evaluate(context)
+      }
+      Expression::Case(branches, fallback) => {
+        for (condition, output) in branches {
+          if condition.evaluate(context) == Datum::Bool(true) {
+            return output.evaluate(context);
+          }
+        }
+        fallback.evaluate(context)
+      }
+      Expression::Coalesce(expressions) => expressions
+        .iter()
+        .map(|expression| expression.evaluate(context))
+        .find(|datum| *datum != Datum::Null)
+        .unwrap_or(Datum::Null),
+    }
+  }
+}
+
+fn compare(comparison: Comparison, left: &Datum, right: &Datum) -> bool {
+  let ordering = match (left, right) {
+    (Datum::String(l), Datum::String(r)) => Some(l.cmp(r)),
+    _ => compare_numbers(left, right),
+  };
+  match comparison {
+    Comparison::Equal => left == right,
+    Comparison::NotEqual => left != right,
+    Comparison::Less => ordering.is_some_and(|o| o.is_lt()),
+    Comparison::LessOrEqual => ordering.is_some_and(|o| o.is_le()),
+    Comparison::Greater => ordering.is_some_and(|o| o.is_gt()),
+    Comparison::GreaterOrEqual => ordering.is_some_and(|o| o.is_ge()),
+  }
+}
+
+fn comparison_operator(operator: &str) -> Option<Comparison> {
+  match operator {
+    "==" => Some(Comparison::Equal),
+    "!=" => Some(Comparison::NotEqual),
+    "<" => Some(Comparison::Less),
+    "<=" => Some(Comparison::LessOrEqual),
+    ">" => Some(Comparison::Greater),
+    ">=" => Some(Comparison::GreaterOrEqual),
+    _ => None,
+  }
+}
+
+/// Parses a top level filter, which may use either the legacy or the expression syntax.
+fn parse_filter(value: &JsonValue) -> Result<Expression, String> {
+  let items = match value {
+    JsonValue::Bool(b) => return Ok(Expression::Literal(Datum::Bool(*b))),
+    JsonValue::Array(items) => items,
+    _ => return Err(format!("expected an array, found `{}`", value)),
+  };
+  let operator = match items.first() {
+    Some(JsonValue::String(operator)) => operator.as_str(),
+    _ => return Err(String::from("expected an operator as first element")),
+  };
+  let arguments = &items[1..];
+
+  if let Some(comparison) = comparison_operator(operator)
+    && is_legacy_comparison(arguments)
+  {
+    return Ok(Expression::Compare(
+      comparison,
+      Box::new(legacy_key(&arguments[0])?),
+      Box::new(Expression::Literal(Datum::from(&arguments[1]))),
+    ));
+  }
+
+  match operator {
+    "all" => Ok(Expression::All(parse_all(arguments, parse_filter)?)),
+    "any" => Ok(Expression::Any(parse_all(arguments, parse_filter)?)),
+    "none" => Ok(Expression::Not(Box::new(Expression::Any(parse_all(
+      arguments,
+      parse_filter,
+    )?)))),
+    "in" | "!in" if is_legacy_in(arguments) => {
+      let expression = Expression::In(
+        Box::new(legacy_key(&arguments[0])?),
+        Box::new(Expression::Literal(Datum::Array(
+          arguments[1..].iter().map(Datum::from).collect(),
+        ))),
+      );
+      match operator {
+        "in" => Ok(expression),
+        _ => Ok(Expression::Not(Box::new(expression))),
+      }
+    }
+    "has" | "!has" if arguments.len() == 1 => {
+      let expression = match parse_key(operator, &arguments[0])?.as_str() {
+        "$id" => Expression::Compare(
+          Comparison::NotEqual,
+          Box::new(Expression::Id),
+          Box::new(Expression::Literal(Datum::Null)),
+        ),
+        "$type" => Expression::Literal(Datum::Bool(true)),
+        key => Expression::Has(key.to_string()),
+      };
+      match operator {
+        "has" => Ok(expression),
+        _ => Ok(Expression::Not(Box::new(expression))),
+      }
+    }
+    _ => parse_expression(value),
+  }
+}
+
+/// Mirrors the style-spec rule: a comparison is legacy syntax if it has exactly two arguments, the first one
+/// is a key and the second one is not an expression.
+fn is_legacy_comparison(arguments: &[JsonValue]) -> bool {
+  arguments.len() == 2 && arguments[0].is_string() && !arguments[1].is_array()
+}
+
+fn is_legacy_in(arguments: &[JsonValue]) -> bool {
+  !arguments.is_empty()
+    && arguments[0].is_string()
+    && (arguments.len() != 2 || !arguments[1].is_array())
+}
+
+fn legacy_key(value: &JsonValue) -> Result<Expression, String> {
+  match value.as_str() {
+    Some("$type") => Ok(Expression::GeometryType),
+    Some("$id") => Ok(Expression::Id),
+    Some(key) => Ok(Expression::Get(key.to_string())),
+    None => Err(format!("expected a property key, found `{}`", value)),
+  }
+}
+
+fn parse_all<F>(values: &[JsonValue], parser: F) -> Result<Vec<Expression>, String>
+where
+  F: Fn(&JsonValue) -> Result<Expression, String>,
+{
+  values.iter().map(parser).collect()
+}
+
+fn expect_arguments(operator: &str, arguments: &[JsonValue], count: usize) -> Result<(), String> {
+  if arguments.len() != count {
+    return Err(format!(
+      "`{}` expects {} argument(s), found {}",
+      operator,
+      count,
+      arguments.len()
+    ));
+  }
+  Ok(())
+}
+
+fn parse_key(operator: &str, value: &JsonValue) -> Result<String, String> {
+  match value {
+    JsonValue::String(key) => Ok(key.clone()),
+    _ => Err(format!(
+      "`{}` expects a string key, found `{}`",
+      operator, value
+    )),
+  }
+}
+
+/// Parses a value in expression syntax, where arrays are always expressions.
+fn parse_expression(value: &JsonValue) -> Result<Expression, String> {
+  let items = match value {
+    JsonValue::Array(items) => items,
+    JsonValue::Object(_) => return Err(format!("unexpected object `{}`", value)),
+    _ => return Ok(Expression::Literal(Datum::from(value))),
+  };
+  let operator = match items.first() {
+    Some(JsonValue::String(operator)) => operator.as_str(),
+    _ => return Err(String::from("expected an operator as first element")),
+  };
+  let arguments = &items[1..];
+
+  if let Some(comparison) = comparison_operator(operator) {
+    expect_arguments(operator, arguments, 2)?;
+    return Ok(Expression::Compare(
+      comparison,
+      Box::new(parse_expression(&arguments[0])?),
+      Box::new(parse_expression(&arguments[1])?),
+    ));
+  }
+
+  match operator {
+    "literal" => {
+      expect_arguments(operator, arguments, 1)?;
+      Ok(Expression::Literal(Datum::from(&arguments[0])))
+    }
+    "get" => {
+      expect_arguments(operator, arguments, 1)?;
+      Ok(Expression::Get(parse_key(operator, &arguments[0])?))
+    }
+    "has" => {
+      expect_arguments(operator, arguments, 1)?;
+      Ok(Expression::Has(parse_key(operator, &arguments[0])?))
+    }
+    "id" => {
+      expect_arguments(operator, arguments, 0)?;
+      Ok(Expression::Id)
+    }
+    "geometry-type" => {
+      expect_arguments(operator, arguments, 0)?;
+      Ok(Expression::GeometryType)
+    }
+    "zoom" => {
+      expect_arguments(operator, arguments, 0)?;
+      Ok(Expression::Zoom)
+    }
+    "!" => {
+      expect_arguments(operator, arguments, 1)?;
+      Ok(Expression::Not(Box::new(parse_expression(&arguments[0])?)))
+    }
+    "all" => Ok(Expression::All(parse_all(arguments, parse_expression)?)),
+    "any" => Ok(Expression::Any(parse_all(arguments, parse_expression)?)),
+    "in" => {
+      expect_arguments(operator, arguments, 2)?;
+      Ok(Expression::In(
+        Box::new(parse_expression(&arguments[0])?),
+        Box::new(parse_expression(&arguments[1])?),
+      ))
+    }
+    "match" => {
+      if arguments.len() < 2 || arguments.len() % 2 != 0 {
+        return Err(String::from(
+          "`match` expects an input, label/output pairs and a fallback",
+        ));
+      }
+      let branches = arguments[1..arguments.len() - 1]
+        .chunks(2)
+        .map(|pair| {
+          let labels = match &pair[0] {
+            JsonValue::Array(labels) => labels.iter().map(Datum::from).collect(),
+            label => vec![Datum::from(label)],
+          };
+          Ok((labels, parse_expression(&pair[1])?))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+      Ok(Expression::Match(
+        Box::new(parse_expression(&arguments[0])?),
+        branches,
+        Box::new(parse_expression(&arguments[arguments.len() - 1])?),
+      ))
+    }
+    "case" => {
+      if arguments.is_empty() || arguments.len() % 2 != 1 {
+        return Err(String::from(
+          "`case` expects condition/output pairs and a fallback",
+        ));
+      }
+      let branches = arguments[..arguments.len() - 1]
+        .chunks(2)
+        .map(|pair| Ok((parse_expression(&pair[0])?, parse_expression(&pair[1])?)))
+        .collect::<Result<Vec<_>, String>>()?;
+      Ok(Expression::Case(
+        branches,
+        Box::new(parse_expression(&arguments[arguments.len() - 1])?),
+      ))
+    }
+    "coalesce" => Ok(Expression::Coalesce(parse_all(
+      arguments,
+      parse_expression,
+    )?)),
+    _ => Err(format!("unsupported operator `{}`", operator)),
+  }
+}
@@ -0,0 +1,165 @@
+//! This module provides the `FlatLayer` struct.
+//!
+//! The `FlatLayer` struct stores the features of a layer in a few flat buffers instead of one object per
+//! feature, which suits binary rendering pipelines and avoids the cost of creating many small objects in
+//! JavaScript. The geometry layout follows nested offsets similar to GeoArrow:
+//!
+//! - The parts of feature `i` are `part_offsets[feature_offsets[i]..feature_offsets[i + 1]]`.
+//! - The rings of part `j` are `ring_offsets[part_offsets[j]..part_offsets[j + 1]]`.
+//! - The vertices of ring `k` are `ring_offsets[k]..ring_offsets[k + 1]`, each stored as an `x, y` pair in `coordinates`.
+//!
+//! A part is a polygon, a line or the points of a feature. Polygons have their exterior ring first followed
+//! by their holes, lines and points have exactly one ring. Polygon rings keep their closing vertex.
+//!
+//! # Types
+//!
+//! The `flat` module defines the following types:
+//!
+//! - `FlatLayer`: Represents the features of a layer in flat buffers.
+//! - `PropertyColumn`: Represents the values of a single property for all features of a layer.
+
+use geo_types::{Geometry, LineString, Polygon};
+
+use crate::feature::{Feature, Value};
+
+/// A structure representing the features of a layer in flat buffers.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FlatLayer {
+  /// The vertex positions as consecutive `x, y` pairs in tile coordinates.
+  pub coordinates: Vec<i32>,
+
+  /// The offsets of the vertices of each ring, with one more entry than there are rings.
+  pub ring_offsets: Vec<u32>,
+
+  /// The offsets of the rings of each part, with one more entry than there are parts.
+  pub part_offsets: Vec<u32>,
+
+  /// The offsets of the parts of each feature, with one more entry than there are features.
+  pub feature_offsets: Vec<u32>,
+
+  /// The geometry type of each feature, `1` for points, `2` for lines and `3` for polygons as in the vector tile specification.
+  pub geometry_types: Vec<u8>,
+
+  /// The id of each feature.
+  pub ids: Vec<Option<u64>>,
+
+  /// The properties of the features as columns, in the order of the keys of the layer.
+  pub properties: Vec<PropertyColumn>,
+}
+
+/// A structure representing the values of a single property for all features of a layer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyColumn {
+  /// The name of the property.
+  pub name: String,
+
+  /// The value of the property for each feature, `None` if the feature does not have the property.
+  pub values: Vec<Option<Value>>,
+}
+
+impl FlatLayer {
+  /// Returns the number of features.
+  pub fn len(&self) -> usize {
+    self.geometry_types.len()
+  }
+
+  /// Returns `true` if the layer has no features.
+  pub fn is_empty(&self) -> bool {
+    self.geometry_types.is_empty()
+  }
+
+  /// Builds the buffers from decoded features, with one property column per key that is used by any feature.
+  pub(crate) fn from_features(keys: &[String], features: Vec<Feature<i32>>) -> Self {
+    let mut layer = FlatLayer {
+      ring_offsets: vec![0],
+      part_offsets: vec![0],
+      feature_offsets: vec![0],
+      properties: keys
+        .iter()
+        .map(|name| PropertyColumn {
+          name: name.clone(),
+          values: Vec::with_capacity(features.len()),
+        })
+        .collect(),
+      ..Default::default()
+    };
+
+    for feature in features {
+      let geometry_type = layer.push_geometry(&feature.geometry);
+      layer.geometry_types.push(geometry_type);
+      layer
+        .feature_offsets
+        .push(layer.part_offsets.len() as u32 - 1);
+      layer.ids.push(feature.id);
+
+      let mut properties = feature.properties.unwrap_or_default();
+      for column in layer.properties.iter_mut() {
+        column.values.push(properties.remove(&column.name));
+      }
+    }
+
+    layer
+      .properties
+      .retain(|column| column.values.iter().any(Option::is_some));
+    layer
+  }
+
+  fn push_ring(&mut self, ring: &LineString<i32>) {
+    for coord in ring {
+      self.coordinates.push(coord.x);
+      self.coordinates.push(coord.y);
+    }
+    self.ring_offsets.push(self.coordinates.len() as u32 / 2);
+  }
+
+  fn finish_part(&mut self) {
+    self.part_offsets.push(self.ring_offsets.len() as u32 - 1);
+  }
+
+  fn push_polygon(&mut self, polygon: &Polygon<i32>) {
+    self.push_ring(polygon.exterior());
+    for interior in polygon.interiors() {
+      self.push_ring(interior);
+    }
+    self.finish_part();
+  }
+
+  /// Appends the parts of a geometry and returns its geometry type.
+  fn push_geometry(&mut self, geometry: &Geometry<i32>) -> u8 {
+    match geometry {
+      Geometry::Point(point) => {
+        self.push_ring(&LineString::new(vec![point.0]));
+        self.finish_part();
+        1
+      }
+      Geometry::MultiPoint(points) => {
+        self.push_ring(&points.iter().map(|point| point.0).collect());
+        self.finish_part();
+        1
+      }
+      Geometry::LineString(linestring) => {
+        self.push_ring(linestring);
+        self.finish_part();
+        2
+      }
+      Geometry::MultiLineString(linestrings) => {
+        for linestring in linestrings {
+          self.push_ring(linestring);
+          self.finish_part();
+        }
+        2
+      }
+      Geometry::Polygon(polygon) => {
+        self.push_polygon(polygon);
+        3
+      }
+      Geometry::MultiPolygon(polygons) => {
+        for polygon in polygons {
+          self.push_polygon(polygon);
+        }
+        3
+      }
+      _ => 0,
+    }
+  }
+}
@@ -10,7 +10,7 @@
 //!
 //! ```toml
 //! [dependencies]
-//! mvt-reader = "2.3.0"
+//! mvt-reader = "3.0.0"
 //! ```
 //!
 //! Then, you can import and use the library in your code:
@@ -45,39 +45,79 @@
 //! The `mvt-reader` library provides the following features:
 //!
 //! - `wasm`: Enables the compilation of the library as a WebAssembly module, allowing usage in JavaScript/TypeScript projects.
-//! - `protoc`: Enables the use of `prost-build` to compile the protobuf definition sources from `vector_tile.proto`. This is useful for development and testing purposes, but it is not required for using the library in production. If the `protoc` feature is not enabled, the library will use pre-generated Rust code for the protobuf definitions.
+//! - `filter`: Enables the `filter` module for evaluating Mapbox GL / MapLibre style-spec filter expressions against features.
+//! - `tessellate`: Enables the `tessellate` module for triangulating polygons and extruding lines into vertex and index buffers. Together with the `wasm` feature, it adds `tessellateLayer` and `extrudeLayer` to the WebAssembly module.
+//! - `mvt3`: Decodes the additions of the MVT 3 draft specification, such as inlined attributes with list and map values, elevations, geometric attributes and splines, and accepts layers of version 3.
+//! - `serde`: Implements `Serialize` for values, features and layers and enables the `de` module for deserializing the properties of features into custom types with [`Reader::deserialize_features`].
+//! - `rstar`: Stores the feature envelopes of each layer in an `rstar` R-tree to speed up [`Reader::query_bbox`] on large layers.
+//! - `protoc`: Enables the use of `prost-build` to compile the protobuf definition sources from `vector_tile.proto`, or from the vendored draft in `draft/vector_tile.proto` together with the `mvt3` feature. This is useful for development and testing purposes, but it is not required for using the library in production. If the `protoc` feature is not enabled, the library will use pre-generated Rust code for the protobuf definitions.
 //!
 //! To enable the `wasm` feature, add the following to your `Cargo.toml` file:
 //!
 //! ```toml
 //! [dependencies]
-//! mvt-reader = { version = "2.3.0", features = ["wasm"] }
+//! mvt-reader = { version = "3.0.0", features = ["wasm"] }
 //! ```
 //! 
 //! To enable the `protoc` feature, add the following to your `Cargo.toml` file:
 //! 
 //! ```toml
 //! [dependencies]
-//! mvt-reader = { version = "2.3.0", features = ["protoc"] }
+//! mvt-reader = { version = "3.0.0", features = ["protoc"] }
 //! ```
 //!
 //! # License
 //!
 //! This project is licensed under the [MIT License](https://github.com/codeart1st/mvt-reader/blob/main/LICENSE).
 
+#[cfg(feature = "mvt3")]
+mod attributes;
+mod clip;
+#[cfg(feature = "serde")]
+pub mod de;
+pub mod diff;
+#[cfg(feature = "mvt3")]
+pub mod elevation;
+mod encode;
 pub mod error;
 pub mod feature;
+#[cfg(feature = "filter")]
+pub mod filter;
+pub mod flat;
 pub mod layer;
+pub mod options;
+mod overzoom;
+pub mod query;
+pub mod schema;
+#[cfg(feature = "serde")]
+mod ser;
+mod simplify;
+#[cfg(feature = "mvt3")]
+pub mod spline;
+pub mod stats;
+pub mod stitch;
+#[cfg(feature = "tessellate")]
+pub mod tessellate;
 
 mod vector_tile;
 
-use feature::{Feature, Value};
+pub use diff::diff;
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use feature::{Feature, PropertyView, Value};
 use geo_types::{
-  Coord, CoordNum, Geometry, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon,
+  Coord, CoordNum, Geometry, GeometryCollection, Line, LineString, MultiLineString, MultiPoint,
+  MultiPolygon, Point, Polygon, Rect, Triangle,
 };
 use layer::Layer;
 use num_traits::NumCast;
-use prost::{Message, bytes::Bytes};
+use options::FeatureOptions;
+use prost::{
+  Message,
+  bytes::{Buf, Bytes},
+};
 use vector_tile::{Tile, tile::GeomType};
 
 /// The dimension used for the vector tile.
@@ -86,6 +126,31 @@ const DIMENSION: u32 = 2;
 /// Reader for decoding and accessing vector tile data.
 pub struct Reader {
   tile: Tile,
+
+  /// The encoded tile, if the reader was created from tile data.
+  data: Option<Bytes>,
+
+  /// Lazily built lookup structures, one per layer.
+  caches: Vec<LayerCache>,
+}
+
+/// Lookup structures of a layer that are built on first use.
+#[derive(Default)]
+struct LayerCache {
+  /// Map from feature id to feature indices.
+  id_index: OnceLock<HashMap<u64, Vec<usize>>>,
+
+  /// Envelope of each feature's encoded geometry.
+  envelopes: OnceLock<Vec<Option<Rect<i32>>>>,
+
+  /// Keys and values shared by the properties of the features.
+  table: OnceLock<std::sync::Arc<feature::LayerTable>>,
+
+  /// R-tree over the feature envelopes.
+  #[cfg(feature = "rstar")]
+  rtree: OnceLock<
+    rstar::RTree<rstar::primitives::GeomWithData<rstar::primitives::Rectangle<[i32; 2]>, usize>>,
+  >,
 }
 
 impl Reader {
@@ -108,8 +173,16 @@ impl Reader {
   /// let reader = Reader::new(data);
   /// ```
   pub fn new(data: Vec<u8>) -> Result<Self, error::ParserError> {
-    match Tile::decode(Bytes::from(data)) {
-      Ok(tile) => Ok(Self { tile }),
+    let data = Bytes::from(data);
+    let mut reader = Self::decode(data.clone())?;
+    reader.data = Some(data);
+    Ok(reader)
+  }
+
+  /// Decodes the vector tile data from any buffer.
+  fn decode<B: Buf>(data: B) -> Result<Self, error::ParserError> {
+    match Tile::decode(data) {
+      Ok(tile) => Ok(Self::from_tile(tile)),
       Err(error) => Err(error::ParserError::new(error::DecodeError::new(Box::new(
         error,
       )))),
@@ -181,6 +254,95 @@ impl Reader {
     })
   }
 
+  /// Computes the size breakdown and content statistics of each layer in the vector tile.
+  ///
+  /// Unlike the other methods, the statistics describe the encoded protobuf messages. Layers of any
+  /// version are included and invalid tags or geometries are counted as far as they can be read.
+  ///
+  /// # Returns
+  ///
+  /// The `TileStats` of the tile.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::Reader;
+  ///
+  /// let data = vec![/* Vector tile data */];
+  /// let reader = Reader::new(data).unwrap();
+  ///
+  /// for layer in reader.stats().layers {
+  ///   println!(
+  ///     "{}: {} bytes, {} in geometries, {} unused keys",
+  ///     layer.name,
+  ///     layer.encoded_size,
+  ///     layer.geometry_size,
+  ///     layer.unused_keys.len()
+  ///   );
+  /// }
+  /// ```
+  pub fn stats(&self) -> stats::TileStats {
+    match &self.data {
+      Some(data) => stats::tile_stats(&self.tile, data),
+      // e.g. an overzoomed tile, which is measured as it would be written by `to_bytes`
+      None => stats::tile_stats(&self.tile, &self.to_bytes()),
+    }
+  }
+
+  /// Infers the schema of the properties of each layer in the vector tile.
+  ///
+  /// Properties are decoded like those of [`get_features`](Reader::get_features), so the last value of a
+  /// duplicate key wins. Geometries are not decoded. Up to
+  /// [`DEFAULT_MAX_SAMPLES`](schema::DEFAULT_MAX_SAMPLES) distinct sample strings are kept per key.
+  ///
+  /// # Returns
+  ///
+  /// A result containing the `TileSchema` if successful, or a `ParserError` if there is an error parsing the tile.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::Reader;
+  ///
+  /// let data = vec![/* Vector tile data */];
+  /// let reader = Reader::new(data).unwrap();
+  ///
+  /// for layer in reader.infer_schema().unwrap().layers {
+  ///   for (key, field) in &layer.fields {
+  ///     println!("{}.{}: {} ({:.0}% null)", layer.name, key, field.field_type(), layer.null_ratio(key) * 100.0);
+  ///   }
+  /// }
+  /// ```
+  pub fn infer_schema(&self) -> Result<schema::TileSchema, error::ParserError> {
+    self.infer_schema_with_samples(schema::DEFAULT_MAX_SAMPLES)
+  }
+
+  /// Infers the schema of the properties of each layer like [`infer_schema`](Reader::infer_schema), keeping the given number of sample strings per key.
+  ///
+  /// # Arguments
+  ///
+  /// * `max_samples` - The maximum number of distinct sample strings kept per key.
+  ///
+  /// # Returns
+  ///
+  /// A result containing the `TileSchema` if successful, or a `ParserError` if there is an error parsing the tile.
+  pub fn infer_schema_with_samples(
+    &self,
+    max_samples: usize,
+  ) -> Result<schema::TileSchema, error::ParserError> {
+    let mut tile_schema = schema::TileSchema::new(max_samples);
+    for layer_index in process_layers(&self.tile.layers, |_, index| index)? {
+      let layer = &self.tile.layers[layer_index];
+      let layer_schema = tile_schema.layer_mut(&layer.name);
+      for (feature_index, feature) in layer.features.iter().enumerate() {
+        let properties = parse_properties::<HashMap<String, Value>>(layer, feature)
+          .map_err(|error| error.with_context(layer_index, Some(feature_index)))?;
+        layer_schema.observe(&properties, max_samples);
+      }
+    }
+    Ok(tile_schema)
+  }
+
   /// Retrieves the features of a specific layer in the vector tile.
   ///
   /// # Arguments
@@ -245,46 +407,1302 @@ impl Reader {
   /// // Get features with i16 coordinates
   /// let features = reader.get_features_as::<i16>(0);
   /// ```
-  pub fn get_features_as<T: CoordNum>(&self, layer_index: usize) -> Result<Vec<Feature<T>>, error::ParserError> {
-    let layer = self.tile.layers.get(layer_index);
-    match layer {
-      Some(layer) => {
-        let mut features = Vec::with_capacity(layer.features.len());
-        for feature in layer.features.iter() {
-          if let Some(geom_type) = feature.r#type {
-            match GeomType::try_from(geom_type) {
-              Ok(geom_type) => {
-                let parsed_geometry = match parse_geometry::<T>(&feature.geometry, geom_type) {
-                  Ok(parsed_geometry) => parsed_geometry,
-                  Err(error) => {
-                    return Err(error);
-                  }
-                };
-
-                let parsed_tags = match parse_tags(&feature.tags, &layer.keys, &layer.values) {
-                  Ok(parsed_tags) => parsed_tags,
-                  Err(error) => {
-                    return Err(error);
-                  }
-                };
-
-                features.push(Feature {
-                  geometry: parsed_geometry,
-                  id: feature.id,
-                  properties: Some(parsed_tags),
-                });
-              }
-              Err(error) => {
-                return Err(error::ParserError::new(error::DecodeError::new(Box::new(
-                  error,
-                ))));
-              }
-            }
-          }
-        }
-        Ok(features)
+  pub fn get_features_as<T: CoordNum>(
+    &self,
+    layer_index: usize,
+  ) -> Result<Vec<Feature<T>>, error::ParserError> {
+    self.collect_features(layer_index, &FeatureOptions::default(), |_, _, _| true)
+  }
+
+  /// Retrieves the features of a specific layer, processing their geometries according to the given options.
+  ///
+  /// The processing steps operate on the integer tile coordinates before they are converted to `T`.
+  ///
+  /// # Arguments
+  ///
+  /// * `layer_index` - The index of the layer.
+  /// * `options` - The processing steps to apply.
+  ///
+  /// # Type Parameters
+  ///
+  /// * `T` - The numeric type for geometry coordinates (e.g. `f32`, `i32`, `i16`).
+  ///
+  /// # Returns
+  ///
+  /// A result containing a vector of features if successful, or a `ParserError` if there is an error parsing the tile or accessing the layer.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::{Reader, options::{FeatureOptions, Simplification}};
+  ///
+  /// let data = vec![/* Vector tile data */];
+  /// let reader = Reader::new(data).unwrap();
+  ///
+  /// // Clip geometries to the tile extent with a buffer of 64 units
+  /// let options = FeatureOptions {
+  ///   clip_buffer: Some(64),
+  ///   ..Default::default()
+  /// };
+  /// let features = reader.get_features_with_options::<f32>(0, &options);
+  ///
+  /// // Reduce geometries for a 256px canvas
+  /// let options = FeatureOptions {
+  ///   resolution: Some(256),
+  ///   simplification: Some(Simplification::DouglasPeucker(8.0)),
+  ///   ..Default::default()
+  /// };
+  /// let features = reader.get_features_with_options::<f32>(0, &options);
+  /// ```
+  pub fn get_features_with_options<T: CoordNum>(
+    &self,
+    layer_index: usize,
+    options: &FeatureOptions,
+  ) -> Result<Vec<Feature<T>>, error::ParserError> {
+    self.collect_features(layer_index, options, |_, _, _| true)
+  }
+
+  /// Retrieves the features of a specific layer that pass a style-spec filter expression.
+  ///
+  /// The filter is evaluated against the id, geometry type and properties of each feature before its
+  /// geometry is decoded, so rejected features do not pay for geometry decoding.
+  ///
+  /// # Arguments
+  ///
+  /// * `layer_index` - The index of the layer.
+  /// * `filter` - The filter to apply.
+  /// * `zoom` - The zoom level used for `["zoom"]` expressions.
+  ///
+  /// # Returns
+  ///
+  /// A result containing a vector of the accepted features if successful, or a `ParserError` if there is an error parsing the tile or accessing the layer.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::{Reader, filter::Filter};
+  ///
+  /// let data = vec![/* Vector tile data */];
+  /// let reader = Reader::new(data).unwrap();
+  ///
+  /// let filter = Filter::parse(r#"["==", ["get", "class"], "primary"]"#).unwrap();
+  /// let features = reader.get_features_filtered(0, &filter, 14.0);
+  /// ```
+  #[cfg(feature = "filter")]
+  pub fn get_features_filtered(
+    &self,
+    layer_index: usize,
+    filter: &filter::Filter,
+    zoom: f64,
+  ) -> Result<Vec<Feature>, error::ParserError> {
+    self.collect_features(
+      layer_index,
+      &FeatureOptions::default(),
+      |feature, geom_type, properties| {
+        filter.evaluate_with(&filter::Context {
+          id: feature.id,
+          geometry_type: filter::geom_type_name(geom_type),
+          properties,
+          zoom,
+        })
+      },
+    )
+  }
+
+  /// Retrieves the features of a specific layer that are accepted by a predicate on their properties.
+  ///
+  /// The predicate receives a [`PropertyView`] that reads the layer's key and value tables directly.
+  /// Properties are only copied and geometries are only decoded for accepted features.
+  ///
+  /// # Arguments
+  ///
+  /// * `layer_index` - The index of the layer.
+  /// * `predicate` - A closure that returns `true` for features that should be decoded.
+  ///
+  /// # Returns
+  ///
+  /// A result containing a vector of the accepted features if successful, or a `ParserError` if there is an error parsing the tile or accessing the layer.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::Reader;
+  ///
+  /// let data = vec![/* Vector tile data */];
+  /// let reader = Reader::new(data).unwrap();
+  ///
+  /// let features = reader.get_features_where(0, |props| props.get_str("class") == Some("primary"));
+  /// ```
+  pub fn get_features_where<P>(
+    &self,
+    layer_index: usize,
+    predicate: P,
+  ) -> Result<Vec<Feature>, error::ParserError>
+  where
+    P: FnMut(&PropertyView) -> bool,
+  {
+    self.get_features_as_where::<f32, P>(layer_index, predicate)
+  }
+
+  /// Retrieves the features of a specific layer that are accepted by a predicate on their properties, with
+  /// geometry coordinates in the specified numeric type.
+  ///
+  /// This is a generic version of [`get_features_where`](Reader::get_features_where).
+  ///
+  /// # Arguments
+  ///
+  /// * `layer_index` - The index of the layer.
+  /// * `predicate` - A closure that returns `true` for features that should be decoded.
+  ///
+  /// # Type Parameters
+  ///
+  /// * `T` - The numeric type for geometry coordinates (e.g. `f32`, `i32`, `i16`).
+  ///
+  /// # Returns
+  ///
+  /// A result containing a vector of the accepted features if successful, or a `ParserError` if there is an error parsing the tile or accessing the layer.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::Reader;
+  ///
+  /// let data = vec![/* Vector tile data */];
+  /// let reader = Reader::new(data).unwrap();
+  ///
+  /// let features = reader.get_features_as_where::<i32, _>(0, |props| props.contains_key("name"));
+  /// ```
+  pub fn get_features_as_where<T, P>(
+    &self,
+    layer_index: usize,
+    mut predicate: P,
+  ) -> Result<Vec<Feature<T>>, error::ParserError>
+  where
+    T: CoordNum,
+    P: FnMut(&PropertyView) -> bool,
+  {
+    self.collect_features(
+      layer_index,
+      &FeatureOptions::default(),
+      |_, _, properties| predicate(properties),
+    )
+  }
+
+  /// Retrieves the features of a specific layer with their properties in encoding order.
+  ///
+  /// Unlike [`get_features`](Reader::get_features), the order of the properties and the values of duplicate
+  /// keys are kept.
+  ///
+  /// # Arguments
+  ///
+  /// * `layer_index` - The index of the layer.
+  ///
+  /// # Type Parameters
+  ///
+  /// * `T` - The numeric type for geometry coordinates (e.g. `f32`, `i32`, `i16`).
+  ///
+  /// # Returns
+  ///
+  /// A result containing a vector of features if successful, or a `ParserError` if there is an error parsing the tile or accessing the layer.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::Reader;
+  ///
+  /// let data = vec![/* Vector tile data */];
+  /// let reader = Reader::new(data).unwrap();
+  ///
+  /// for feature in reader.get_features_ordered::<f32>(0).unwrap_or_default() {
+  ///   for key in feature.duplicate_keys() {
+  ///     println!("duplicate key {}", key);
+  ///   }
+  /// }
+  /// ```
+  pub fn get_features_ordered<T: CoordNum>(
+    &self,
+    layer_index: usize,
+  ) -> Result<Vec<feature::OrderedFeature<T>>, error::ParserError> {
+    let features = self.collect_features_with(
+      layer_index,
+      &FeatureOptions::default(),
+      |_, _, _| true,
+      decode_properties,
+    )?;
+    Ok(
+      features
+        .into_iter()
+        .map(|feature| feature::OrderedFeature {
+          geometry: feature.geometry,
+          id: feature.id,
+          properties: Some(feature.properties),
+        })
+        .collect(),
+    )
+  }
+
+  /// Retrieves the features of a specific layer with properties that share the keys and values of the layer.
+  ///
+  /// The keys and values of a layer are decoded once and shared by all of its features, which only store
+  /// their tags. This avoids copying the same strings into every feature of large layers. Use
+  /// [`SharedFeature::into_owned`](feature::SharedFeature::into_owned) to convert a feature into one with a `HashMap`.
+  ///
+  /// # Arguments
+  ///
+  /// * `layer_index` - The index of the layer.
+  ///
+  /// # Type Parameters
+  ///
+  /// * `T` - The numeric type for geometry coordinates (e.g. `f32`, `i32`, `i16`).
+  ///
+  /// # Returns
+  ///
+  /// A result containing a vector of features if successful, or a `ParserError` if there is an error parsing the tile or accessing the layer.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::Reader;
+  ///
+  /// let data = vec![/* Vector tile data */];
+  /// let reader = Reader::new(data).unwrap();
+  ///
+  /// for feature in reader.get_features_shared::<f32>(0).unwrap_or_default() {
+  ///   if let Some(properties) = &feature.properties {
+  ///     println!("{:?}", properties.get("name"));
+  ///   }
+  /// }
+  /// ```
+  pub fn get_features_shared<T: CoordNum>(
+    &self,
+    layer_index: usize,
+  ) -> Result<Vec<feature::SharedFeature<T>>, error::ParserError> {
+    let Some(table) = self.layer_table(layer_index) else {
+      return Ok(vec![]);
+    };
+    let features = self.collect_features_with(
+      layer_index,
+      &FeatureOptions::default(),
+      |_, _, _| true,
+      |layer, feature| {
+        let attributes = parse_inlined_attributes(layer, feature)?
+          .into_iter()
+          .map(|(key, value)| (key.into(), value))
+          .collect();
+        Ok(feature::SharedProperties::new(
+          table.clone(),
+          feature.tags.clone(),
+          attributes,
+        ))
+      },
+    )?;
+    Ok(
+      features
+        .into_iter()
+        .map(|feature| feature::SharedFeature {
+          geometry: feature.geometry,
+          id: feature.id,
+          properties: Some(feature.properties),
+        })
+        .collect(),
+    )
+  }
+
+  /// Retrieves the geometries of a specific layer together with their properties deserialized into a custom type.
+  ///
+  /// The properties are decoded like those of [`get_features`](Reader::get_features), so the last value of a
+  /// duplicate key wins, and deserialized with [`de::from_properties`]. If the properties of a feature do not
+  /// match the type, a `DeserializeError` naming the layer, the feature id and the property is returned.
+  ///
+  /// # Arguments
+  ///
+  /// * `layer_index` - The index of the layer.
+  ///
+  /// # Type Parameters
+  ///
+  /// * `R` - The type the properties of each feature are deserialized into.
+  ///
+  /// # Returns
+  ///
+  /// A result containing a vector of geometries and deserialized properties if successful, or a `ParserError` if there is an error parsing the tile or deserializing the properties.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::Reader;
+  /// use serde::Deserialize;
+  ///
+  /// #[derive(Deserialize)]
+  /// struct Road {
+  ///   class: String,
+  ///   lanes: Option<u8>,
+  /// }
+  ///
+  /// let data = vec![/* Vector tile data */];
+  /// let reader = Reader::new(data).unwrap();
+  ///
+  /// for (geometry, road) in reader.deserialize_features::<Road>(0).unwrap_or_default() {
+  ///   println!("{} with {:?} lanes", road.class, road.lanes);
+  /// }
+  /// ```
+  #[cfg(feature = "serde")]
+  pub fn deserialize_features<R>(
+    &self,
+    layer_index: usize,
+  ) -> Result<Vec<(Geometry<f32>, R)>, error::ParserError>
+  where
+    R: serde::de::DeserializeOwned,
+  {
+    let features = self.collect_features_with::<f32, R, _, _>(
+      layer_index,
+      &FeatureOptions::default(),
+      |_, _, _| true,
+      |layer, feature| {
+        let properties = decode_properties::<HashMap<String, Value>>(layer, feature)?;
+        de::from_properties(properties).map_err(|source| {
+          error::ParserError::new(error::DeserializeError::new(
+            layer.name.clone(),
+            feature.id,
+            source.to_string(),
+          ))
+        })
+      },
+    )?;
+    Ok(
+      features
+        .into_iter()
+        .map(|feature| (feature.geometry, feature.properties))
+        .collect(),
+    )
+  }
+
+  /// Retrieves the first feature with the given id in a specific layer.
+  ///
+  /// Ids are optional and not guaranteed to be unique. Features without an id are never returned and if
+  /// several features share the id, the first one in layer order is returned. Use
+  /// [`features_by_id`](Reader::features_by_id) to retrieve all of them.
+  ///
+  /// The id index of a layer is built on first use and reused by later lookups.
+  ///
+  /// # Arguments
+  ///
+  /// * `layer_index` - The index of the layer.
+  /// * `id` - The id of the feature.
+  ///
+  /// # Returns
+  ///
+  /// A result containing the feature, or `None` if the layer or the id does not exist, or a `ParserError` if there is an error parsing the feature.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::Reader;
+  ///
+  /// let data = vec![/* Vector tile data */];
+  /// let reader = Reader::new(data).unwrap();
+  ///
+  /// match reader.feature_by_id(0, 42) {
+  ///   Ok(Some(feature)) => println!("{:?}", feature.properties),
+  ///   Ok(None) => println!("Feature not found"),
+  ///   Err(error) => {
+  ///     todo!();
+  ///   }
+  /// }
+  /// ```
+  pub fn feature_by_id(
+    &self,
+    layer_index: usize,
+    id: u64,
+  ) -> Result<Option<Feature>, error::ParserError> {
+    for index in self.feature_indices_by_id(layer_index, id) {
+      if let Some(feature) = self.read_feature(layer_index, *index)? {
+        return Ok(Some(feature));
+      }
+    }
+    Ok(None)
+  }
+
+  /// Retrieves all features with the given id in a specific layer, in layer order.
+  ///
+  /// # Arguments
+  ///
+  /// * `layer_index` - The index of the layer.
+  /// * `id` - The id of the features.
+  ///
+  /// # Returns
+  ///
+  /// A result containing a vector of the matching features, which is empty if the layer or the id does not exist, or a `ParserError` if there is an error parsing a feature.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::Reader;
+  ///
+  /// let data = vec![/* Vector tile data */];
+  /// let reader = Reader::new(data).unwrap();
+  ///
+  /// let features = reader.features_by_id(0, 42);
+  /// ```
+  pub fn features_by_id(
+    &self,
+    layer_index: usize,
+    id: u64,
+  ) -> Result<Vec<Feature>, error::ParserError> {
+    let mut features = Vec::new();
+    for index in self.feature_indices_by_id(layer_index, id) {
+      if let Some(feature) = self.read_feature(layer_index, *index)? {
+        features.push(feature);
+      }
+    }
+    Ok(features)
+  }
+
+  /// Retrieves the indices of all features with the given id in a specific layer.
+  ///
+  /// # Arguments
+  ///
+  /// * `layer_index` - The index of the layer.
+  /// * `id` - The id of the features.
+  ///
+  /// # Returns
+  ///
+  /// The feature indices in ascending order, empty if the layer or the id does not exist.
+  pub fn feature_indices_by_id(&self, layer_index: usize, id: u64) -> &[usize] {
+    let (Some(layer), Some(cache)) = (
+      self.tile.layers.get(layer_index),
+      self.caches.get(layer_index),
+    ) else {
+      return &[];
+    };
+    let index = cache.id_index.get_or_init(|| {
+      let mut index: HashMap<u64, Vec<usize>> = HashMap::new();
+      for (feature_index, feature) in layer.features.iter().enumerate() {
+        if let Some(id) = feature.id {
+          index.entry(id).or_default().push(feature_index);
+        }
+      }
+      index
+    });
+    index.get(&id).map(Vec::as_slice).unwrap_or(&[])
+  }
+
+  /// Retrieves the features under a point, e.g. for hover tooltips or click handlers.
+  ///
+  /// Polygons match if the point lies inside them (outside of their holes) or within `tolerance` of their
+  /// outline, lines match if the point lies within `tolerance` of one of their segments and points match
+  /// if the point lies within a radius of `tolerance` around them.
+  ///
+  /// # Arguments
+  ///
+  /// * `layer_index` - The index of the layer to query, or `None` to query all layers.
+  /// * `x` - The x coordinate of the point in tile coordinates.
+  /// * `y` - The y coordinate of the point in tile coordinates.
+  /// * `tolerance` - The maximum distance in tile coordinates.
+  ///
+  /// # Returns
+  ///
+  /// A result containing the matching features sorted by ascending distance if successful, or a `ParserError` if there is an error parsing the tile.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::Reader;
+  ///
+  /// let data = vec![/* Vector tile data */];
+  /// let reader = Reader::new(data).unwrap();
+  ///
+  /// match reader.query_point(None, 2048.0, 2048.0, 16.0) {
+  ///   Ok(results) => {
+  ///     for result in results {
+  ///       println!("Layer {}: {:?} at {}", result.layer_index, result.feature.id, result.distance);
+  ///     }
+  ///   }
+  ///   Err(error) => {
+  ///     todo!();
+  ///   }
+  /// }
+  /// ```
+  pub fn query_point(
+    &self,
+    layer_index: Option<usize>,
+    x: f64,
+    y: f64,
+    tolerance: f64,
+  ) -> Result<Vec<query::QueryResult>, error::ParserError> {
+    let layer_indices = match layer_index {
+      Some(layer_index) => layer_index..layer_index.saturating_add(1).min(self.tile.layers.len()),
+      None => 0..self.tile.layers.len(),
+    };
+    let mut results = Vec::new();
+    for layer_index in layer_indices {
+      let layer = &self.tile.layers[layer_index];
+      for (feature_index, feature) in layer.features.iter().enumerate() {
+        if let Some(feature) =
+          parse_feature(layer, feature, &FeatureOptions::default(), |_, _, _| true)
+            .map_err(|error| error.with_context(layer_index, Some(feature_index)))?
+        {
+          let distance = query::distance_to_geometry((x, y), &feature.geometry);
+          if distance <= tolerance {
+            results.push(query::QueryResult {
+              layer_index,
+              feature_index,
+              distance,
+              feature,
+            });
+          }
+        }
+      }
+    }
+    results.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+    Ok(results)
+  }
+
+  /// Retrieves the features of a specific layer that intersect a rectangle, e.g. a viewport.
+  ///
+  /// Candidates are selected by the envelopes of their encoded geometries, which are computed once per
+  /// layer without decoding the geometries. With the `rstar` feature enabled, the envelopes are additionally
+  /// stored in an R-tree. Only candidates are decoded and tested exactly against the rectangle.
+  ///
+  /// # Arguments
+  ///
+  /// * `layer_index` - The index of the layer.
+  /// * `rect` - The rectangle in tile coordinates.
+  ///
+  /// # Returns
+  ///
+  /// A result containing the intersecting features in layer order if successful, or a `ParserError` if there is an error parsing the tile.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::Reader;
+  /// use geo_types::{Rect, coord};
+  ///
+  /// let data = vec![/* Vector tile data */];
+  /// let reader = Reader::new(data).unwrap();
+  ///
+  /// let viewport = Rect::new(coord! { x: 0.0, y: 0.0 }, coord! { x: 2048.0, y: 2048.0 });
+  /// let features = reader.query_bbox(0, viewport);
+  /// ```
+  pub fn query_bbox(
+    &self,
+    layer_index: usize,
+    rect: Rect<f64>,
+  ) -> Result<Vec<Feature>, error::ParserError> {
+    let (Some(layer), Some(cache)) = (
+      self.tile.layers.get(layer_index),
+      self.caches.get(layer_index),
+    ) else {
+      return Ok(vec![]);
+    };
+    let envelopes = cache.envelopes.get_or_init(|| {
+      layer
+        .features
+        .iter()
+        .map(|feature| query::envelope(&feature.geometry))
+        .collect()
+    });
+
+    let min = [rect.min().x.floor() as i32, rect.min().y.floor() as i32];
+    let max = [rect.max().x.ceil() as i32, rect.max().y.ceil() as i32];
+
+    #[cfg(feature = "rstar")]
+    let candidates = {
+      use rstar::{
+        AABB, RTree,
+        primitives::{GeomWithData, Rectangle},
+      };
+
+      let rtree = cache.rtree.get_or_init(|| {
+        RTree::bulk_load(
+          envelopes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, envelope)| {
+              envelope.map(|envelope| {
+                GeomWithData::new(
+                  Rectangle::from_corners(
+                    [envelope.min().x, envelope.min().y],
+                    [envelope.max().x, envelope.max().y],
+                  ),
+                  index,
+                )
+              })
+            })
+            .collect(),
+        )
+      });
+      let mut candidates: Vec<usize> = rtree
+        .locate_in_envelope_intersecting(&AABB::from_corners(min, max))
+        .map(|entry| entry.data)
+        .collect();
+      candidates.sort_unstable();
+      candidates
+    };
+
+    #[cfg(not(feature = "rstar"))]
+    let candidates = envelopes
+      .iter()
+      .enumerate()
+      .filter_map(|(index, envelope)| match envelope {
+        Some(envelope)
+          if envelope.min().x <= max[0]
+            && envelope.max().x >= min[0]
+            && envelope.min().y <= max[1]
+            && envelope.max().y >= min[1] =>
+        {
+          Some(index)
+        }
+        _ => None,
+      })
+      .collect::<Vec<usize>>();
+
+    let mut features = Vec::with_capacity(candidates.len());
+    for index in candidates {
+      if let Some(feature) = parse_feature(
+        layer,
+        &layer.features[index],
+        &FeatureOptions::default(),
+        |_, _, _| true,
+      )
+      .map_err(|error| error.with_context(layer_index, Some(index)))?
+        && query::intersects_rect(&rect, &feature.geometry)
+      {
+        features.push(feature);
+      }
+    }
+    Ok(features)
+  }
+
+  /// Triangulates the polygons of all features of a specific layer into a single triangle list.
+  ///
+  /// # Arguments
+  ///
+  /// * `layer_index` - The index of the layer.
+  ///
+  /// # Returns
+  ///
+  /// A result containing the `Mesh` of the layer if successful, or a `ParserError` if there is an error parsing the tile.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::Reader;
+  ///
+  /// let data = vec![/* Vector tile data */];
+  /// let reader = Reader::new(data).unwrap();
+  ///
+  /// let mesh = reader.tessellate_layer(0);
+  /// ```
+  #[cfg(feature = "tessellate")]
+  pub fn tessellate_layer(
+    &self,
+    layer_index: usize,
+  ) -> Result<tessellate::Mesh, error::ParserError> {
+    let mut mesh = tessellate::Mesh::new();
+    for feature in self.get_features_as::<f32>(layer_index)? {
+      mesh.append(&feature.tessellate());
+    }
+    Ok(mesh)
+  }
+
+  /// Extrudes the lines of all features of a specific layer into a single triangle strip.
+  ///
+  /// # Arguments
+  ///
+  /// * `layer_index` - The index of the layer.
+  /// * `line_style` - The width, join and cap of the lines.
+  ///
+  /// # Returns
+  ///
+  /// A result containing the `Mesh` of the layer if successful, or a `ParserError` if there is an error parsing the tile.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::{Reader, tessellate::LineStyle};
+  ///
+  /// let data = vec![/* Vector tile data */];
+  /// let reader = Reader::new(data).unwrap();
+  ///
+  /// let style = LineStyle { width: 8.0, ..Default::default() };
+  /// let mesh = reader.extrude_layer(0, &style);
+  /// ```
+  #[cfg(feature = "tessellate")]
+  pub fn extrude_layer(
+    &self,
+    layer_index: usize,
+    line_style: &tessellate::LineStyle,
+  ) -> Result<tessellate::Mesh, error::ParserError> {
+    let mut mesh = tessellate::Mesh::strip();
+    for feature in self.get_features_as::<f32>(layer_index)? {
+      mesh.append(&feature.extrude(line_style));
+    }
+    Ok(mesh)
+  }
+
+  /// Retrieves the features of a specific layer with the elevation and geometric attributes of each vertex.
+  ///
+  /// Elevations and geometric attributes are part of the MVT 3 draft specification. Features without
+  /// elevations are placed at elevation `0`.
+  ///
+  /// # Arguments
+  ///
+  /// * `layer_index` - The index of the layer.
+  ///
+  /// # Type Parameters
+  ///
+  /// * `T` - The numeric type for the `x` and `y` coordinates (e.g. `f32`, `i32`, `i16`).
+  ///
+  /// # Returns
+  ///
+  /// A result containing a vector of features if successful, or a `ParserError` if there is an error parsing
+  /// the tile or the number of elevations or attribute values does not match the number of vertices.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::{Reader, elevation::Geometry3D};
+  ///
+  /// let data = vec![/* Vector tile data */];
+  /// let reader = Reader::new(data).unwrap();
+  ///
+  /// for feature in reader.get_features_3d::<f32>(0).unwrap_or_default() {
+  ///   if let Geometry3D::Point(coord) = feature.geometry {
+  ///     println!("{} {} {}", coord.x, coord.y, coord.z);
+  ///   }
+  /// }
+  /// ```
+  #[cfg(feature = "mvt3")]
+  pub fn get_features_3d<T: CoordNum>(
+    &self,
+    layer_index: usize,
+  ) -> Result<Vec<elevation::Feature3D<T>>, error::ParserError> {
+    let Some(layer) = self.tile.layers.get(layer_index) else {
+      return Ok(vec![]);
+    };
+    let mut features = Vec::with_capacity(layer.features.len());
+    for (feature_index, feature) in layer.features.iter().enumerate() {
+      let feature_3d = parse_feature_3d(layer, feature)
+        .map_err(|error| error.with_context(layer_index, Some(feature_index)))?;
+      if let Some(feature_3d) = feature_3d {
+        features.push(feature_3d);
+      }
+    }
+    Ok(features)
+  }
+
+  /// Retrieves the spline features of a specific layer with their control points and knots.
+  ///
+  /// Splines are part of the MVT 3 draft specification. The other features of the layer are skipped, use
+  /// [`get_features`](Reader::get_features) to retrieve all features with splines flattened into linestrings.
+  ///
+  /// # Arguments
+  ///
+  /// * `layer_index` - The index of the layer.
+  ///
+  /// # Type Parameters
+  ///
+  /// * `T` - The numeric type for the control points (e.g. `f32`, `i32`, `i16`).
+  ///
+  /// # Returns
+  ///
+  /// A result containing a vector of spline features if successful, or a `ParserError` if there is an error parsing the tile.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::Reader;
+  ///
+  /// let data = vec![/* Vector tile data */];
+  /// let reader = Reader::new(data).unwrap();
+  ///
+  /// for feature in reader.get_splines::<f32>(0).unwrap_or_default() {
+  ///   let linestring = feature.spline.flatten(0.5).unwrap();
+  /// }
+  /// ```
+  #[cfg(feature = "mvt3")]
+  pub fn get_splines<T: CoordNum>(
+    &self,
+    layer_index: usize,
+  ) -> Result<Vec<spline::SplineFeature<T>>, error::ParserError> {
+    let Some(layer) = self.tile.layers.get(layer_index) else {
+      return Ok(vec![]);
+    };
+    let mut features = Vec::new();
+    for (feature_index, feature) in layer.features.iter().enumerate() {
+      if feature.r#type != Some(GeomType::Spline as i32) {
+        continue;
+      }
+      let spline_feature = parse_spline(layer, feature).and_then(|(spline, _)| {
+        let properties = parse_properties(layer, feature)?;
+        Ok(spline::SplineFeature {
+          spline,
+          id: feature.id,
+          properties: Some(properties),
+        })
+      });
+      features.push(
+        spline_feature.map_err(|error| error.with_context(layer_index, Some(feature_index)))?,
+      );
+    }
+    Ok(features)
+  }
+
+  /// Retrieves the features of a specific layer as flat buffers.
+  ///
+  /// See the [`flat`] module for the layout of the buffers.
+  ///
+  /// # Arguments
+  ///
+  /// * `layer_index` - The index of the layer.
+  ///
+  /// # Returns
+  ///
+  /// A result containing the `FlatLayer` if successful, or a `ParserError` if there is an error parsing the tile.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::Reader;
+  ///
+  /// let data = vec![/* Vector tile data */];
+  /// let reader = Reader::new(data).unwrap();
+  ///
+  /// if let Ok(layer) = reader.get_flat_layer(0) {
+  ///   println!("{} features with {} vertices", layer.len(), layer.coordinates.len() / 2);
+  /// }
+  /// ```
+  pub fn get_flat_layer(&self, layer_index: usize) -> Result<flat::FlatLayer, error::ParserError> {
+    let keys = match self.tile.layers.get(layer_index) {
+      Some(layer) => &layer.keys[..],
+      None => &[],
+    };
+    Ok(flat::FlatLayer::from_features(
+      keys,
+      self.get_features_as::<i32>(layer_index)?,
+    ))
+  }
+
+  /// Derives a child tile from this tile, e.g. to render zoom levels beyond the maximum zoom level of a tileset.
+  ///
+  /// The coordinates are scaled to the zoom level of the child and every feature is clipped to the child
+  /// tile plus a buffer. Features without geometry left are dropped, all other features keep their id and properties.
+  ///
+  /// # Arguments
+  ///
+  /// * `parent` - The coordinates of this tile.
+  /// * `child` - The coordinates of the child tile, which must be a descendant of the parent tile.
+  /// * `buffer` - The width of the buffer around the child tile in tile coordinates of the child.
+  ///
+  /// # Returns
+  ///
+  /// A result containing a `Reader` for the child tile if successful, or a `ParserError` if the child is not a descendant of the parent or there is an error parsing the tile.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::{Reader, stitch::TileCoordinates};
+  ///
+  /// let data = vec![/* Vector tile data */];
+  /// let reader = Reader::new(data).unwrap();
+  ///
+  /// let child = reader.overzoom(
+  ///   TileCoordinates::new(14, 8800, 5373),
+  ///   TileCoordinates::new(16, 35201, 21494),
+  ///   64,
+  /// );
+  /// ```
+  pub fn overzoom(
+    &self,
+    parent: stitch::TileCoordinates,
+    child: stitch::TileCoordinates,
+    buffer: u32,
+  ) -> Result<Reader, error::ParserError> {
+    let (dz, column, row) = overzoom::child_offset(parent, child)?;
+    let layers = process_layers(&self.tile.layers, |layer, index| {
+      overzoom::overzoom_layer(layer, index, dz, (column, row), buffer)
+    })?
+    .into_iter()
+    .collect::<Result<Vec<_>, _>>()?;
+    Ok(Self::from_tile(Tile { layers }))
+  }
+
+  /// Encodes the vector tile into bytes.
+  ///
+  /// # Returns
+  ///
+  /// The vector tile data as a byte vector, which can be read again with [`Reader::new`].
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::Reader;
+  ///
+  /// let data = vec![/* Vector tile data */];
+  /// let reader = Reader::new(data).unwrap();
+  ///
+  /// let bytes = reader.to_bytes();
+  /// ```
+  pub fn to_bytes(&self) -> Vec<u8> {
+    self.tile.encode_to_vec()
+  }
+
+  fn from_tile(tile: Tile) -> Self {
+    Self {
+      caches: tile.layers.iter().map(|_| LayerCache::default()).collect(),
+      tile,
+      data: None,
+    }
+  }
+
+  /// Decodes a single feature, returning `None` for features without geometry type.
+  fn read_feature<T: CoordNum>(
+    &self,
+    layer_index: usize,
+    feature_index: usize,
+  ) -> Result<Option<Feature<T>>, error::ParserError> {
+    match self.tile.layers.get(layer_index) {
+      Some(layer) => match layer.features.get(feature_index) {
+        Some(feature) => parse_feature(layer, feature, &FeatureOptions::default(), |_, _, _| true)
+          .map_err(|error| error.with_context(layer_index, Some(feature_index))),
+        None => Ok(None),
+      },
+      None => Ok(None),
+    }
+  }
+
+  fn collect_features<T, P>(
+    &self,
+    layer_index: usize,
+    options: &FeatureOptions,
+    accept: P,
+  ) -> Result<Vec<Feature<T>>, error::ParserError>
+  where
+    T: CoordNum,
+    P: FnMut(&vector_tile::tile::Feature, GeomType, &PropertyView) -> bool,
+  {
+    let features = self.collect_features_with(layer_index, options, accept, decode_properties)?;
+    Ok(features.into_iter().map(Feature::from).collect())
+  }
+
+  /// Decodes the accepted features of a layer, decoding their properties with the given closure.
+  ///
+  /// The closure is only called for features with valid tags.
+  fn collect_features_with<T, C, P, D>(
+    &self,
+    layer_index: usize,
+    options: &FeatureOptions,
+    mut accept: P,
+    mut decode_properties: D,
+  ) -> Result<Vec<ParsedFeature<T, C>>, error::ParserError>
+  where
+    T: CoordNum,
+    P: FnMut(&vector_tile::tile::Feature, GeomType, &PropertyView) -> bool,
+    D: FnMut(
+      &vector_tile::tile::Layer,
+      &vector_tile::tile::Feature,
+    ) -> Result<C, error::ParserError>,
+  {
+    let layer = self.tile.layers.get(layer_index);
+    match layer {
+      Some(layer) => {
+        let mut features = Vec::with_capacity(layer.features.len());
+        for (feature_index, feature) in layer.features.iter().enumerate() {
+          if let Some(feature) =
+            parse_feature_with(layer, feature, options, &mut accept, &mut decode_properties)
+              .map_err(|error| error.with_context(layer_index, Some(feature_index)))?
+          {
+            features.push(feature);
+          }
+        }
+        Ok(features)
+      }
+      None => Ok(vec![]),
+    }
+  }
+
+  /// Returns the shared key and value table of a layer, decoding it on first use.
+  fn layer_table(&self, layer_index: usize) -> Option<std::sync::Arc<feature::LayerTable>> {
+    let layer = self.tile.layers.get(layer_index)?;
+    let cache = &self.caches[layer_index];
+    let table = cache.table.get_or_init(|| {
+      std::sync::Arc::new(feature::LayerTable::new(
+        layer.keys.iter().map(|key| key.as_str().into()).collect(),
+        layer.values.iter().cloned().map(map_value).collect(),
+      ))
+    });
+    Some(table.clone())
+  }
+}
+
+/// A decoded feature with its properties in the container chosen by the caller.
+struct ParsedFeature<T: CoordNum, C> {
+  geometry: Geometry<T>,
+  id: Option<u64>,
+  properties: C,
+}
+
+impl<T: CoordNum> From<ParsedFeature<T, HashMap<String, Value>>> for Feature<T> {
+  fn from(feature: ParsedFeature<T, HashMap<String, Value>>) -> Self {
+    Feature {
+      geometry: feature.geometry,
+      id: feature.id,
+      properties: Some(feature.properties),
+    }
+  }
+}
+
+/// Decodes a feature if it has a geometry type and is accepted by the given closure.
+///
+/// Returns `None` for features that are rejected or have no geometry left after processing.
+fn parse_feature<T, P>(
+  layer: &vector_tile::tile::Layer,
+  feature: &vector_tile::tile::Feature,
+  options: &FeatureOptions,
+  accept: P,
+) -> Result<Option<Feature<T>>, error::ParserError>
+where
+  T: CoordNum,
+  P: FnMut(&vector_tile::tile::Feature, GeomType, &PropertyView) -> bool,
+{
+  let feature = parse_feature_with(layer, feature, options, accept, decode_properties)?;
+  Ok(feature.map(Feature::from))
+}
+
+/// Decodes a feature like [`parse_feature`], decoding its properties with the given closure.
+///
+/// The tags are validated before the closure is called, so it does not need to validate them again.
+fn parse_feature_with<T, C, P, D>(
+  layer: &vector_tile::tile::Layer,
+  feature: &vector_tile::tile::Feature,
+  options: &FeatureOptions,
+  mut accept: P,
+  decode_properties: D,
+) -> Result<Option<ParsedFeature<T, C>>, error::ParserError>
+where
+  T: CoordNum,
+  P: FnMut(&vector_tile::tile::Feature, GeomType, &PropertyView) -> bool,
+  D:
+    FnOnce(&vector_tile::tile::Layer, &vector_tile::tile::Feature) -> Result<C, error::ParserError>,
+{
+  let Some(geom_type) = feature.r#type else {
+    return Ok(None);
+  };
+  match GeomType::try_from(geom_type) {
+    Ok(geom_type) => {
+      if let Err(error) = validate_tags(&feature.tags, &layer.keys, &layer.values) {
+        // geometry errors take precedence over tags errors
+        decode_geometry::<T>(layer, feature, geom_type, options)?;
+        return Err(error);
+      }
+
+      let attributes = match parse_inlined_attributes(layer, feature) {
+        Ok(attributes) => attributes,
+        Err(error) => {
+          decode_geometry::<T>(layer, feature, geom_type, options)?;
+          return Err(error);
+        }
+      };
+      let properties = PropertyView::new(&feature.tags, &layer.keys, &layer.values, &attributes);
+      if !accept(feature, geom_type, &properties) {
+        return Ok(None);
       }
-      None => Ok(vec![]),
+
+      let parsed_geometry = if options.is_empty() {
+        decode_geometry::<T>(layer, feature, geom_type, options)?
+      } else {
+        let parsed_geometry = decode_geometry::<i32>(layer, feature, geom_type, options)?;
+        match process_geometry(parsed_geometry, layer.extent.unwrap_or(4096), options) {
+          Some(processed_geometry) => convert_geometry(processed_geometry),
+          None => return Ok(None),
+        }
+      };
+
+      let parsed_tags = decode_properties(layer, feature)?;
+
+      Ok(Some(ParsedFeature {
+        geometry: parsed_geometry,
+        id: feature.id,
+        properties: parsed_tags,
+      }))
+    }
+    Err(error) => Err(error::ParserError::new(error::DecodeError::new(Box::new(
+      error,
+    )))),
+  }
+}
+
+/// Decodes the geometry of a feature, flattening splines into linestrings.
+#[cfg_attr(not(feature = "mvt3"), allow(unused_variables))]
+fn decode_geometry<T: CoordNum>(
+  layer: &vector_tile::tile::Layer,
+  feature: &vector_tile::tile::Feature,
+  geom_type: GeomType,
+  options: &FeatureOptions,
+) -> Result<Geometry<T>, error::ParserError> {
+  #[cfg(feature = "mvt3")]
+  if geom_type == GeomType::Spline {
+    let tolerance = options
+      .spline_tolerance
+      .unwrap_or(spline::DEFAULT_TOLERANCE);
+    let (spline, domain) = parse_spline::<f64>(layer, feature)?;
+    let flattened = spline.flatten_in(domain, tolerance)?;
+    // integer coordinates are rounded to the nearest tile coordinate, float coordinates are kept as they are
+    let integral = T::from(0.5).is_none_or(|half| half == T::zero());
+    let convert = |value: f64| {
+      NumCast::from(if integral { value.round() } else { value }).unwrap_or_else(T::zero)
+    };
+    return Ok(Geometry::LineString(LineString::new(
+      flattened
+        .0
+        .iter()
+        .map(|coord| Coord {
+          x: convert(coord.x),
+          y: convert(coord.y),
+        })
+        .collect(),
+    )));
+  }
+  parse_geometry(&feature.geometry, geom_type)
+}
+
+/// Decodes and validates the control points, knots and degree of a spline feature, together with its domain.
+#[cfg(feature = "mvt3")]
+fn parse_spline<T: CoordNum>(
+  layer: &vector_tile::tile::Layer,
+  feature: &vector_tile::tile::Feature,
+) -> Result<(spline::Spline<T>, (f64, f64)), error::ParserError> {
+  let control_points = match parse_geometry::<T>(&feature.geometry, GeomType::Linestring)? {
+    Geometry::LineString(linestring) => linestring,
+    _ => return Err(error::ParserError::new(error::GeometryError::new())),
+  };
+  let spline = spline::Spline {
+    control_points,
+    knots: attributes::parse_knots(&feature.spline_knots, layer)?,
+    degree: feature.spline_degree(),
+  };
+  match spline.domain() {
+    Some(domain) => Ok((spline, domain)),
+    None => Err(error::ParserError::new(error::GeometryError::new())),
+  }
+}
+
+/// Decodes a feature with the elevation and geometric attributes of each vertex.
+#[cfg(feature = "mvt3")]
+fn parse_feature_3d<T: CoordNum>(
+  layer: &vector_tile::tile::Layer,
+  feature: &vector_tile::tile::Feature,
+) -> Result<Option<elevation::Feature3D<T>>, error::ParserError> {
+  let Some(feature_2d) =
+    parse_feature::<T, _>(layer, feature, &FeatureOptions::default(), |_, _, _| true)?
+  else {
+    return Ok(None);
+  };
+  let vertex_count = elevation::vertex_count(&feature_2d.geometry);
+
+  let mut cursor: i64 = 0;
+  let elevations: Vec<f64> = feature
+    .elevations
+    .iter()
+    .map(|delta| {
+      cursor += *delta as i64;
+      attributes::scale(layer.elevation_scaling.as_ref(), cursor)
+    })
+    .collect();
+  if !elevations.is_empty() && elevations.len() != vertex_count {
+    return Err(error::ParserError::new(error::GeometryError::new()));
+  }
+
+  let geometric_attributes =
+    attributes::parse_geometric_attributes(&feature.geometric_attributes, layer)?;
+  if geometric_attributes
+    .values()
+    .any(|values| values.len() != vertex_count)
+  {
+    return Err(error::ParserError::new(error::TagsError::new()));
+  }
+
+  match elevation::elevate(feature_2d.geometry, &elevations) {
+    Some(geometry) => Ok(Some(elevation::Feature3D {
+      geometry,
+      id: feature_2d.id,
+      properties: feature_2d.properties,
+      geometric_attributes,
+    })),
+    None => Err(error::ParserError::new(error::GeometryError::new())),
+  }
+}
+
+/// Applies the processing steps of the options to a geometry in tile coordinates.
+fn process_geometry(
+  geometry: Geometry<i32>,
+  extent: u32,
+  options: &FeatureOptions,
+) -> Option<Geometry<i32>> {
+  let mut geometry = geometry;
+  if let Some(buffer) = options.clip_buffer {
+    let buffer = buffer.min(i32::MAX as u32) as i32;
+    let extent = extent.min(i32::MAX as u32) as i32;
+    geometry = clip::clip_geometry(geometry, -buffer, extent.saturating_add(buffer))?;
+  }
+  if options.grid_size(extent).is_some() || options.simplification.is_some() {
+    geometry =
+      simplify::simplify_geometry(geometry, options.grid_size(extent), options.simplification)?;
+  }
+  Some(geometry)
+}
+
+/// Converts the coordinates of a geometry in tile coordinates to another numeric type.
+fn convert_geometry<T: CoordNum>(geometry: Geometry<i32>) -> Geometry<T> {
+  map_coords(&geometry, &|coord| Coord {
+    x: NumCast::from(coord.x).unwrap_or_else(T::zero),
+    y: NumCast::from(coord.y).unwrap_or_else(T::zero),
+  })
+}
+
+/// Maps every coordinate of a geometry with the given function.
+pub(crate) fn map_coords<A, B, F>(geometry: &Geometry<A>, map: &F) -> Geometry<B>
+where
+  A: CoordNum,
+  B: CoordNum,
+  F: Fn(Coord<A>) -> Coord<B>,
+{
+  let map_linestring = |linestring: &LineString<A>| {
+    LineString::new(linestring.0.iter().map(|coord| map(*coord)).collect())
+  };
+  let map_polygon = |polygon: &Polygon<A>| {
+    Polygon::new(
+      map_linestring(polygon.exterior()),
+      polygon.interiors().iter().map(map_linestring).collect(),
+    )
+  };
+  match geometry {
+    Geometry::Point(point) => Point(map(point.0)).into(),
+    Geometry::MultiPoint(points) => {
+      MultiPoint(points.iter().map(|point| Point(map(point.0))).collect()).into()
+    }
+    Geometry::LineString(linestring) => map_linestring(linestring).into(),
+    Geometry::MultiLineString(linestrings) => {
+      MultiLineString::new(linestrings.iter().map(map_linestring).collect()).into()
+    }
+    Geometry::Polygon(polygon) => map_polygon(polygon).into(),
+    Geometry::MultiPolygon(polygons) => {
+      MultiPolygon::new(polygons.iter().map(map_polygon).collect()).into()
+    }
+    Geometry::Line(line) => Line::new(map(line.start), map(line.end)).into(),
+    Geometry::Rect(rect) => Rect::new(map(rect.min()), map(rect.max())).into(),
+    Geometry::Triangle(triangle) => {
+      let [a, b, c] = triangle.to_array();
+      Triangle::new(map(a), map(b), map(c)).into()
+    }
+    Geometry::GeometryCollection(collection) => {
+      Geometry::GeometryCollection(GeometryCollection::new_from(
+        collection
+          .iter()
+          .map(|geometry| map_coords(geometry, map))
+          .collect(),
+      ))
     }
   }
 }
@@ -300,23 +1718,24 @@ where
   for (index, layer) in layers.iter().enumerate() {
     match layer.version {
       1 | 2 => results.push(processor(layer, index)),
+      #[cfg(feature = "mvt3")]
+      3 => results.push(processor(layer, index)),
       _ => {
-        return Err(error::ParserError::new(error::VersionError::new(
-          layer.name.clone(),
-          layer.version,
-        )));
+        return Err(
+          error::ParserError::new(error::VersionError::new(layer.name.clone(), layer.version))
+            .with_context(index, None),
+        );
       }
     }
   }
   Ok(results)
 }
 
-fn parse_tags(
+fn validate_tags(
   tags: &[u32],
   keys: &[String],
   values: &[vector_tile::tile::Value],
-) -> Result<std::collections::HashMap<String, Value>, error::ParserError> {
-  let mut result = std::collections::HashMap::new();
+) -> Result<(), error::ParserError> {
   for item in tags.chunks(2) {
     if item.len() != 2
       || item[0] >= keys.len().try_into().unwrap()
@@ -324,12 +1743,55 @@ fn parse_tags(
     {
       return Err(error::ParserError::new(error::TagsError::new()));
     }
-    result.insert(
-      keys[item[0] as usize].clone(),
-      map_value(values[item[1] as usize].clone()),
-    );
   }
-  Ok(result)
+  Ok(())
+}
+
+/// Validates and decodes the tags and, with the `mvt3` feature, the inlined attributes of a feature in encoding order.
+///
+/// Attributes follow the tags, so they take precedence over tags with the same key in maps.
+fn parse_properties<C>(
+  layer: &vector_tile::tile::Layer,
+  feature: &vector_tile::tile::Feature,
+) -> Result<C, error::ParserError>
+where
+  C: FromIterator<(String, Value)>,
+{
+  validate_tags(&feature.tags, &layer.keys, &layer.values)?;
+  decode_properties(layer, feature)
+}
+
+/// Decodes the properties of a feature like [`parse_properties`], for tags that were already validated.
+fn decode_properties<C>(
+  layer: &vector_tile::tile::Layer,
+  feature: &vector_tile::tile::Feature,
+) -> Result<C, error::ParserError>
+where
+  C: FromIterator<(String, Value)>,
+{
+  let tags = feature.tags.chunks(2).map(|item| {
+    (
+      layer.keys[item[0] as usize].clone(),
+      map_value(layer.values[item[1] as usize].clone()),
+    )
+  });
+  Ok(
+    tags
+      .chain(parse_inlined_attributes(layer, feature)?)
+      .collect(),
+  )
+}
+
+/// Decodes the inlined attributes of a feature, which only exist in the MVT 3 draft specification.
+#[cfg_attr(not(feature = "mvt3"), allow(unused_variables))]
+fn parse_inlined_attributes(
+  layer: &vector_tile::tile::Layer,
+  feature: &vector_tile::tile::Feature,
+) -> Result<Vec<(String, Value)>, error::ParserError> {
+  #[cfg(feature = "mvt3")]
+  return attributes::parse_attributes(&feature.attributes, layer);
+  #[cfg(not(feature = "mvt3"))]
+  Ok(Vec::new())
 }
 
 fn map_value(value: vector_tile::tile::Value) -> Value {
@@ -379,6 +1841,12 @@ fn parse_geometry<T: CoordNum>(
   if geom_type == GeomType::Unknown {
     return Err(error::ParserError::new(error::GeometryError::new()));
   }
+  // the control points of splines are encoded like a linestring
+  #[cfg(feature = "mvt3")]
+  let geom_type = match geom_type {
+    GeomType::Spline => GeomType::Linestring,
+    geom_type => geom_type,
+  };
 
   // worst case capacity to prevent reallocation. not needed to be exact.
   let mut coordinates: Vec<Coord<T>> = Vec::with_capacity(geometry_data.len());
@@ -492,7 +1960,7 @@ fn parse_geometry<T: CoordNum>(
         None => Err(error::ParserError::new(error::GeometryError::new())),
       }
     }
-    GeomType::Unknown => Err(error::ParserError::new(error::GeometryError::new())),
+    _ => Err(error::ParserError::new(error::GeometryError::new())),
   }
 }
 
@@ -501,7 +1969,7 @@ pub mod wasm {
 
   use crate::feature::Value;
   use geojson::{Feature, GeoJson, JsonObject, JsonValue, feature::Id};
-  use serde::ser::{Serialize, SerializeStruct};
+  use serde::ser::Serialize;
   use serde_wasm_bindgen::Serializer;
   use wasm_bindgen::prelude::*;
 
@@ -517,6 +1985,13 @@ pub mod wasm {
         Value::Float(f) => JsonValue::from(f),
         Value::Double(d) => JsonValue::from(d),
         Value::String(s) => JsonValue::from(s),
+        Value::List(values) => JsonValue::Array(values.into_iter().map(JsonValue::from).collect()),
+        Value::Map(map) => JsonValue::Object(
+          map
+            .into_iter()
+            .map(|(key, value)| (key, JsonValue::from(value)))
+            .collect(),
+        ),
       }
     }
   }
@@ -524,23 +1999,105 @@ pub mod wasm {
   /// Converts a `super::feature::Feature` into a `wasm_bindgen::JsValue`.
   impl From<super::feature::Feature> for wasm_bindgen::JsValue {
     fn from(feature: super::feature::Feature) -> Self {
+      IntegerMode::Number.feature(feature)
+    }
+  }
+
+  /// An enumeration of the representations of 64-bit integers in JavaScript.
+  #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+  enum IntegerMode {
+    /// Integers are converted to numbers, losing precision beyond `Number.MAX_SAFE_INTEGER`.
+    #[default]
+    Number,
+
+    /// Integers are converted to `BigInt`.
+    BigInt,
+
+    /// Integers are converted to decimal strings.
+    String,
+  }
+
+  impl IntegerMode {
+    fn parse(mode: &str) -> Option<Self> {
+      match mode {
+        "number" => Some(IntegerMode::Number),
+        "bigint" => Some(IntegerMode::BigInt),
+        "string" => Some(IntegerMode::String),
+        _ => None,
+      }
+    }
+
+    fn serializer(&self) -> Serializer {
+      Serializer::json_compatible()
+        .serialize_large_number_types_as_bigints(*self == IntegerMode::BigInt)
+    }
+
+    fn integer<T>(&self, integer: T) -> JsonValue
+    where
+      T: ToString + Into<JsonValue> + num_traits::ToPrimitive,
+    {
+      const MAX_SAFE_INTEGER: f64 = 9007199254740991.0;
+      match self {
+        IntegerMode::String => JsonValue::String(integer.to_string()),
+        IntegerMode::Number => match integer.to_f64() {
+          Some(number) if number.abs() > MAX_SAFE_INTEGER => JsonValue::from(number),
+          _ => integer.into(),
+        },
+        IntegerMode::BigInt => integer.into(),
+      }
+    }
+
+    fn value(&self, value: Value) -> JsonValue {
+      match value {
+        Value::Int(i) | Value::SInt(i) => self.integer(i),
+        Value::UInt(u) => self.integer(u),
+        Value::List(values) => {
+          JsonValue::Array(values.into_iter().map(|value| self.value(value)).collect())
+        }
+        Value::Map(map) => JsonValue::Object(
+          map
+            .into_iter()
+            .map(|(key, value)| (key, self.value(value)))
+            .collect(),
+        ),
+        value => value.into(),
+      }
+    }
+
+    fn id(&self, id: u64) -> Id {
+      match self.integer(id) {
+        JsonValue::String(id) => Id::String(id),
+        JsonValue::Number(id) => Id::Number(id),
+        _ => unreachable!(),
+      }
+    }
+
+    fn scalar(&self, id: u64) -> JsValue {
+      match self {
+        IntegerMode::Number => JsValue::from(id as f64),
+        IntegerMode::BigInt => JsValue::from(id),
+        IntegerMode::String => JsValue::from_str(&id.to_string()),
+      }
+    }
+
+    fn feature(&self, feature: super::feature::Feature) -> JsValue {
       let properties: Option<JsonObject> = feature.properties.as_ref().map(|props| {
         props
           .clone()
           .into_iter()
-          .map(|(k, v)| (k, v.into()))
+          .map(|(k, v)| (k, self.value(v)))
           .collect()
       });
 
       let geojson = GeoJson::Feature(Feature {
         bbox: None,
         geometry: Some(feature.get_geometry().into()),
-        id: feature.id.map(|id| Id::Number(id.into())),
+        id: feature.id.map(|id| self.id(id)),
         properties,
         foreign_members: None,
       });
 
-      geojson.serialize(&Serializer::json_compatible()).unwrap()
+      geojson.serialize(&self.serializer()).unwrap()
     }
   }
 
@@ -551,25 +2108,102 @@ pub mod wasm {
     }
   }
 
-  impl Serialize for super::layer::Layer {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-      S: serde::ser::Serializer,
-    {
-      let mut state = serializer.serialize_struct("Layer", 5)?;
-      state.serialize_field("layer_index", &self.layer_index)?;
-      state.serialize_field("version", &self.version)?;
-      state.serialize_field("name", &self.name)?;
-      state.serialize_field("feature_count", &self.feature_count)?;
-      state.serialize_field("extent", &self.extent)?;
-      state.end()
-    }
-  }
+  #[wasm_bindgen(typescript_custom_section)]
+  const TYPESCRIPT_TYPES: &str = r#"
+/** A property value of a feature. */
+export type PropertyValue =
+  | string
+  | number
+  | bigint
+  | boolean
+  | null
+  | PropertyValue[]
+  | { [key: string]: PropertyValue };
+
+/** A position as `[x, y]` in tile coordinates. */
+export type Position = [number, number];
+
+/** A GeoJSON geometry. */
+export type Geometry =
+  | { type: "Point"; coordinates: Position }
+  | { type: "MultiPoint"; coordinates: Position[] }
+  | { type: "LineString"; coordinates: Position[] }
+  | { type: "MultiLineString"; coordinates: Position[][] }
+  | { type: "Polygon"; coordinates: Position[][] }
+  | { type: "MultiPolygon"; coordinates: Position[][][] }
+  | { type: "GeometryCollection"; geometries: Geometry[] };
+
+/** A feature of a layer as GeoJSON feature. */
+export interface Feature {
+  type: "Feature";
+  geometry: Geometry;
+  id?: number | bigint | string;
+  properties?: Record<string, PropertyValue>;
+}
+
+/** The metadata of a layer. */
+export interface Layer {
+  layer_index: number;
+  version: number;
+  name: string;
+  feature_count: number;
+  extent: number;
+}
+
+/** The features of a layer as typed arrays, see `Reader.getFlatLayer`. */
+export interface FlatLayer {
+  coordinates: Int32Array | Float32Array;
+  ringOffsets: Uint32Array;
+  partOffsets: Uint32Array;
+  featureOffsets: Uint32Array;
+  geometryTypes: Uint8Array;
+  ids: Float64Array | (bigint | string | null)[];
+  properties: Record<string, PropertyValue[]>;
+}
+
+/** A flat layer of a decoded tile with its name and its index in the tile, see `decodeTile`. */
+export interface DecodedLayer extends FlatLayer {
+  name: string;
+  index: number;
+}
+
+/** The flat layers of a tile in tile order and their transferable buffers, see `decodeTile`. */
+export interface DecodedTile {
+  layers: DecodedLayer[];
+  transfer: ArrayBuffer[];
+}
+
+/** Triangles as vertex and index buffers, see `Reader.tessellateLayer` and `Reader.extrudeLayer`. */
+export interface Mesh {
+  vertices: Float32Array;
+  indices: Uint32Array;
+  topology: "triangles" | "triangle-strip";
+}
+
+/** The kind of a `ParserError`. */
+export type ErrorKind = "decode" | "version" | "tags" | "geometry" | "filter" | "overzoom" | "deserialize" | "other";
+
+/** The error thrown by the reader if no error callback is given. */
+export interface ParserError extends Error {
+  name: "ParserError";
+  kind: ErrorKind;
+  layerIndex?: number;
+  featureIndex?: number;
+}
+
+/** The representation of feature ids and 64-bit integer values, see `Reader.setIntegerMode`. */
+export type IntegerMode = "number" | "bigint" | "string";
+
+/** A callback receiving the description of an error. */
+export type ErrorCallback = (error: string) => void;
+"#;
 
   /// Reader for decoding and accessing vector tile data in WebAssembly.
   #[wasm_bindgen]
   pub struct Reader {
     reader: Option<super::Reader>,
+
+    integer_mode: IntegerMode,
   }
 
   #[wasm_bindgen]
@@ -579,35 +2213,73 @@ pub mod wasm {
     /// # Arguments
     ///
     /// * `data` - The vector tile data as a `Vec<u8>`.
-    /// * `error_callback` - An optional JavaScript callback function to handle errors. It should accept a single parameter which will contain the error message as a string.
+    /// * `error_callback` - An optional JavaScript callback function to handle errors. It should accept a single parameter which will contain the error message as a string. If it is omitted, errors are thrown as `Error` objects with a `kind` and the `layerIndex` and `featureIndex` where they occurred.
     ///
     /// # Examples
     ///
     /// ```
     /// let tileData = getVectorTileData();
-    /// let reader = new Reader(tileData, handleErrors);
+    /// try {
+    ///   let reader = new Reader(tileData);
+    /// } catch (error) {
+    ///   console.log(error.kind, error.message);
+    /// }
     /// ```
     #[wasm_bindgen(constructor)]
-    pub fn new(data: Vec<u8>, error_callback: Option<js_sys::Function>) -> Reader {
+    pub fn new(
+      data: Vec<u8>,
+      #[wasm_bindgen(unchecked_param_type = "ErrorCallback")] error_callback: Option<
+        js_sys::Function,
+      >,
+    ) -> Result<Reader, JsValue> {
       let reader = match super::Reader::new(data) {
         Ok(reader) => Some(reader),
         Err(error) => {
-          if let Some(callback) = error_callback {
-            callback
-              .call1(&JsValue::NULL, &JsValue::from_str(&format!("{:?}", error)))
-              .unwrap();
-          }
+          report_error(error, error_callback)?;
           None
         }
       };
-      Reader { reader }
+      Ok(Reader {
+        reader,
+        integer_mode: IntegerMode::default(),
+      })
+    }
+
+    /// Sets how feature ids and 64-bit integer property values are represented.
+    ///
+    /// JavaScript numbers represent integers exactly only up to `Number.MAX_SAFE_INTEGER`, so larger
+    /// ids and values lose precision in the default mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - `"number"` (default) for numbers, `"bigint"` for `BigInt` or `"string"` for decimal strings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// reader.setIntegerMode('bigint');
+    /// let features = reader.getFeatures(0);
+    /// console.log(typeof features[0].id); // "bigint"
+    /// ```
+    #[wasm_bindgen(js_name = setIntegerMode)]
+    pub fn set_integer_mode(
+      &mut self,
+      #[wasm_bindgen(unchecked_param_type = "IntegerMode")] mode: &str,
+    ) -> Result<(), JsValue> {
+      match IntegerMode::parse(mode) {
+        Some(mode) => {
+          self.integer_mode = mode;
+          Ok(())
+        }
+        None => Err(js_sys::TypeError::new(&format!("Unknown integer mode `{}`", mode)).into()),
+      }
     }
 
     /// Retrieves the layer names present in the vector tile.
     ///
     /// # Arguments
     ///
-    /// * `error_callback` - An optional JavaScript callback function to handle errors. It should accept a single parameter which will contain the error message as a string.
+    /// * `error_callback` - An optional JavaScript callback function to handle errors. It should accept a single parameter which will contain the error message as a string. If it is omitted, errors are thrown as `Error` objects with a `kind` and the `layerIndex` and `featureIndex` where they occurred.
     ///
     /// # Returns
     ///
@@ -621,8 +2293,13 @@ pub mod wasm {
     ///   console.log(layerNames[i]);
     /// }
     /// ```
-    #[wasm_bindgen(js_name = getLayerNames)]
-    pub fn get_layer_names(&self, error_callback: Option<js_sys::Function>) -> JsValue {
+    #[wasm_bindgen(js_name = getLayerNames, unchecked_return_type = "string[] | null")]
+    pub fn get_layer_names(
+      &self,
+      #[wasm_bindgen(unchecked_param_type = "ErrorCallback")] error_callback: Option<
+        js_sys::Function,
+      >,
+    ) -> Result<JsValue, JsValue> {
       self.handle_result(|reader| reader.get_layer_names(), error_callback)
     }
 
@@ -630,7 +2307,7 @@ pub mod wasm {
     ///
     /// # Arguments
     ///
-    /// * `error_callback` - An optional JavaScript callback function to handle errors. It should accept a single parameter which will contain the error message as a string.
+    /// * `error_callback` - An optional JavaScript callback function to handle errors. It should accept a single parameter which will contain the error message as a string. If it is omitted, errors are thrown as `Error` objects with a `kind` and the `layerIndex` and `featureIndex` where they occurred.
     ///
     /// # Returns
     ///
@@ -644,8 +2321,13 @@ pub mod wasm {
     ///   console.log(layers[i].name);
     /// }
     /// ```
-    #[wasm_bindgen(js_name = getLayerMetadata)]
-    pub fn get_layer_metadata(&self, error_callback: Option<js_sys::Function>) -> JsValue {
+    #[wasm_bindgen(js_name = getLayerMetadata, unchecked_return_type = "Layer[] | null")]
+    pub fn get_layer_metadata(
+      &self,
+      #[wasm_bindgen(unchecked_param_type = "ErrorCallback")] error_callback: Option<
+        js_sys::Function,
+      >,
+    ) -> Result<JsValue, JsValue> {
       self.handle_result(|reader| reader.get_layer_metadata(), error_callback)
     }
 
@@ -654,7 +2336,7 @@ pub mod wasm {
     /// # Arguments
     ///
     /// * `layer_index` - The index of the layer to retrieve features from.
-    /// * `error_callback` - An optional JavaScript callback function to handle errors. It should accept a single parameter which will contain the error message as a string.
+    /// * `error_callback` - An optional JavaScript callback function to handle errors. It should accept a single parameter which will contain the error message as a string. If it is omitted, errors are thrown as `Error` objects with a `kind` and the `layerIndex` and `featureIndex` where they occurred.
     ///
     /// # Returns
     ///
@@ -668,39 +2350,501 @@ pub mod wasm {
     ///   console.log(features[i]);
     /// }
     /// ```
-    #[wasm_bindgen(js_name = getFeatures)]
+    #[wasm_bindgen(js_name = getFeatures, unchecked_return_type = "Feature[] | null")]
     pub fn get_features(
       &self,
       layer_index: usize,
-      error_callback: Option<js_sys::Function>,
-    ) -> JsValue {
-      self.handle_result(|reader| reader.get_features(layer_index), error_callback)
+      #[wasm_bindgen(unchecked_param_type = "ErrorCallback")] error_callback: Option<
+        js_sys::Function,
+      >,
+    ) -> Result<JsValue, JsValue> {
+      let mode = self.integer_mode;
+      self.handle(
+        |reader| reader.get_features(layer_index),
+        |features| {
+          features
+            .into_iter()
+            .map(|feature| mode.feature(feature))
+            .collect::<js_sys::Array>()
+            .into()
+        },
+        error_callback,
+      )
+    }
+
+    /// Retrieves the features of a specific layer in the vector tile as typed arrays.
+    ///
+    /// # Arguments
+    ///
+    /// * `layer_index` - The index of the layer to retrieve features from.
+    /// * `float32` - An optional flag to return the coordinates as `Float32Array` instead of `Int32Array`.
+    /// * `error_callback` - An optional JavaScript callback function to handle errors. It should accept a single parameter which will contain the error message as a string. If it is omitted, errors are thrown as `Error` objects with a `kind` and the `layerIndex` and `featureIndex` where they occurred.
+    ///
+    /// # Returns
+    ///
+    /// A JavaScript object with the following fields:
+    ///
+    /// * `coordinates` - The vertex positions as `x, y` pairs in an `Int32Array` or `Float32Array`.
+    /// * `ringOffsets`, `partOffsets`, `featureOffsets` - The nested offsets of rings, parts and features as `Uint32Array`.
+    /// * `geometryTypes` - The geometry type of each feature as `Uint8Array`.
+    /// * `ids` - The id of each feature as `Float64Array`, `NaN` for features without id.
+    /// * `properties` - An object with an array of values for each property name, `null` for features without the property.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let layer = reader.getFlatLayer(0, false, handleErrors);
+    /// for (let i = 0; i < layer.geometryTypes.length; i++) {
+    ///   console.log(layer.ids[i], layer.properties.name[i]);
+    /// }
+    /// ```
+    #[wasm_bindgen(js_name = getFlatLayer, unchecked_return_type = "FlatLayer | null")]
+    pub fn get_flat_layer(
+      &self,
+      layer_index: usize,
+      float32: Option<bool>,
+      #[wasm_bindgen(unchecked_param_type = "ErrorCallback")] error_callback: Option<
+        js_sys::Function,
+      >,
+    ) -> Result<JsValue, JsValue> {
+      self.handle(
+        |reader| reader.get_flat_layer(layer_index),
+        |layer| flat_layer_to_js(layer, float32.unwrap_or(false), self.integer_mode),
+        error_callback,
+      )
+    }
+
+    /// Triangulates the polygons of a specific layer in the vector tile for rendering with WebGL.
+    ///
+    /// # Arguments
+    ///
+    /// * `layer_index` - The index of the layer to triangulate.
+    /// * `error_callback` - An optional JavaScript callback function to handle errors. It should accept a single parameter which will contain the error message as a string. If it is omitted, errors are thrown as `Error` objects with a `kind` and the `layerIndex` and `featureIndex` where they occurred.
+    ///
+    /// # Returns
+    ///
+    /// A JavaScript object with the vertex positions as `Float32Array` in `vertices`, the indices of a triangle list as `Uint32Array` in `indices` and `"triangles"` in `topology`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mesh = reader.tessellateLayer(0, handleErrors);
+    /// gl.bufferData(gl.ARRAY_BUFFER, mesh.vertices, gl.STATIC_DRAW);
+    /// gl.bufferData(gl.ELEMENT_ARRAY_BUFFER, mesh.indices, gl.STATIC_DRAW);
+    /// gl.drawElements(gl.TRIANGLES, mesh.indices.length, gl.UNSIGNED_INT, 0);
+    /// ```
+    #[cfg(feature = "tessellate")]
+    #[wasm_bindgen(js_name = tessellateLayer, unchecked_return_type = "Mesh | null")]
+    pub fn tessellate_layer(
+      &self,
+      layer_index: usize,
+      #[wasm_bindgen(unchecked_param_type = "ErrorCallback")] error_callback: Option<
+        js_sys::Function,
+      >,
+    ) -> Result<JsValue, JsValue> {
+      self.handle(
+        |reader| reader.tessellate_layer(layer_index),
+        mesh_to_js,
+        error_callback,
+      )
+    }
+
+    /// Extrudes the lines of a specific layer in the vector tile for rendering with WebGL.
+    ///
+    /// # Arguments
+    ///
+    /// * `layer_index` - The index of the layer to extrude.
+    /// * `line_width` - The width in tile coordinates to extrude lines with.
+    /// * `error_callback` - An optional JavaScript callback function to handle errors. It should accept a single parameter which will contain the error message as a string. If it is omitted, errors are thrown as `Error` objects with a `kind` and the `layerIndex` and `featureIndex` where they occurred.
+    ///
+    /// # Returns
+    ///
+    /// A JavaScript object with the vertex positions as `Float32Array` in `vertices`, the indices of a triangle strip as `Uint32Array` in `indices` and `"triangle-strip"` in `topology`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mesh = reader.extrudeLayer(0, 8, handleErrors);
+    /// gl.bufferData(gl.ARRAY_BUFFER, mesh.vertices, gl.STATIC_DRAW);
+    /// gl.bufferData(gl.ELEMENT_ARRAY_BUFFER, mesh.indices, gl.STATIC_DRAW);
+    /// gl.drawElements(gl.TRIANGLE_STRIP, mesh.indices.length, gl.UNSIGNED_INT, 0);
+    /// ```
+    #[cfg(feature = "tessellate")]
+    #[wasm_bindgen(js_name = extrudeLayer, unchecked_return_type = "Mesh | null")]
+    pub fn extrude_layer(
+      &self,
+      layer_index: usize,
+      line_width: f32,
+      #[wasm_bindgen(unchecked_param_type = "ErrorCallback")] error_callback: Option<
+        js_sys::Function,
+      >,
+    ) -> Result<JsValue, JsValue> {
+      let style = crate::tessellate::LineStyle {
+        width: line_width,
+        ..Default::default()
+      };
+      self.handle(
+        |reader| reader.extrude_layer(layer_index, &style),
+        mesh_to_js,
+        error_callback,
+      )
     }
 
-    fn handle_result<T, F>(&self, operation: F, error_callback: Option<js_sys::Function>) -> JsValue
+    fn handle_result<T, F>(
+      &self,
+      operation: F,
+      error_callback: Option<js_sys::Function>,
+    ) -> Result<JsValue, JsValue>
     where
       T: IntoIterator,
       T::Item: Into<JsValue>,
       F: FnOnce(&super::Reader) -> Result<T, super::error::ParserError>,
     {
-      match &self.reader {
-        Some(reader) => match operation(reader) {
-          Ok(result) => result
+      self.handle(
+        operation,
+        |result| {
+          result
             .into_iter()
             .map(Into::into)
             .collect::<js_sys::Array>()
-            .into(),
+            .into()
+        },
+        error_callback,
+      )
+    }
+
+    /// Runs an operation on the reader and converts its result.
+    ///
+    /// Errors are thrown as JavaScript errors unless an error callback is given, in which case the
+    /// callback is called and `null` is returned. A reader that failed to decode always returns `null`.
+    fn handle<T, F, C>(
+      &self,
+      operation: F,
+      convert: C,
+      error_callback: Option<js_sys::Function>,
+    ) -> Result<JsValue, JsValue>
+    where
+      F: FnOnce(&super::Reader) -> Result<T, super::error::ParserError>,
+      C: FnOnce(T) -> JsValue,
+    {
+      match &self.reader {
+        Some(reader) => match operation(reader) {
+          Ok(result) => Ok(convert(result)),
           Err(error) => {
-            if let Some(callback) = error_callback {
-              callback
-                .call1(&JsValue::NULL, &JsValue::from_str(&format!("{:?}", error)))
-                .unwrap();
-            }
-            JsValue::NULL
+            report_error(error, error_callback)?;
+            Ok(JsValue::NULL)
           }
         },
-        None => JsValue::NULL,
+        None => Ok(JsValue::NULL),
+      }
+    }
+  }
+
+  /// Decodes a vector tile into flat layers whose buffers can be transferred between threads.
+  ///
+  /// This is meant as entry point for Web Workers and Node worker threads: the tile can be transferred to
+  /// the worker as `ArrayBuffer` and is decoded directly from it, without copying it into WebAssembly
+  /// memory as a whole. The typed arrays of the result are listed in `transfer` so they can be posted
+  /// back without copying.
+  ///
+  /// # Arguments
+  ///
+  /// * `buffer` - The vector tile data.
+  /// * `layer_names` - The names of the layers to decode, all layers if omitted.
+  /// * `float32` - Whether to return the coordinates as `Float32Array` instead of `Int32Array`.
+  /// * `integer_mode` - The representation of feature ids and 64-bit integer values as described for
+  ///   `Reader.setIntegerMode`, `'number'` if omitted.
+  ///
+  /// # Returns
+  ///
+  /// An object with the flat layers in tile order in `layers` and their underlying `ArrayBuffer`s in
+  /// `transfer`. Every layer carries its `name` and its `index` in the tile, since layer names need not be
+  /// unique. Errors are thrown as described for `Reader`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// // worker.js
+  /// self.onmessage = ({ data }) => {
+  ///   const { layers, transfer } = decodeTile(data, ['roads'], false, 'bigint');
+  ///   self.postMessage(layers, transfer);
+  /// };
+  /// ```
+  #[wasm_bindgen(js_name = decodeTile, unchecked_return_type = "DecodedTile")]
+  pub fn decode_tile(
+    buffer: &js_sys::ArrayBuffer,
+    layer_names: Option<Vec<String>>,
+    float32: Option<bool>,
+    #[wasm_bindgen(unchecked_param_type = "IntegerMode")] integer_mode: Option<String>,
+  ) -> Result<JsValue, JsValue> {
+    let integer_mode = match integer_mode.as_deref().map(IntegerMode::parse) {
+      None => IntegerMode::default(),
+      Some(Some(mode)) => mode,
+      Some(None) => {
+        return Err(
+          js_sys::TypeError::new(&format!(
+            "Unknown integer mode `{}`",
+            integer_mode.unwrap_or_default()
+          ))
+          .into(),
+        );
+      }
+    };
+    let reader = match super::Reader::decode(ViewBuf::new(js_sys::Uint8Array::new(buffer))) {
+      Ok(reader) => reader,
+      Err(error) => return report_error(error, None).map(|_| JsValue::NULL),
+    };
+    let names = match reader.get_layer_names() {
+      Ok(names) => names,
+      Err(error) => return report_error(error, None).map(|_| JsValue::NULL),
+    };
+
+    let layers = js_sys::Array::new();
+    let transfer = js_sys::Array::new();
+    for (layer_index, name) in names.into_iter().enumerate() {
+      if layer_names
+        .as_ref()
+        .is_some_and(|layer_names| !layer_names.contains(&name))
+      {
+        continue;
+      }
+      let layer = match reader.get_flat_layer(layer_index) {
+        Ok(layer) => flat_layer_to_js(layer, float32.unwrap_or(false), integer_mode),
+        Err(error) => return report_error(error, None).map(|_| JsValue::NULL),
+      };
+      for field in [
+        "coordinates",
+        "ringOffsets",
+        "partOffsets",
+        "featureOffsets",
+        "geometryTypes",
+        "ids",
+      ] {
+        let array = js_sys::Reflect::get(&layer, &JsValue::from_str(field))?;
+        let array_buffer = js_sys::Reflect::get(&array, &JsValue::from_str("buffer"))?;
+        if array_buffer.is_instance_of::<js_sys::ArrayBuffer>() {
+          transfer.push(&array_buffer);
+        }
+      }
+      js_sys::Reflect::set(
+        &layer,
+        &JsValue::from_str("name"),
+        &JsValue::from_str(&name),
+      )?;
+      js_sys::Reflect::set(
+        &layer,
+        &JsValue::from_str("index"),
+        &JsValue::from(layer_index as u32),
+      )?;
+      layers.push(&layer);
+    }
+
+    let result = js_sys::Object::new();
+    js_sys::Reflect::set(&result, &JsValue::from_str("layers"), &layers)?;
+    js_sys::Reflect::set(&result, &JsValue::from_str("transfer"), &transfer)?;
+    Ok(result.into())
+  }
+
+  /// The number of bytes that are copied at once from a `Uint8Array` into WebAssembly memory.
+  const VIEW_WINDOW: u32 = 16 * 1024;
+
+  /// A buffer reading from a JavaScript `Uint8Array` through a small window in WebAssembly memory.
+  struct ViewBuf {
+    view: js_sys::Uint8Array,
+    length: u32,
+
+    /// The position of the window in the view.
+    start: u32,
+    window: Vec<u8>,
+
+    /// The read position in the window.
+    offset: usize,
+  }
+
+  impl ViewBuf {
+    fn new(view: js_sys::Uint8Array) -> Self {
+      let mut buf = Self {
+        length: view.length(),
+        view,
+        start: 0,
+        window: Vec::with_capacity(VIEW_WINDOW as usize),
+        offset: 0,
+      };
+      buf.fill(0);
+      buf
+    }
+
+    /// Moves the window to the given position in the view.
+    fn fill(&mut self, start: u32) {
+      let end = start.saturating_add(VIEW_WINDOW).min(self.length);
+      self.start = start;
+      self.offset = 0;
+      self.window.resize((end - start) as usize, 0);
+      self.view.subarray(start, end).copy_to(&mut self.window);
+    }
+  }
+
+  impl super::Buf for ViewBuf {
+    fn remaining(&self) -> usize {
+      (self.length - self.start) as usize - self.offset
+    }
+
+    fn chunk(&self) -> &[u8] {
+      &self.window[self.offset..]
+    }
+
+    fn advance(&mut self, count: usize) {
+      assert!(
+        count <= self.remaining(),
+        "cannot advance past the end of the view"
+      );
+      self.offset += count;
+      if self.offset >= self.window.len() {
+        self.fill(self.start + self.offset as u32);
       }
     }
   }
+
+  /// Passes an error to the error callback, or converts it into a JavaScript error if there is no callback.
+  ///
+  /// The JavaScript error carries the `kind` of the error (e.g. `"geometry"`) and, if known, the `layerIndex`
+  /// and `featureIndex` where it occurred.
+  fn report_error(
+    error: super::error::ParserError,
+    error_callback: Option<js_sys::Function>,
+  ) -> Result<(), JsValue> {
+    if let Some(callback) = error_callback {
+      // kept for backward compatibility
+      callback
+        .call1(&JsValue::NULL, &JsValue::from_str(&format!("{:?}", error)))
+        .unwrap();
+      return Ok(());
+    }
+    let js_error = js_sys::Error::new(&error.to_string());
+    js_error.set_name("ParserError");
+    let context = [
+      ("kind", JsValue::from_str(error.kind().as_str())),
+      (
+        "layerIndex",
+        error
+          .layer_index()
+          .map_or(JsValue::UNDEFINED, |index| JsValue::from(index as f64)),
+      ),
+      (
+        "featureIndex",
+        error
+          .feature_index()
+          .map_or(JsValue::UNDEFINED, |index| JsValue::from(index as f64)),
+      ),
+    ];
+    for (name, value) in context {
+      js_sys::Reflect::set(&js_error, &JsValue::from_str(name), &value).unwrap();
+    }
+    Err(js_error.into())
+  }
+
+  /// Converts a `FlatLayer` into a JavaScript object of typed arrays.
+  fn flat_layer_to_js(
+    layer: super::flat::FlatLayer,
+    float32: bool,
+    integer_mode: IntegerMode,
+  ) -> JsValue {
+    let coordinates: JsValue = if float32 {
+      let coordinates: Vec<f32> = layer
+        .coordinates
+        .iter()
+        .map(|value| *value as f32)
+        .collect();
+      js_sys::Float32Array::from(&coordinates[..]).into()
+    } else {
+      js_sys::Int32Array::from(&layer.coordinates[..]).into()
+    };
+    let ids: JsValue = match integer_mode {
+      IntegerMode::Number => {
+        let ids: Vec<f64> = layer
+          .ids
+          .iter()
+          .map(|id| id.map_or(f64::NAN, |id| id as f64))
+          .collect();
+        js_sys::Float64Array::from(&ids[..]).into()
+      }
+      _ => layer
+        .ids
+        .iter()
+        .map(|id| id.map_or(JsValue::NULL, |id| integer_mode.scalar(id)))
+        .collect::<js_sys::Array>()
+        .into(),
+    };
+
+    let properties = js_sys::Object::new();
+    for column in layer.properties {
+      let values: js_sys::Array = column
+        .values
+        .into_iter()
+        .map(|value| match value {
+          Some(value) => integer_mode
+            .value(value)
+            .serialize(&integer_mode.serializer())
+            .unwrap(),
+          None => JsValue::NULL,
+        })
+        .collect();
+      js_sys::Reflect::set(&properties, &JsValue::from_str(&column.name), &values).unwrap();
+    }
+
+    let result = js_sys::Object::new();
+    for (name, value) in [
+      ("coordinates", coordinates),
+      (
+        "ringOffsets",
+        js_sys::Uint32Array::from(&layer.ring_offsets[..]).into(),
+      ),
+      (
+        "partOffsets",
+        js_sys::Uint32Array::from(&layer.part_offsets[..]).into(),
+      ),
+      (
+        "featureOffsets",
+        js_sys::Uint32Array::from(&layer.feature_offsets[..]).into(),
+      ),
+      (
+        "geometryTypes",
+        js_sys::Uint8Array::from(&layer.geometry_types[..]).into(),
+      ),
+      ("ids", ids),
+      ("properties", properties.into()),
+    ] {
+      js_sys::Reflect::set(&result, &JsValue::from_str(name), &value).unwrap();
+    }
+    result.into()
+  }
+
+  /// Converts a `Mesh` into a JavaScript object of typed arrays.
+  #[cfg(feature = "tessellate")]
+  fn mesh_to_js(mesh: super::tessellate::Mesh) -> JsValue {
+    let result = js_sys::Object::new();
+    js_sys::Reflect::set(
+      &result,
+      &JsValue::from_str("vertices"),
+      &js_sys::Float32Array::from(&mesh.vertices[..]),
+    )
+    .unwrap();
+    js_sys::Reflect::set(
+      &result,
+      &JsValue::from_str("indices"),
+      &js_sys::Uint32Array::from(&mesh.indices[..]),
+    )
+    .unwrap();
+    let topology = match mesh.topology {
+      super::tessellate::Topology::Triangles => "triangles",
+      super::tessellate::Topology::TriangleStrip => "triangle-strip",
+    };
+    js_sys::Reflect::set(
+      &result,
+      &JsValue::from_str("topology"),
+      &JsValue::from_str(topology),
+    )
+    .unwrap();
+    result.into()
+  }
 }
@@ -0,0 +1,65 @@
+//! This module provides the `FeatureOptions` struct.
+//!
+//! The `FeatureOptions` struct controls optional processing steps that are applied to the geometries of features while they are decoded.
+//!
+//! # Types
+//!
+//! The `options` module defines the following types:
+//!
+//! - `FeatureOptions`: Represents the options used by [`Reader::get_features_with_options`](crate::Reader::get_features_with_options).
+//! - `Simplification`: Represents the algorithm and tolerance used to simplify lines and polygon rings.
+
+/// A structure representing options for decoding features.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeatureOptions {
+  /// Clips geometries to the tile extent, extended by the given buffer in tile coordinates on every side.
+  ///
+  /// `Some(0)` clips to `[0, extent]`, `Some(buffer)` clips to `[-buffer, extent + buffer]` and `None` disables clipping.
+  /// Features without any geometry left after clipping are dropped.
+  pub clip_buffer: Option<u32>,
+
+  /// Quantizes coordinates to the grid of the given display resolution in pixels, e.g. `256` for a 256px canvas.
+  ///
+  /// The coordinates stay in tile coordinates but are snapped to multiples of `extent / resolution`.
+  /// Repeated vertices are removed, lines collapsing to a single vertex and polygon rings collapsing to
+  /// zero area are dropped. `None` or `Some(0)` disable quantization.
+  pub resolution: Option<u32>,
+
+  /// Simplifies lines and polygon rings after quantization.
+  ///
+  /// Lines and rings without enough vertices left are dropped in the same way as for `resolution`.
+  pub simplification: Option<Simplification>,
+
+  /// The maximum deviation in tile coordinates when flattening spline geometries of the MVT 3 draft specification.
+  ///
+  /// `None` uses [`DEFAULT_TOLERANCE`](crate::spline::DEFAULT_TOLERANCE).
+  #[cfg(feature = "mvt3")]
+  pub spline_tolerance: Option<f64>,
+}
+
+/// An enumeration of the supported simplification algorithms.
+///
+/// The tolerances are given in tile coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Simplification {
+  /// Douglas–Peucker simplification, removing vertices closer than the given distance to the simplified line.
+  DouglasPeucker(f64),
+
+  /// Visvalingam–Whyatt simplification, removing vertices whose triangle with their neighbours is smaller than the given area.
+  Visvalingam(f64),
+}
+
+impl FeatureOptions {
+  /// Returns `true` if no processing step is enabled.
+  pub(crate) fn is_empty(&self) -> bool {
+    self.clip_buffer.is_none() && self.resolution.unwrap_or(0) == 0 && self.simplification.is_none()
+  }
+
+  /// Returns the size of a grid cell in tile coordinates for a layer with the given extent.
+  pub(crate) fn grid_size(&self, extent: u32) -> Option<f64> {
+    match self.resolution {
+      Some(resolution) if resolution > 0 => Some(extent as f64 / resolution as f64),
+      _ => None,
+    }
+  }
+}
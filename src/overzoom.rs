@@ -0,0 +1,149 @@
+//! This module provides the derivation of child tiles from a parent tile.
+//!
+//! A child tile at zoom level `z + dz` covers a `1 / 2^dz` part of its parent at zoom level `z`. The
+//! parent coordinates are scaled by `2^dz` and translated so that the child quadrant starts at the
+//! origin. Every feature is then clipped to the extent of the child tile plus a buffer and encoded
+//! again. The scaling is computed exactly in 128-bit integers and the scaled geometries are clipped in
+//! floating point, so only the clipped coordinates, which lie within the buffered extent, are narrowed to
+//! the 32-bit range of a tile. Splines are flattened after scaling, so the flattening tolerance applies to
+//! the child tile.
+
+use geo_types::{Coord, Geometry};
+
+use crate::error::{self, ParserError};
+use crate::stitch::TileCoordinates;
+use crate::vector_tile::tile::{Feature, GeomType, Layer};
+
+/// Computes the zoom difference and the position of the child within its parent.
+///
+/// Returns an error if the child is not a descendant of the parent.
+pub(crate) fn child_offset(
+  parent: TileCoordinates,
+  child: TileCoordinates,
+) -> Result<(u32, u64, u64), ParserError> {
+  let invalid = || {
+    ParserError::new(error::OverzoomError::new(format!(
+      "tile {}/{}/{} is not a descendant of tile {}/{}/{}",
+      child.z, child.x, child.y, parent.z, parent.x, parent.y
+    )))
+  };
+  if child.z < parent.z || child.z - parent.z >= 32 {
+    return Err(invalid());
+  }
+  let dz = (child.z - parent.z) as u32;
+  let (x, y) = (child.x as u64, child.y as u64);
+  if x >> dz != parent.x as u64 || y >> dz != parent.y as u64 {
+    return Err(invalid());
+  }
+  let mask = (1u64 << dz) - 1;
+  Ok((dz, x & mask, y & mask))
+}
+
+/// Derives the layer of a child tile from the layer of its parent.
+///
+/// Features without geometry left inside the buffered child extent are dropped, all other features keep
+/// their id, type and tags. The keys and values of the layer are kept as they are. Elevations and
+/// geometric attributes of the MVT 3 draft specification are dropped and splines become linestrings.
+pub(crate) fn overzoom_layer(
+  layer: &Layer,
+  layer_index: usize,
+  dz: u32,
+  (column, row): (u64, u64),
+  buffer: u32,
+) -> Result<Layer, ParserError> {
+  let extent = layer.extent.unwrap_or(4096);
+  let scale = 1i128 << dz;
+  let (offset_x, offset_y) = (
+    column as i128 * extent as i128,
+    row as i128 * extent as i128,
+  );
+  // exact for any zoom difference below 32, rounded to the nearest float only far outside of the child
+  let transform = |coord: Coord<i32>| Coord {
+    x: (coord.x as i128 * scale - offset_x) as f64,
+    y: (coord.y as i128 * scale - offset_y) as f64,
+  };
+  let buffer = buffer.min(i32::MAX as u32) as i32;
+  let max = (extent.min(i32::MAX as u32) as i32).saturating_add(buffer);
+
+  let mut features = Vec::with_capacity(layer.features.len());
+  for (feature_index, feature) in layer.features.iter().enumerate() {
+    let Some(geom_type) = feature
+      .r#type
+      .and_then(|geom_type| GeomType::try_from(geom_type).ok())
+    else {
+      continue;
+    };
+    let geometry = scaled_geometry(layer, feature, geom_type, &transform)
+      .map_err(|error| error.with_context(layer_index, Some(feature_index)))?;
+    let Some(geometry) = crate::clip::clip_geometry(geometry, -buffer, max) else {
+      continue;
+    };
+    let data = crate::encode::encode_geometry(&geometry);
+    if data.is_empty() {
+      continue;
+    }
+    let mut feature = feature.clone();
+    feature.geometry = data;
+    #[cfg(feature = "mvt3")]
+    {
+      // clipping changes the vertices, so per-vertex values no longer line up
+      feature.elevations.clear();
+      feature.geometric_attributes.clear();
+      if geom_type == GeomType::Spline {
+        // splines are flattened in the coordinates of the child tile
+        feature.r#type = Some(GeomType::Linestring as i32);
+        feature.spline_knots.clear();
+        feature.spline_degree = None;
+      }
+    }
+    features.push(feature);
+  }
+
+  Ok(Layer {
+    version: layer.version,
+    name: layer.name.clone(),
+    features,
+    keys: layer.keys.clone(),
+    values: layer.values.clone(),
+    extent: layer.extent,
+    #[cfg(feature = "mvt3")]
+    string_values: layer.string_values.clone(),
+    #[cfg(feature = "mvt3")]
+    float_values: layer.float_values.clone(),
+    #[cfg(feature = "mvt3")]
+    double_values: layer.double_values.clone(),
+    #[cfg(feature = "mvt3")]
+    int_values: layer.int_values.clone(),
+    #[cfg(feature = "mvt3")]
+    elevation_scaling: layer.elevation_scaling,
+    #[cfg(feature = "mvt3")]
+    attribute_scalings: layer.attribute_scalings.clone(),
+  })
+}
+
+/// Decodes the geometry of a feature into the coordinates of the child tile.
+#[cfg_attr(not(feature = "mvt3"), allow(unused_variables))]
+fn scaled_geometry<F>(
+  layer: &Layer,
+  feature: &Feature,
+  geom_type: GeomType,
+  transform: &F,
+) -> Result<Geometry<f64>, ParserError>
+where
+  F: Fn(Coord<i32>) -> Coord<f64>,
+{
+  #[cfg(feature = "mvt3")]
+  if geom_type == GeomType::Spline {
+    let (spline, domain) = crate::parse_spline::<i32>(layer, feature)?;
+    let spline = crate::spline::Spline {
+      control_points: spline.control_points.0.into_iter().map(transform).collect(),
+      knots: spline.knots,
+      degree: spline.degree,
+    };
+    return Ok(Geometry::LineString(
+      spline.flatten_in(domain, crate::spline::DEFAULT_TOLERANCE)?,
+    ));
+  }
+  let geometry: Geometry<i32> = crate::parse_geometry(&feature.geometry, geom_type)?;
+  Ok(crate::map_coords(&geometry, transform))
+}
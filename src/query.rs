@@ -0,0 +1,238 @@
+//! This module provides spatial queries against the features of a vector tile.
+//!
+//! All queries operate in tile coordinates, i.e. in the `0..extent` space of the layer.
+//!
+//! # Types
+//!
+//! The `query` module defines the following types:
+//!
+//! - `QueryResult`: Represents a feature matched by a query together with its position in the tile.
+
+use geo_types::{Coord, CoordNum, Geometry, LineString, Polygon, Rect, coord};
+use num_traits::NumCast;
+
+use crate::feature::Feature;
+
+/// A structure representing a feature matched by a spatial query.
+#[derive(Debug, Clone)]
+pub struct QueryResult {
+  /// The index of the layer containing the feature.
+  pub layer_index: usize,
+
+  /// The index of the feature within its layer.
+  pub feature_index: usize,
+
+  /// The distance between the query point and the feature in tile coordinates. Zero if the point lies inside a polygon.
+  pub distance: f64,
+
+  /// The matched feature.
+  pub feature: Feature,
+}
+
+fn to_f64<T: CoordNum>(coord: Coord<T>) -> (f64, f64) {
+  (
+    NumCast::from(coord.x).unwrap_or(0.0),
+    NumCast::from(coord.y).unwrap_or(0.0),
+  )
+}
+
+fn distance_to_point(point: (f64, f64), other: (f64, f64)) -> f64 {
+  (point.0 - other.0).hypot(point.1 - other.1)
+}
+
+fn distance_to_segment(point: (f64, f64), start: (f64, f64), end: (f64, f64)) -> f64 {
+  let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+  let length_squared = dx * dx + dy * dy;
+  if length_squared == 0.0 {
+    return distance_to_point(point, start);
+  }
+  let t = (((point.0 - start.0) * dx + (point.1 - start.1) * dy) / length_squared).clamp(0.0, 1.0);
+  distance_to_point(point, (start.0 + t * dx, start.1 + t * dy))
+}
+
+fn distance_to_linestring<T: CoordNum>(point: (f64, f64), linestring: &LineString<T>) -> f64 {
+  match linestring.0.len() {
+    0 => f64::INFINITY,
+    1 => distance_to_point(point, to_f64(linestring.0[0])),
+    _ => linestring
+      .lines()
+      .map(|line| distance_to_segment(point, to_f64(line.start), to_f64(line.end)))
+      .fold(f64::INFINITY, f64::min),
+  }
+}
+
+/// Even-odd ray casting test of a point against a single ring.
+fn ring_contains<T: CoordNum>(point: (f64, f64), ring: &LineString<T>) -> bool {
+  let mut inside = false;
+  for line in ring.lines() {
+    let (start, end) = (to_f64(line.start), to_f64(line.end));
+    if (start.1 > point.1) != (end.1 > point.1)
+      && point.0 < (end.0 - start.0) * (point.1 - start.1) / (end.1 - start.1) + start.0
+    {
+      inside = !inside;
+    }
+  }
+  inside
+}
+
+fn distance_to_polygon<T: CoordNum>(point: (f64, f64), polygon: &Polygon<T>) -> f64 {
+  if ring_contains(point, polygon.exterior())
+    && !polygon
+      .interiors()
+      .iter()
+      .any(|interior| ring_contains(point, interior))
+  {
+    return 0.0;
+  }
+  polygon
+    .interiors()
+    .iter()
+    .map(|interior| distance_to_linestring(point, interior))
+    .fold(distance_to_linestring(point, polygon.exterior()), f64::min)
+}
+
+/// Computes the distance between a point and a geometry, which is zero for points inside polygons.
+pub(crate) fn distance_to_geometry<T: CoordNum>(point: (f64, f64), geometry: &Geometry<T>) -> f64 {
+  match geometry {
+    Geometry::Point(p) => distance_to_point(point, to_f64(p.0)),
+    Geometry::MultiPoint(points) => points
+      .iter()
+      .map(|p| distance_to_point(point, to_f64(p.0)))
+      .fold(f64::INFINITY, f64::min),
+    Geometry::Line(line) => distance_to_segment(point, to_f64(line.start), to_f64(line.end)),
+    Geometry::LineString(linestring) => distance_to_linestring(point, linestring),
+    Geometry::MultiLineString(linestrings) => linestrings
+      .iter()
+      .map(|linestring| distance_to_linestring(point, linestring))
+      .fold(f64::INFINITY, f64::min),
+    Geometry::Polygon(polygon) => distance_to_polygon(point, polygon),
+    Geometry::MultiPolygon(polygons) => polygons
+      .iter()
+      .map(|polygon| distance_to_polygon(point, polygon))
+      .fold(f64::INFINITY, f64::min),
+    Geometry::Rect(rect) => distance_to_polygon(point, &rect.to_polygon()),
+    Geometry::Triangle(triangle) => distance_to_polygon(point, &triangle.to_polygon()),
+    Geometry::GeometryCollection(collection) => collection
+      .iter()
+      .map(|geometry| distance_to_geometry(point, geometry))
+      .fold(f64::INFINITY, f64::min),
+  }
+}
+
+/// Computes the envelope of an encoded geometry by walking its command stream without building the geometry.
+pub(crate) fn envelope(geometry_data: &[u32]) -> Option<Rect<i32>> {
+  let mut cursor: [i32; 2] = [0, 0];
+  let mut min: [i32; 2] = [i32::MAX, i32::MAX];
+  let mut max: [i32; 2] = [i32::MIN, i32::MIN];
+  let mut parameter_count: u32 = 0;
+
+  for value in geometry_data.iter() {
+    if parameter_count == 0 {
+      if matches!(value & 0x7, 1 | 2) {
+        parameter_count = (value >> 3) * crate::DIMENSION;
+      }
+    } else {
+      let integer_value = ((value >> 1) as i32) ^ -((value & 1) as i32);
+      let axis = if parameter_count.is_multiple_of(crate::DIMENSION) {
+        0
+      } else {
+        1
+      };
+      cursor[axis] = cursor[axis].saturating_add(integer_value);
+      if axis == 1 {
+        for axis in 0..2 {
+          min[axis] = min[axis].min(cursor[axis]);
+          max[axis] = max[axis].max(cursor[axis]);
+        }
+      }
+      parameter_count -= 1;
+    }
+  }
+
+  if min[0] > max[0] {
+    return None;
+  }
+  Some(Rect::new(
+    coord! { x: min[0], y: min[1] },
+    coord! { x: max[0], y: max[1] },
+  ))
+}
+
+fn rect_contains(rect: &Rect<f64>, point: (f64, f64)) -> bool {
+  point.0 >= rect.min().x
+    && point.0 <= rect.max().x
+    && point.1 >= rect.min().y
+    && point.1 <= rect.max().y
+}
+
+/// Liang–Barsky test whether a segment crosses a rectangle.
+fn segment_intersects_rect(rect: &Rect<f64>, start: (f64, f64), end: (f64, f64)) -> bool {
+  let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+  let mut t0: f64 = 0.0;
+  let mut t1: f64 = 1.0;
+  for (p, q) in [
+    (-dx, start.0 - rect.min().x),
+    (dx, rect.max().x - start.0),
+    (-dy, start.1 - rect.min().y),
+    (dy, rect.max().y - start.1),
+  ] {
+    if p == 0.0 {
+      if q < 0.0 {
+        return false;
+      }
+    } else {
+      let t = q / p;
+      if p < 0.0 {
+        t0 = t0.max(t);
+      } else {
+        t1 = t1.min(t);
+      }
+      if t0 > t1 {
+        return false;
+      }
+    }
+  }
+  true
+}
+
+fn linestring_intersects_rect<T: CoordNum>(rect: &Rect<f64>, linestring: &LineString<T>) -> bool {
+  match linestring.0.len() {
+    0 => false,
+    1 => rect_contains(rect, to_f64(linestring.0[0])),
+    _ => linestring
+      .lines()
+      .any(|line| segment_intersects_rect(rect, to_f64(line.start), to_f64(line.end))),
+  }
+}
+
+fn polygon_intersects_rect<T: CoordNum>(rect: &Rect<f64>, polygon: &Polygon<T>) -> bool {
+  linestring_intersects_rect(rect, polygon.exterior())
+    || polygon
+      .interiors()
+      .iter()
+      .any(|interior| linestring_intersects_rect(rect, interior))
+    // the rectangle lies completely inside the polygon
+    || distance_to_polygon((rect.min().x, rect.min().y), polygon) == 0.0
+}
+
+/// Tests whether a geometry intersects a rectangle.
+pub(crate) fn intersects_rect<T: CoordNum>(rect: &Rect<f64>, geometry: &Geometry<T>) -> bool {
+  match geometry {
+    Geometry::Point(p) => rect_contains(rect, to_f64(p.0)),
+    Geometry::MultiPoint(points) => points.iter().any(|p| rect_contains(rect, to_f64(p.0))),
+    Geometry::Line(line) => segment_intersects_rect(rect, to_f64(line.start), to_f64(line.end)),
+    Geometry::LineString(linestring) => linestring_intersects_rect(rect, linestring),
+    Geometry::MultiLineString(linestrings) => linestrings
+      .iter()
+      .any(|linestring| linestring_intersects_rect(rect, linestring)),
+    Geometry::Polygon(polygon) => polygon_intersects_rect(rect, polygon),
+    Geometry::MultiPolygon(polygons) => polygons
+      .iter()
+      .any(|polygon| polygon_intersects_rect(rect, polygon)),
+    Geometry::Rect(other) => polygon_intersects_rect(rect, &other.to_polygon()),
+    Geometry::Triangle(triangle) => polygon_intersects_rect(rect, &triangle.to_polygon()),
+    Geometry::GeometryCollection(collection) => collection
+      .iter()
+      .any(|geometry| intersects_rect(rect, geometry)),
+  }
+}
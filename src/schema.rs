@@ -0,0 +1,301 @@
+//! This module provides the schema of the properties found in the layers of vector tiles.
+//!
+//! A schema records for each key of a layer which `Value` variants were observed and how often, the
+//! range of its numeric values and a few distinct sample strings. Schemas of many tiles are aggregated
+//! with [`TileSchema::merge`], e.g. to build the `vector_layers` metadata of a tileset or attribute pickers.
+//!
+//! With the `serde` feature, a `TileSchema` serializes to the `vector_layers` shape of TileJSON, i.e. an
+//! array of objects with the layer name as `id` and a `fields` object mapping each key to its type.
+//!
+//! # Types
+//!
+//! The `schema` module defines the following types:
+//!
+//! - `TileSchema`: Represents the schemas of all layers, as returned by [`Reader::infer_schema`](crate::Reader::infer_schema).
+//! - `LayerSchema`: Represents the keys of a layer and the number of its features.
+//! - `FieldSchema`: Represents the values observed for a key.
+//! - `ValueKind`: Represents the variant of a `Value` without its content.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::feature::Value;
+
+/// The number of distinct sample strings kept per key by default.
+pub const DEFAULT_MAX_SAMPLES: usize = 10;
+
+/// An enumeration of the variants of `Value`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ValueKind {
+  String,
+  Float,
+  Double,
+  Int,
+  UInt,
+  SInt,
+  Bool,
+  Null,
+  List,
+  Map,
+}
+
+impl ValueKind {
+  /// Returns `true` for the numeric variants.
+  pub fn is_numeric(&self) -> bool {
+    matches!(
+      self,
+      ValueKind::Float | ValueKind::Double | ValueKind::Int | ValueKind::UInt | ValueKind::SInt
+    )
+  }
+}
+
+impl From<&Value> for ValueKind {
+  fn from(value: &Value) -> Self {
+    match value {
+      Value::String(_) => ValueKind::String,
+      Value::Float(_) => ValueKind::Float,
+      Value::Double(_) => ValueKind::Double,
+      Value::Int(_) => ValueKind::Int,
+      Value::UInt(_) => ValueKind::UInt,
+      Value::SInt(_) => ValueKind::SInt,
+      Value::Bool(_) => ValueKind::Bool,
+      Value::Null => ValueKind::Null,
+      Value::List(_) => ValueKind::List,
+      Value::Map(_) => ValueKind::Map,
+    }
+  }
+}
+
+/// A structure representing the values observed for a key of a layer.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FieldSchema {
+  /// The number of features with the key.
+  pub count: usize,
+
+  /// The number of values of each observed variant.
+  pub kinds: BTreeMap<ValueKind, usize>,
+
+  /// The smallest numeric value, if any value is numeric.
+  pub min: Option<f64>,
+
+  /// The largest numeric value, if any value is numeric.
+  pub max: Option<f64>,
+
+  /// Distinct string values in the order they were first observed, up to the maximum number of samples.
+  pub samples: Vec<String>,
+}
+
+impl FieldSchema {
+  /// Returns the number of explicit null values.
+  ///
+  /// Features without the key are not counted, see [`LayerSchema::missing_count`].
+  pub fn null_count(&self) -> usize {
+    self.kinds.get(&ValueKind::Null).copied().unwrap_or(0)
+  }
+
+  /// Returns the type of the key as used in the `fields` of TileJSON `vector_layers`.
+  ///
+  /// Null values are ignored. Keys with values of a single kind are `"String"`, `"Number"`, `"Boolean"`,
+  /// `"Array"` or `"Object"`, keys with values of several kinds are `"Mixed"` and keys with only null
+  /// values are `"Null"`.
+  pub fn field_type(&self) -> &'static str {
+    let mut types = self
+      .kinds
+      .keys()
+      .filter_map(|kind| match kind {
+        ValueKind::String => Some("String"),
+        ValueKind::Bool => Some("Boolean"),
+        ValueKind::List => Some("Array"),
+        ValueKind::Map => Some("Object"),
+        ValueKind::Null => None,
+        _ => Some("Number"),
+      })
+      .collect::<Vec<_>>();
+    // the numeric kinds are adjacent in the order of `ValueKind`
+    types.dedup();
+    match types[..] {
+      [] => "Null",
+      [field_type] => field_type,
+      _ => "Mixed",
+    }
+  }
+
+  fn observe(&mut self, value: &Value, max_samples: usize) {
+    self.count += 1;
+    *self.kinds.entry(ValueKind::from(value)).or_default() += 1;
+    if let Some(number) = value.as_f64() {
+      self.extend_range(number, number);
+    }
+    if let Value::String(s) = value {
+      self.add_sample(s, max_samples);
+    }
+  }
+
+  fn merge(&mut self, other: &FieldSchema, max_samples: usize) {
+    self.count += other.count;
+    for (kind, count) in &other.kinds {
+      *self.kinds.entry(*kind).or_default() += count;
+    }
+    if let (Some(min), Some(max)) = (other.min, other.max) {
+      self.extend_range(min, max);
+    }
+    for sample in &other.samples {
+      self.add_sample(sample, max_samples);
+    }
+  }
+
+  fn extend_range(&mut self, min: f64, max: f64) {
+    self.min = Some(self.min.map_or(min, |current| current.min(min)));
+    self.max = Some(self.max.map_or(max, |current| current.max(max)));
+  }
+
+  fn add_sample(&mut self, sample: &str, max_samples: usize) {
+    if self.samples.len() < max_samples && !self.samples.iter().any(|s| s == sample) {
+      self.samples.push(sample.to_string());
+    }
+  }
+}
+
+/// A structure representing the keys found in the features of a layer.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LayerSchema {
+  /// The name of the layer.
+  pub name: String,
+
+  /// The number of features in the layer.
+  pub feature_count: usize,
+
+  /// The schema of each key, sorted by key.
+  pub fields: BTreeMap<String, FieldSchema>,
+}
+
+impl LayerSchema {
+  /// Returns the number of features of the layer without the key.
+  ///
+  /// # Arguments
+  ///
+  /// * `key` - The key of the property.
+  pub fn missing_count(&self, key: &str) -> usize {
+    self.feature_count - self.fields.get(key).map_or(0, |field| field.count)
+  }
+
+  /// Returns the share of features of the layer without a value for the key, between `0` and `1`.
+  ///
+  /// Features without the key and features with an explicit null value are both counted as null.
+  ///
+  /// # Arguments
+  ///
+  /// * `key` - The key of the property.
+  pub fn null_ratio(&self, key: &str) -> f64 {
+    if self.feature_count == 0 {
+      return 0.0;
+    }
+    let null_count = self.fields.get(key).map_or(0, FieldSchema::null_count);
+    (self.missing_count(key) + null_count) as f64 / self.feature_count as f64
+  }
+
+  /// Records the properties of a feature.
+  pub(crate) fn observe(&mut self, properties: &HashMap<String, Value>, max_samples: usize) {
+    self.feature_count += 1;
+    for (key, value) in properties {
+      self
+        .fields
+        .entry(key.clone())
+        .or_default()
+        .observe(value, max_samples);
+    }
+  }
+
+  fn merge(&mut self, other: &LayerSchema, max_samples: usize) {
+    self.feature_count += other.feature_count;
+    for (key, field) in &other.fields {
+      self
+        .fields
+        .entry(key.clone())
+        .or_default()
+        .merge(field, max_samples);
+    }
+  }
+}
+
+/// A structure representing the schemas of the layers of one or more tiles.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileSchema {
+  /// The schema of each layer in the order the layers were first observed.
+  pub layers: Vec<LayerSchema>,
+
+  max_samples: usize,
+}
+
+impl Default for TileSchema {
+  fn default() -> Self {
+    Self::new(DEFAULT_MAX_SAMPLES)
+  }
+}
+
+impl TileSchema {
+  /// Creates an empty schema.
+  ///
+  /// # Arguments
+  ///
+  /// * `max_samples` - The maximum number of distinct sample strings kept per key.
+  pub fn new(max_samples: usize) -> Self {
+    Self {
+      layers: Vec::new(),
+      max_samples,
+    }
+  }
+
+  /// Returns the maximum number of distinct sample strings kept per key.
+  pub fn max_samples(&self) -> usize {
+    self.max_samples
+  }
+
+  /// Retrieves the schema of a layer by name.
+  pub fn layer(&self, name: &str) -> Option<&LayerSchema> {
+    self.layers.iter().find(|layer| layer.name == name)
+  }
+
+  /// Returns the schema of a layer by name, adding an empty one if the layer is not known yet.
+  pub(crate) fn layer_mut(&mut self, name: &str) -> &mut LayerSchema {
+    let index = match self.layers.iter().position(|layer| layer.name == name) {
+      Some(index) => index,
+      None => {
+        self.layers.push(LayerSchema {
+          name: name.to_string(),
+          ..LayerSchema::default()
+        });
+        self.layers.len() - 1
+      }
+    };
+    &mut self.layers[index]
+  }
+
+  /// Adds the layers of another schema, e.g. of another tile of the same tileset.
+  ///
+  /// Layers are matched by name. Counts are summed, numeric ranges are extended and sample strings are
+  /// added until the maximum number of samples of this schema is reached.
+  ///
+  /// # Arguments
+  ///
+  /// * `other` - The schema to add.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::{Reader, schema::TileSchema};
+  ///
+  /// let tiles: Vec<Vec<u8>> = vec![/* Vector tile data */];
+  ///
+  /// let mut schema = TileSchema::default();
+  /// for data in tiles {
+  ///   let reader = Reader::new(data).unwrap();
+  ///   schema.merge(&reader.infer_schema().unwrap());
+  /// }
+  /// ```
+  pub fn merge(&mut self, other: &TileSchema) {
+    let max_samples = self.max_samples;
+    for layer in &other.layers {
+      self.layer_mut(&layer.name).merge(layer, max_samples);
+    }
+  }
+}
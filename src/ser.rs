@@ -0,0 +1,106 @@
+//! This module implements serde `Serialize` for the types of the `feature` and `layer` modules.
+//!
+//! Values are serialized untagged, i.e. as the plain string, number, boolean, null, sequence or map they
+//! contain. Features are serialized as structs with their geometry, id and properties. Schemas are serialized
+//! in the `vector_layers` shape of TileJSON.
+
+use std::collections::BTreeMap;
+
+use geo_types::{CoordNum, Geometry};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+use crate::feature::{Feature, OrderedFeature, SharedFeature, SharedProperties, Value};
+use crate::layer::Layer;
+use crate::schema::{LayerSchema, TileSchema};
+
+impl Serialize for Value {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    match self {
+      Value::String(s) => serializer.serialize_str(s),
+      Value::Float(f) => serializer.serialize_f32(*f),
+      Value::Double(d) => serializer.serialize_f64(*d),
+      Value::Int(i) | Value::SInt(i) => serializer.serialize_i64(*i),
+      Value::UInt(u) => serializer.serialize_u64(*u),
+      Value::Bool(b) => serializer.serialize_bool(*b),
+      Value::Null => serializer.serialize_none(),
+      Value::List(values) => serializer.collect_seq(values),
+      Value::Map(map) => serializer.collect_map(map),
+    }
+  }
+}
+
+impl Serialize for SharedProperties {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    // the last value of a duplicate key wins, like in `get`
+    let properties: BTreeMap<&str, &Value> = self.iter().collect();
+    serializer.collect_map(properties)
+  }
+}
+
+impl<T: CoordNum + Serialize> Serialize for Feature<T> {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serialize_feature(serializer, &self.geometry, self.id, &self.properties)
+  }
+}
+
+impl<T: CoordNum + Serialize> Serialize for OrderedFeature<T> {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serialize_feature(serializer, &self.geometry, self.id, &self.properties)
+  }
+}
+
+impl<T: CoordNum + Serialize> Serialize for SharedFeature<T> {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serialize_feature(serializer, &self.geometry, self.id, &self.properties)
+  }
+}
+
+fn serialize_feature<S, T, P>(
+  serializer: S,
+  geometry: &Geometry<T>,
+  id: Option<u64>,
+  properties: &Option<P>,
+) -> Result<S::Ok, S::Error>
+where
+  S: Serializer,
+  T: CoordNum + Serialize,
+  P: Serialize,
+{
+  let mut state = serializer.serialize_struct("Feature", 3)?;
+  state.serialize_field("geometry", geometry)?;
+  state.serialize_field("id", &id)?;
+  state.serialize_field("properties", properties)?;
+  state.end()
+}
+
+impl Serialize for Layer {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut state = serializer.serialize_struct("Layer", 5)?;
+    state.serialize_field("layer_index", &self.layer_index)?;
+    state.serialize_field("version", &self.version)?;
+    state.serialize_field("name", &self.name)?;
+    state.serialize_field("feature_count", &self.feature_count)?;
+    state.serialize_field("extent", &self.extent)?;
+    state.end()
+  }
+}
+
+impl Serialize for TileSchema {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(&self.layers)
+  }
+}
+
+impl Serialize for LayerSchema {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    let fields: BTreeMap<&str, &str> = self
+      .fields
+      .iter()
+      .map(|(key, field)| (key.as_str(), field.field_type()))
+      .collect();
+    let mut state = serializer.serialize_struct("LayerSchema", 2)?;
+    state.serialize_field("id", &self.name)?;
+    state.serialize_field("fields", &fields)?;
+    state.end()
+  }
+}
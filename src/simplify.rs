@@ -0,0 +1,275 @@
+//! This module provides the reduction of decoded geometries for lower display resolutions.
+//!
+//! Coordinates are first snapped to a grid and repeated vertices are removed, then lines and polygon
+//! rings are simplified with the Douglas–Peucker or Visvalingam–Whyatt algorithm. Lines collapsing to a
+//! single vertex and rings collapsing to zero area are dropped. Rings that change their winding order
+//! while being reduced are dropped as well, since they would otherwise turn from exteriors into holes.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use geo_types::{Coord, Geometry, LineString, MultiLineString, MultiPoint, MultiPolygon, Polygon};
+
+use crate::options::Simplification;
+
+/// Quantizes and simplifies a geometry.
+///
+/// Returns `None` if nothing of the geometry is left.
+pub(crate) fn simplify_geometry(
+  geometry: Geometry<i32>,
+  grid_size: Option<f64>,
+  simplification: Option<Simplification>,
+) -> Option<Geometry<i32>> {
+  let reducer = Reducer {
+    grid_size,
+    simplification,
+  };
+  match geometry {
+    Geometry::Point(point) => Some(Geometry::Point(reducer.snap(point.0).into())),
+    Geometry::MultiPoint(points) => Some(
+      MultiPoint::new(
+        points
+          .into_iter()
+          .map(|point| reducer.snap(point.0).into())
+          .collect(),
+      )
+      .into(),
+    ),
+    Geometry::LineString(linestring) => reducer.linestring(&linestring).map(Geometry::LineString),
+    Geometry::MultiLineString(linestrings) => {
+      let linestrings: Vec<LineString<i32>> = linestrings
+        .iter()
+        .filter_map(|linestring| reducer.linestring(linestring))
+        .collect();
+      (!linestrings.is_empty()).then(|| MultiLineString::new(linestrings).into())
+    }
+    Geometry::Polygon(polygon) => reducer.polygon(&polygon).map(Geometry::Polygon),
+    Geometry::MultiPolygon(polygons) => {
+      let polygons: Vec<Polygon<i32>> = polygons
+        .iter()
+        .filter_map(|polygon| reducer.polygon(polygon))
+        .collect();
+      (!polygons.is_empty()).then(|| MultiPolygon::new(polygons).into())
+    }
+    geometry => Some(geometry),
+  }
+}
+
+struct Reducer {
+  grid_size: Option<f64>,
+  simplification: Option<Simplification>,
+}
+
+impl Reducer {
+  fn snap(&self, coord: Coord<i32>) -> Coord<i32> {
+    match self.grid_size {
+      Some(size) => Coord {
+        x: snap_value(coord.x, size),
+        y: snap_value(coord.y, size),
+      },
+      None => coord,
+    }
+  }
+
+  /// Snaps the coordinates to the grid and removes repeated vertices.
+  fn quantize(&self, coords: &[Coord<i32>]) -> Vec<Coord<i32>> {
+    let mut quantized: Vec<Coord<i32>> = Vec::with_capacity(coords.len());
+    for coord in coords {
+      let coord = self.snap(*coord);
+      if quantized.last() != Some(&coord) {
+        quantized.push(coord);
+      }
+    }
+    quantized
+  }
+
+  fn simplify(&self, coords: Vec<Coord<i32>>) -> Vec<Coord<i32>> {
+    match self.simplification {
+      Some(Simplification::DouglasPeucker(tolerance)) => douglas_peucker(&coords, tolerance),
+      Some(Simplification::Visvalingam(tolerance)) => visvalingam(&coords, tolerance),
+      None => coords,
+    }
+  }
+
+  fn linestring(&self, linestring: &LineString<i32>) -> Option<LineString<i32>> {
+    let coords = self.simplify(self.quantize(&linestring.0));
+    (coords.len() >= 2).then(|| LineString::new(coords))
+  }
+
+  fn ring(&self, ring: &LineString<i32>) -> Option<LineString<i32>> {
+    let area = signed_area(&ring.0);
+    let mut coords = self.quantize(&ring.0);
+    if coords.len() > 1 && coords.first() != coords.last() {
+      coords.push(coords[0]);
+    }
+    let coords = self.simplify(coords);
+    // a closed ring needs at least three distinct vertices
+    if coords.len() < 4 {
+      return None;
+    }
+    let reduced_area = signed_area(&coords);
+    (reduced_area != 0.0 && (reduced_area > 0.0) == (area > 0.0)).then(|| LineString::new(coords))
+  }
+
+  fn polygon(&self, polygon: &Polygon<i32>) -> Option<Polygon<i32>> {
+    let exterior = self.ring(polygon.exterior())?;
+    let interiors = polygon
+      .interiors()
+      .iter()
+      .filter_map(|interior| self.ring(interior))
+      .collect();
+    Some(Polygon::new(exterior, interiors))
+  }
+}
+
+fn snap_value(value: i32, size: f64) -> i32 {
+  let snapped = (value as f64 / size).round() * size;
+  snapped.round().clamp(i32::MIN as f64, i32::MAX as f64) as i32
+}
+
+/// Signed area with the same orientation convention as the decoder: exterior rings are positive.
+fn signed_area(coords: &[Coord<i32>]) -> f64 {
+  let Some(mut previous) = coords.last() else {
+    return 0.0;
+  };
+  let mut area = 0.0;
+  for current in coords {
+    area += (current.y as f64 - previous.y as f64) * (current.x as f64 + previous.x as f64);
+    previous = current;
+  }
+  area * 0.5
+}
+
+fn to_f64(coord: Coord<i32>) -> (f64, f64) {
+  (coord.x as f64, coord.y as f64)
+}
+
+/// Perpendicular distance of a point to a segment, falling back to the distance to its start for degenerate segments.
+fn segment_distance(point: Coord<i32>, start: Coord<i32>, end: Coord<i32>) -> f64 {
+  let (point, start, end) = (to_f64(point), to_f64(start), to_f64(end));
+  let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+  let length_squared = dx * dx + dy * dy;
+  if length_squared == 0.0 {
+    return (point.0 - start.0).hypot(point.1 - start.1);
+  }
+  let t = (((point.0 - start.0) * dx + (point.1 - start.1) * dy) / length_squared).clamp(0.0, 1.0);
+  (point.0 - start.0 - t * dx).hypot(point.1 - start.1 - t * dy)
+}
+
+/// Douglas–Peucker simplification keeping the first and the last vertex.
+fn douglas_peucker(coords: &[Coord<i32>], tolerance: f64) -> Vec<Coord<i32>> {
+  if coords.len() <= 2 {
+    return coords.to_vec();
+  }
+  let mut keep = vec![false; coords.len()];
+  keep[0] = true;
+  keep[coords.len() - 1] = true;
+
+  // iterative to avoid deep recursion on long lines
+  let mut stack = vec![(0, coords.len() - 1)];
+  while let Some((first, last)) = stack.pop() {
+    let mut max_distance = 0.0;
+    let mut max_index = first;
+    for index in first + 1..last {
+      let distance = segment_distance(coords[index], coords[first], coords[last]);
+      if distance > max_distance {
+        max_distance = distance;
+        max_index = index;
+      }
+    }
+    if max_index != first && max_distance > tolerance {
+      keep[max_index] = true;
+      stack.push((first, max_index));
+      stack.push((max_index, last));
+    }
+  }
+
+  coords
+    .iter()
+    .zip(keep)
+    .filter_map(|(coord, keep)| keep.then_some(*coord))
+    .collect()
+}
+
+fn triangle_area(a: Coord<i32>, b: Coord<i32>, c: Coord<i32>) -> f64 {
+  let (a, b, c) = (to_f64(a), to_f64(b), to_f64(c));
+  ((b.0 - a.0) * (c.1 - a.1) - (c.0 - a.0) * (b.1 - a.1)).abs() * 0.5
+}
+
+/// A vertex candidate for removal, ordered so that the smallest area is on top of the heap.
+#[derive(PartialEq)]
+struct Candidate {
+  area: f64,
+  index: usize,
+}
+
+impl Eq for Candidate {}
+
+impl Ord for Candidate {
+  fn cmp(&self, other: &Self) -> Ordering {
+    other
+      .area
+      .total_cmp(&self.area)
+      .then_with(|| other.index.cmp(&self.index))
+  }
+}
+
+impl PartialOrd for Candidate {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+/// Visvalingam–Whyatt simplification keeping the first and the last vertex.
+fn visvalingam(coords: &[Coord<i32>], tolerance: f64) -> Vec<Coord<i32>> {
+  let n = coords.len();
+  if n <= 2 {
+    return coords.to_vec();
+  }
+  let mut previous: Vec<usize> = (0..n).map(|index| index.wrapping_sub(1)).collect();
+  let mut next: Vec<usize> = (1..=n).collect();
+  let mut areas: Vec<f64> = vec![f64::INFINITY; n];
+  let mut heap = BinaryHeap::with_capacity(n);
+  for index in 1..n - 1 {
+    areas[index] = triangle_area(coords[index - 1], coords[index], coords[index + 1]);
+    heap.push(Candidate {
+      area: areas[index],
+      index,
+    });
+  }
+
+  let mut removed = vec![false; n];
+  while let Some(Candidate { area, index }) = heap.pop() {
+    if removed[index] || area != areas[index] {
+      // outdated entry
+      continue;
+    }
+    if area >= tolerance {
+      break;
+    }
+    removed[index] = true;
+    let (before, after) = (previous[index], next[index]);
+    next[before] = after;
+    previous[after] = before;
+    // the neighbours form new triangles without the removed vertex
+    for neighbour in [before, after] {
+      if neighbour != 0 && neighbour != n - 1 {
+        areas[neighbour] = triangle_area(
+          coords[previous[neighbour]],
+          coords[neighbour],
+          coords[next[neighbour]],
+        );
+        heap.push(Candidate {
+          area: areas[neighbour],
+          index: neighbour,
+        });
+      }
+    }
+  }
+
+  coords
+    .iter()
+    .zip(removed)
+    .filter_map(|(coord, removed)| (!removed).then_some(*coord))
+    .collect()
+}
@@ -0,0 +1,259 @@
+//! This module provides the `Spline` struct for the spline geometries of the MVT 3 draft specification.
+//!
+//! A spline feature encodes the control points of a B-spline like a single linestring and stores the knot
+//! vector and the degree of the curve next to the geometry. [`Reader::get_features`](crate::Reader::get_features)
+//! flattens splines into linestrings, [`Reader::get_splines`](crate::Reader::get_splines) returns the
+//! control points and knots as they are.
+//!
+//! # Types
+//!
+//! The `spline` module defines the following types:
+//!
+//! - `Spline`: Represents a B-spline with its control points, knots and degree.
+//! - `SplineFeature`: Represents a spline feature with an optional id and optional properties.
+
+use std::collections::HashMap;
+
+use geo_types::{Coord, CoordNum, LineString};
+use num_traits::NumCast;
+
+use crate::error;
+use crate::feature::Value;
+
+/// The default tolerance in tile coordinates used to flatten splines.
+pub const DEFAULT_TOLERANCE: f64 = 1.0;
+
+/// The maximum number of times a knot span is halved while flattening.
+const MAX_DEPTH: u32 = 16;
+
+/// The maximum number of vertices per knot span of a flattened spline, which bounds the size of the
+/// approximation by the size of the encoded spline.
+const MAX_VERTICES_PER_SPAN: usize = 1024;
+
+/// A structure representing a B-spline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spline<T: CoordNum = f32> {
+  /// The control points of the spline.
+  pub control_points: LineString<T>,
+
+  /// The knot vector, with `control_points.len() + degree + 1` non-decreasing values.
+  pub knots: Vec<f64>,
+
+  /// The degree of the spline, e.g. `2` for quadratic and `3` for cubic splines.
+  pub degree: u32,
+}
+
+/// A structure representing a spline feature in a vector tile.
+#[derive(Debug, Clone)]
+pub struct SplineFeature<T: CoordNum = f32> {
+  /// The spline of the feature.
+  pub spline: Spline<T>,
+
+  /// Optional identifier for the feature.
+  pub id: Option<u64>,
+
+  /// Optional properties associated with the feature.
+  pub properties: Option<HashMap<String, Value>>,
+}
+
+impl<T: CoordNum> Spline<T> {
+  /// Returns `true` if the number of knots matches the number of control points and the degree, and the
+  /// knots are non-decreasing.
+  pub fn is_valid(&self) -> bool {
+    let count = self.control_points.0.len();
+    let degree = self.degree as usize;
+    count > degree
+      && self.knots.len() == count + degree + 1
+      && self.knots.windows(2).all(|pair| pair[0] <= pair[1])
+      && self.knots[degree] < self.knots[count]
+  }
+
+  /// Returns the range of parameters for which the spline is defined, `None` if the spline is invalid.
+  pub fn domain(&self) -> Option<(f64, f64)> {
+    self.is_valid().then(|| {
+      (
+        self.knots[self.degree as usize],
+        self.knots[self.control_points.0.len()],
+      )
+    })
+  }
+
+  /// Evaluates the spline at the given parameter with de Boor's algorithm.
+  ///
+  /// # Arguments
+  ///
+  /// * `t` - The parameter, clamped to the domain of the spline.
+  ///
+  /// # Returns
+  ///
+  /// The point on the curve, or `None` if the spline is invalid.
+  pub fn evaluate(&self, t: f64) -> Option<Coord<f64>> {
+    let domain = self.domain()?;
+    Some(self.evaluate_in(domain, t))
+  }
+
+  /// Evaluates a valid spline with the given domain.
+  fn evaluate_in(&self, (start, end): (f64, f64), t: f64) -> Coord<f64> {
+    let t = t.clamp(start, end);
+    let degree = self.degree as usize;
+    let count = self.control_points.0.len();
+
+    // the knot span containing t, the last non-empty span for the end of the domain
+    let span = degree + self.knots[degree + 1..count].partition_point(|knot| *knot <= t);
+
+    let mut points: Vec<Coord<f64>> = (0..=degree)
+      .map(|j| {
+        let point = self.control_points.0[span - degree + j];
+        Coord {
+          x: NumCast::from(point.x).unwrap_or(0.0),
+          y: NumCast::from(point.y).unwrap_or(0.0),
+        }
+      })
+      .collect();
+    for r in 1..=degree {
+      for j in (r..=degree).rev() {
+        let i = span - degree + j;
+        let denominator = self.knots[i + degree + 1 - r] - self.knots[i];
+        let alpha = if denominator == 0.0 {
+          0.0
+        } else {
+          (t - self.knots[i]) / denominator
+        };
+        points[j] = Coord {
+          x: (1.0 - alpha) * points[j - 1].x + alpha * points[j].x,
+          y: (1.0 - alpha) * points[j - 1].y + alpha * points[j].y,
+        };
+      }
+    }
+    points[degree]
+  }
+
+  /// Approximates the spline by a linestring.
+  ///
+  /// Every knot span is subdivided until the curve deviates less than the tolerance from the linestring.
+  /// A span is halved at most until the parts of its control polygon are shorter than the tolerance, and
+  /// the approximation has at most 1024 vertices per knot span.
+  ///
+  /// # Arguments
+  ///
+  /// * `tolerance` - The maximum deviation in tile coordinates.
+  ///
+  /// # Returns
+  ///
+  /// A result containing the vertices of the approximation, or a `ParserError` if the spline is invalid
+  /// or its approximation needs too many vertices.
+  pub fn flatten(&self, tolerance: f64) -> Result<LineString<f64>, error::ParserError> {
+    match self.domain() {
+      Some(domain) => self.flatten_in(domain, tolerance),
+      None => Err(error::ParserError::new(error::GeometryError::new())),
+    }
+  }
+
+  /// Approximates a valid spline with the given domain by a linestring.
+  pub(crate) fn flatten_in(
+    &self,
+    domain: (f64, f64),
+    tolerance: f64,
+  ) -> Result<LineString<f64>, error::ParserError> {
+    let degree = self.degree as usize;
+    let count = self.control_points.0.len();
+    let mut budget = Budget {
+      remaining: (count - degree).saturating_mul(MAX_VERTICES_PER_SPAN),
+    };
+
+    let mut coords = Vec::new();
+    for span in degree..count {
+      let (t0, t1) = (self.knots[span], self.knots[span + 1]);
+      if t0 >= t1 {
+        continue;
+      }
+      let (p0, p1) = (self.evaluate_in(domain, t0), self.evaluate_in(domain, t1));
+      if coords.is_empty() {
+        coords.push(p0);
+      }
+      let max_depth = self.max_depth(span, tolerance);
+      self.subdivide(
+        domain,
+        (t0, p0),
+        (t1, p1),
+        (tolerance, max_depth, 0),
+        &mut budget,
+        &mut coords,
+      )?;
+    }
+    Ok(LineString::new(coords))
+  }
+
+  /// Returns how often a knot span may be halved, which is the number of halvings after which the parts of
+  /// its control polygon are shorter than the tolerance.
+  fn max_depth(&self, span: usize, tolerance: f64) -> u32 {
+    let points = &self.control_points.0[span - self.degree as usize..=span];
+    let length: f64 = points
+      .windows(2)
+      .map(|pair| {
+        let (dx, dy) = (
+          pair[1].x.to_f64().unwrap_or(0.0) - pair[0].x.to_f64().unwrap_or(0.0),
+          pair[1].y.to_f64().unwrap_or(0.0) - pair[0].y.to_f64().unwrap_or(0.0),
+        );
+        (dx * dx + dy * dy).sqrt()
+      })
+      .sum();
+    let ratio = length / tolerance;
+    if ratio.is_nan() || ratio <= 1.0 {
+      0
+    } else {
+      (ratio.log2().ceil() as u32).min(MAX_DEPTH)
+    }
+  }
+
+  fn subdivide(
+    &self,
+    domain: (f64, f64),
+    (t0, p0): (f64, Coord<f64>),
+    (t1, p1): (f64, Coord<f64>),
+    (tolerance, max_depth, depth): (f64, u32, u32),
+    budget: &mut Budget,
+    coords: &mut Vec<Coord<f64>>,
+  ) -> Result<(), error::ParserError> {
+    let t = (t0 + t1) / 2.0;
+    let p = self.evaluate_in(domain, t);
+    // always split twice so that symmetric curves within a span are not mistaken for straight lines
+    if depth >= max_depth || (depth >= 2 && distance_to_segment(p, p0, p1) <= tolerance) {
+      budget.take()?;
+      coords.push(p1);
+      return Ok(());
+    }
+    let limits = (tolerance, max_depth, depth + 1);
+    self.subdivide(domain, (t0, p0), (t, p), limits, budget, coords)?;
+    self.subdivide(domain, (t, p), (t1, p1), limits, budget, coords)
+  }
+}
+
+/// The number of vertices a flattened spline may still generate.
+struct Budget {
+  remaining: usize,
+}
+
+impl Budget {
+  fn take(&mut self) -> Result<(), error::ParserError> {
+    match self.remaining.checked_sub(1) {
+      Some(remaining) => {
+        self.remaining = remaining;
+        Ok(())
+      }
+      None => Err(error::ParserError::new(error::GeometryError::new())),
+    }
+  }
+}
+
+fn distance_to_segment(point: Coord<f64>, start: Coord<f64>, end: Coord<f64>) -> f64 {
+  let (dx, dy) = (end.x - start.x, end.y - start.y);
+  let length = dx * dx + dy * dy;
+  let t = if length == 0.0 {
+    0.0
+  } else {
+    (((point.x - start.x) * dx + (point.y - start.y) * dy) / length).clamp(0.0, 1.0)
+  };
+  let (x, y) = (start.x + t * dx - point.x, start.y + t * dy - point.y);
+  (x * x + y * y).sqrt()
+}
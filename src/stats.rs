@@ -0,0 +1,430 @@
+//! This module provides statistics about the encoded size and contents of the layers of a vector tile.
+//!
+//! Sizes are measured on the encoded tile, so they match the bytes of the tile whether its repeated fields
+//! are packed or not. Fields unknown to the specification are counted among the other bytes of a layer.
+//!
+//! # Types
+//!
+//! The `stats` module defines the following types:
+//!
+//! - `TileStats`: Represents the statistics of all layers, as returned by [`Reader::stats`](crate::Reader::stats).
+//! - `LayerStats`: Represents the size breakdown and contents of a layer.
+//! - `GeometryTypeCounts`: Represents the number of features of each geometry type.
+
+use std::collections::HashMap;
+
+use prost::Message;
+
+use crate::feature::Value;
+#[cfg(feature = "mvt3")]
+use crate::vector_tile::tile::Feature;
+use crate::vector_tile::{
+  Tile,
+  tile::{GeomType, Layer},
+};
+
+/// A structure representing the number of features of each geometry type in a layer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GeometryTypeCounts {
+  /// Features without a geometry type or with an unknown one.
+  pub unknown: usize,
+
+  /// Point and multi point features.
+  pub point: usize,
+
+  /// Linestring and multi linestring features.
+  pub linestring: usize,
+
+  /// Polygon and multi polygon features.
+  pub polygon: usize,
+
+  /// Spline features of the MVT 3 draft specification.
+  #[cfg(feature = "mvt3")]
+  pub spline: usize,
+}
+
+/// A structure representing the size breakdown and contents of a layer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerStats {
+  /// The name of the layer.
+  pub name: String,
+
+  /// The version of the layer.
+  pub version: u32,
+
+  /// The extent of the layer.
+  pub extent: u32,
+
+  /// The number of bytes of the layer in the tile, including its field header.
+  pub encoded_size: usize,
+
+  /// The number of bytes of the geometry commands of all features.
+  ///
+  /// With the `mvt3` feature this includes elevations, geometric attributes and spline knots.
+  pub geometry_size: usize,
+
+  /// The number of bytes of the tags of all features.
+  ///
+  /// With the `mvt3` feature this includes inlined attributes.
+  pub tags_size: usize,
+
+  /// The number of bytes of the key table.
+  pub keys_size: usize,
+
+  /// The number of bytes of the value table.
+  ///
+  /// With the `mvt3` feature this includes the string, float, double and integer value tables.
+  pub values_size: usize,
+
+  /// The number of features in the layer.
+  pub feature_count: usize,
+
+  /// The number of features of each geometry type.
+  pub geometry_types: GeometryTypeCounts,
+
+  /// The number of vertices of all features, i.e. the number of `MoveTo` and `LineTo` parameter pairs.
+  pub vertex_count: usize,
+
+  /// The number of command integers of all features.
+  pub command_count: usize,
+
+  /// Keys of the key table that no feature references.
+  pub unused_keys: Vec<String>,
+
+  /// Values of the value table that no feature references.
+  pub unused_values: Vec<Value>,
+
+  /// Keys that occur more than once in the key table, each listed once.
+  pub duplicate_keys: Vec<String>,
+
+  /// Values that occur more than once in the value table, each listed once.
+  pub duplicate_values: Vec<Value>,
+}
+
+impl LayerStats {
+  /// Returns the number of bytes not attributed to geometries, tags, keys or values, e.g. the name, ids and
+  /// geometry types.
+  pub fn other_size(&self) -> usize {
+    self.encoded_size - self.geometry_size - self.tags_size - self.keys_size - self.values_size
+  }
+
+  /// Returns the average number of command integers per feature.
+  pub fn average_commands_per_feature(&self) -> f64 {
+    if self.feature_count == 0 {
+      0.0
+    } else {
+      self.command_count as f64 / self.feature_count as f64
+    }
+  }
+}
+
+/// A structure representing the statistics of the layers of a vector tile.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileStats {
+  /// The number of bytes of the tile.
+  pub encoded_size: usize,
+
+  /// The statistics of each layer in tile order.
+  pub layers: Vec<LayerStats>,
+}
+
+/// Computes the statistics of a decoded tile from its encoded data.
+pub(crate) fn tile_stats(tile: &Tile, data: &[u8]) -> TileStats {
+  // every layer is a separate occurrence of the repeated field, in tile order
+  let fields = WireFields::new(data).filter(|field| field.tag == 3);
+  TileStats {
+    encoded_size: data.len(),
+    layers: tile
+      .layers
+      .iter()
+      .zip(fields)
+      .map(|(layer, field)| layer_stats(layer, layer_sizes(&field)))
+      .collect(),
+  }
+}
+
+/// The number of bytes of the parts of an encoded layer.
+#[derive(Default)]
+struct LayerSizes {
+  encoded: usize,
+  geometry: usize,
+  tags: usize,
+  keys: usize,
+  values: usize,
+}
+
+/// Attributes the bytes of an encoded layer to its parts.
+///
+/// Every occurrence of a field is counted with its key and length, so unpacked repeated fields and
+/// non-minimal varints are measured as encoded. Unknown fields are left to the other bytes.
+fn layer_sizes(layer: &WireField) -> LayerSizes {
+  let mut sizes = LayerSizes {
+    encoded: layer.size,
+    ..LayerSizes::default()
+  };
+  for field in WireFields::new(layer.payload) {
+    match field.tag {
+      2 => {
+        for field in WireFields::new(field.payload) {
+          match field.tag {
+            4 => sizes.geometry += field.size,
+            #[cfg(feature = "mvt3")]
+            6..=8 => sizes.geometry += field.size,
+            2 => sizes.tags += field.size,
+            #[cfg(feature = "mvt3")]
+            5 => sizes.tags += field.size,
+            _ => (),
+          }
+        }
+      }
+      3 => sizes.keys += field.size,
+      4 => sizes.values += field.size,
+      #[cfg(feature = "mvt3")]
+      9..=12 => sizes.values += field.size,
+      _ => (),
+    }
+  }
+  sizes
+}
+
+fn layer_stats(layer: &Layer, sizes: LayerSizes) -> LayerStats {
+  let mut geometry_types = GeometryTypeCounts::default();
+  let mut vertex_count = 0;
+  let mut command_count = 0;
+  let mut used_keys = vec![false; layer.keys.len()];
+  let mut used_values = vec![false; layer.values.len()];
+
+  for feature in &layer.features {
+    match feature.r#type.map(GeomType::try_from) {
+      Some(Ok(GeomType::Point)) => geometry_types.point += 1,
+      Some(Ok(GeomType::Linestring)) => geometry_types.linestring += 1,
+      Some(Ok(GeomType::Polygon)) => geometry_types.polygon += 1,
+      #[cfg(feature = "mvt3")]
+      Some(Ok(GeomType::Spline)) => geometry_types.spline += 1,
+      _ => geometry_types.unknown += 1,
+    }
+
+    let (vertices, commands) = count_commands(&feature.geometry);
+    vertex_count += vertices;
+    command_count += commands;
+
+    for pair in feature.tags.chunks_exact(2) {
+      if let Some(used) = used_keys.get_mut(pair[0] as usize) {
+        *used = true;
+      }
+      if let Some(used) = used_values.get_mut(pair[1] as usize) {
+        *used = true;
+      }
+    }
+    #[cfg(feature = "mvt3")]
+    mark_attribute_keys(layer, feature, &mut used_keys);
+  }
+
+  LayerStats {
+    name: layer.name.clone(),
+    version: layer.version,
+    extent: layer.extent.unwrap_or(4096),
+    encoded_size: sizes.encoded,
+    geometry_size: sizes.geometry,
+    tags_size: sizes.tags,
+    keys_size: sizes.keys,
+    values_size: sizes.values,
+    feature_count: layer.features.len(),
+    geometry_types,
+    vertex_count,
+    command_count,
+    unused_keys: unused(&layer.keys, &used_keys).cloned().collect(),
+    unused_values: unused(&layer.values, &used_values)
+      .cloned()
+      .map(crate::map_value)
+      .collect(),
+    duplicate_keys: duplicates(&layer.keys, String::as_str).cloned().collect(),
+    duplicate_values: duplicates(&layer.values, |value| value.encode_to_vec())
+      .cloned()
+      .map(crate::map_value)
+      .collect(),
+  }
+}
+
+/// Returns the number of vertices and command integers of a geometry command stream.
+fn count_commands(geometry: &[u32]) -> (usize, usize) {
+  let mut vertices = 0;
+  let mut commands = 0;
+  let mut index = 0;
+  while index < geometry.len() {
+    let id = geometry[index] & 0x7;
+    let count = (geometry[index] >> 3) as usize;
+    commands += 1;
+    index += 1;
+    match id {
+      // MoveTo and LineTo are followed by a pair of parameters per vertex
+      1 | 2 => {
+        let available = (geometry.len() - index) / 2;
+        vertices += count.min(available);
+        index += 2 * count.min(available);
+      }
+      // ClosePath has no parameters
+      7 => (),
+      _ => break,
+    }
+  }
+  (vertices, commands)
+}
+
+/// Marks the keys referenced by the inlined and geometric attributes of a feature as used.
+#[cfg(feature = "mvt3")]
+fn mark_attribute_keys(layer: &Layer, feature: &Feature, used_keys: &mut [bool]) {
+  fn mark_key(key: &str, keys: &[String], used_keys: &mut [bool]) {
+    for (used, _) in used_keys.iter_mut().zip(keys).filter(|(_, k)| *k == key) {
+      *used = true;
+    }
+  }
+
+  fn mark_value(value: &Value, keys: &[String], used_keys: &mut [bool]) {
+    match value {
+      Value::Map(map) => {
+        for (key, value) in map {
+          mark_key(key, keys, used_keys);
+          mark_value(value, keys, used_keys);
+        }
+      }
+      Value::List(values) => {
+        for value in values {
+          mark_value(value, keys, used_keys);
+        }
+      }
+      _ => (),
+    }
+  }
+
+  // malformed attributes are reported when the features are decoded
+  if let Ok(attributes) = crate::attributes::parse_attributes(&feature.attributes, layer) {
+    for (key, value) in &attributes {
+      mark_key(key, &layer.keys, used_keys);
+      mark_value(value, &layer.keys, used_keys);
+    }
+  }
+  if let Ok(attributes) =
+    crate::attributes::parse_geometric_attributes(&feature.geometric_attributes, layer)
+  {
+    for key in attributes.keys() {
+      mark_key(key, &layer.keys, used_keys);
+    }
+  }
+}
+
+fn unused<'a, T>(table: &'a [T], used: &'a [bool]) -> impl Iterator<Item = &'a T> {
+  table
+    .iter()
+    .zip(used)
+    .filter(|(_, used)| !**used)
+    .map(|(entry, _)| entry)
+}
+
+/// Returns the entries occurring more than once in a table, each once in the order of their second occurrence.
+fn duplicates<'a, T, K, F>(table: &'a [T], key: F) -> impl Iterator<Item = &'a T>
+where
+  K: std::hash::Hash + Eq,
+  F: Fn(&'a T) -> K,
+{
+  let mut occurrences = HashMap::new();
+  table.iter().filter(move |entry| {
+    let count = occurrences.entry(key(entry)).or_insert(0);
+    *count += 1;
+    *count == 2
+  })
+}
+
+/// A field of an encoded protobuf message.
+struct WireField<'a> {
+  /// The field number.
+  tag: u64,
+
+  /// The payload of a length-delimited field, empty for the other wire types.
+  payload: &'a [u8],
+
+  /// The number of bytes of the field, including its key and length.
+  size: usize,
+}
+
+/// An iterator over the fields of an encoded protobuf message that stops at the first malformed field.
+struct WireFields<'a> {
+  data: &'a [u8],
+  position: usize,
+}
+
+impl<'a> WireFields<'a> {
+  fn new(data: &'a [u8]) -> Self {
+    Self { data, position: 0 }
+  }
+
+  fn read_field(&mut self) -> Option<WireField<'a>> {
+    let start = self.position;
+    let key = self.read_varint()?;
+    let payload = self.read_value(key)?;
+    Some(WireField {
+      tag: key >> 3,
+      payload,
+      size: self.position - start,
+    })
+  }
+
+  /// Reads the value of a field, returning the payload of length-delimited fields.
+  fn read_value(&mut self, key: u64) -> Option<&'a [u8]> {
+    match key & 0x7 {
+      0 => self.read_varint().map(|_| &[][..]),
+      1 => self.skip(8).map(|_| &[][..]),
+      2 => {
+        let length = usize::try_from(self.read_varint()?).ok()?;
+        let start = self.position;
+        self.skip(length)?;
+        Some(&self.data[start..self.position])
+      }
+      // deprecated groups end with a matching end group key
+      3 => loop {
+        let inner = self.read_varint()?;
+        if inner & 0x7 == 4 {
+          return (inner >> 3 == key >> 3).then_some(&[][..]);
+        }
+        self.read_value(inner)?;
+      },
+      5 => self.skip(4).map(|_| &[][..]),
+      _ => None,
+    }
+  }
+
+  fn read_varint(&mut self) -> Option<u64> {
+    let mut value = 0;
+    // every byte of a varint stores 7 bits, up to 10 bytes
+    for shift in (0..64).step_by(7) {
+      let byte = *self.data.get(self.position)?;
+      self.position += 1;
+      value |= u64::from(byte & 0x7f) << shift;
+      if byte & 0x80 == 0 {
+        return Some(value);
+      }
+    }
+    None
+  }
+
+  fn skip(&mut self, length: usize) -> Option<()> {
+    let end = self.position.checked_add(length)?;
+    if end > self.data.len() {
+      return None;
+    }
+    self.position = end;
+    Some(())
+  }
+}
+
+impl<'a> Iterator for WireFields<'a> {
+  type Item = WireField<'a>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let field = self.read_field();
+    if field.is_none() {
+      self.position = self.data.len();
+    }
+    field
+  }
+}
@@ -0,0 +1,537 @@
+//! This module provides merging of features that were cut at tile boundaries into seamless geometries.
+//!
+//! The features of a layer are read from several tiles, clipped to the extent of their tile to remove
+//! buffers and transformed into a common coordinate space. This space has the resolution of the
+//! highest zoom level and the largest extent among the tiles, so that the coordinate `(x, y)` of tile
+//! `z/tx/ty` with extent `e` maps to `((tx * e + x) * s, (ty * e + y) * s)` with `s = 2^(zmax - z) * emax / e`.
+//!
+//! Features that share the same merge key (their id or the value of a property) are reassembled:
+//!
+//! - Points are collected into a single `MultiPoint`.
+//! - Lines are joined end-to-end where an endpoint of one part matches an endpoint of another part.
+//! - Polygons are unioned by removing the edges that the pieces share along the tile boundaries.
+//!
+//! Features without a merge key are returned unchanged apart from the coordinate transformation.
+//! Coordinates are matched exactly, which holds for tiles of the same tileset since their coordinates
+//! are quantized on the same grid.
+//!
+//! # Types
+//!
+//! The `stitch` module defines the following types:
+//!
+//! - `TileCoordinates`: Represents the zoom level and position of a tile.
+//! - `MergeKey`: Represents how the pieces of a feature are identified across tiles.
+
+use std::collections::{HashMap, HashSet};
+
+use geo_types::{
+  Coord, Geometry, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon,
+};
+
+use crate::Reader;
+use crate::error::ParserError;
+use crate::feature::{Feature, Value, ValueKey};
+use crate::options::FeatureOptions;
+
+/// A structure representing the zoom level and position of a tile in a tile pyramid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TileCoordinates {
+  /// The zoom level of the tile.
+  pub z: u8,
+
+  /// The column of the tile.
+  pub x: u32,
+
+  /// The row of the tile.
+  pub y: u32,
+}
+
+impl TileCoordinates {
+  /// Creates a new `TileCoordinates` instance.
+  ///
+  /// # Arguments
+  ///
+  /// * `z` - The zoom level of the tile.
+  /// * `x` - The column of the tile.
+  /// * `y` - The row of the tile.
+  pub fn new(z: u8, x: u32, y: u32) -> Self {
+    Self { z, x, y }
+  }
+}
+
+/// An enumeration describing how the pieces of a feature are identified across tiles.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MergeKey {
+  /// Features with the same id are merged.
+  Id,
+
+  /// Features with the same value of the given property are merged.
+  Property(String),
+}
+
+type Point64 = (i64, i64);
+
+/// The key under which the pieces of a feature are grouped.
+#[derive(PartialEq, Eq, Hash)]
+enum GroupKey {
+  Id(u64),
+  Property(ValueKey),
+}
+
+/// A tile boundary in the common coordinate space.
+#[derive(Clone, Copy)]
+struct TileBox {
+  min: Point64,
+  max: Point64,
+}
+
+impl TileBox {
+  fn on_boundary(&self, point: Point64) -> bool {
+    let inside_x = point.0 >= self.min.0 && point.0 <= self.max.0;
+    let inside_y = point.1 >= self.min.1 && point.1 <= self.max.1;
+    (inside_y && (point.0 == self.min.0 || point.0 == self.max.0))
+      || (inside_x && (point.1 == self.min.1 || point.1 == self.max.1))
+  }
+}
+
+/// The pieces of a feature collected from several tiles.
+struct Group {
+  id: Option<u64>,
+  properties: Option<HashMap<String, Value>>,
+  points: Vec<Point64>,
+  lines: Vec<Vec<Point64>>,
+  rings: Vec<Vec<Point64>>,
+}
+
+/// Merges the features of a layer across several tiles.
+///
+/// A merged feature takes the id and the properties of its first piece in the order of `tiles`, the
+/// properties of the other pieces are ignored even if they differ. Holes that end up without an
+/// exterior containing them, e.g. because a piece of the exterior is missing, are kept as polygons of
+/// their own.
+///
+/// # Arguments
+///
+/// * `tiles` - The readers of the tiles together with their coordinates.
+/// * `layer_name` - The name of the layer to merge. Tiles without this layer are skipped.
+/// * `key` - How pieces of the same feature are identified.
+///
+/// # Returns
+///
+/// A result containing the merged features in the common coordinate space if successful, or a `ParserError` if there is an error parsing one of the tiles.
+///
+/// # Examples
+///
+/// ```
+/// use mvt_reader::{Reader, stitch::{MergeKey, TileCoordinates, stitch}};
+///
+/// let left = Reader::new(vec![/* Vector tile data */]).unwrap();
+/// let right = Reader::new(vec![/* Vector tile data */]).unwrap();
+///
+/// let features = stitch(
+///   &[(&left, TileCoordinates::new(14, 8800, 5373)), (&right, TileCoordinates::new(14, 8801, 5373))],
+///   "roads",
+///   &MergeKey::Id,
+/// );
+/// ```
+pub fn stitch(
+  tiles: &[(&Reader, TileCoordinates)],
+  layer_name: &str,
+  key: &MergeKey,
+) -> Result<Vec<Feature<f64>>, ParserError> {
+  let mut sources = Vec::new();
+  for (reader, coordinates) in tiles {
+    let layers = reader.get_layer_metadata()?;
+    if let Some(layer) = layers.iter().find(|layer| layer.name == layer_name) {
+      sources.push((
+        *reader,
+        *coordinates,
+        layer.layer_index,
+        layer.extent.max(1),
+      ));
+    }
+  }
+  let Some(max_zoom) = sources
+    .iter()
+    .map(|(_, coordinates, _, _)| coordinates.z)
+    .max()
+  else {
+    return Ok(vec![]);
+  };
+  let max_extent = sources
+    .iter()
+    .map(|(_, _, _, extent)| *extent)
+    .max()
+    .unwrap_or(4096);
+
+  let options = FeatureOptions {
+    clip_buffer: Some(0),
+    ..Default::default()
+  };
+  let mut boxes = Vec::with_capacity(sources.len());
+  let mut groups: Vec<Group> = Vec::new();
+  let mut group_indices: HashMap<(&'static str, GroupKey), usize> = HashMap::new();
+  let mut unkeyed: Vec<Feature<f64>> = Vec::new();
+
+  for (reader, coordinates, layer_index, extent) in sources {
+    let scale = 2f64.powi((max_zoom - coordinates.z) as i32) * max_extent as f64 / extent as f64;
+    let transform = |coord: Coord<i32>| -> Point64 {
+      (
+        ((coordinates.x as f64 * extent as f64 + coord.x as f64) * scale).round() as i64,
+        ((coordinates.y as f64 * extent as f64 + coord.y as f64) * scale).round() as i64,
+      )
+    };
+    boxes.push(TileBox {
+      min: transform(Coord { x: 0, y: 0 }),
+      max: transform(Coord {
+        x: extent as i32,
+        y: extent as i32,
+      }),
+    });
+
+    for feature in reader.get_features_with_options::<i32>(layer_index, &options)? {
+      let group_key = match key {
+        MergeKey::Id => feature.id.map(GroupKey::Id),
+        MergeKey::Property(name) => feature
+          .properties
+          .as_ref()
+          .and_then(|properties| properties.get(name))
+          .map(|value| GroupKey::Property(ValueKey::from(value))),
+      };
+      let Some(group_key) = group_key else {
+        unkeyed.push(Feature {
+          geometry: crate::map_coords(&feature.geometry, &|coord| to_coord(transform(coord))),
+          id: feature.id,
+          properties: feature.properties,
+        });
+        continue;
+      };
+      let group_key = (geometry_kind(&feature.geometry), group_key);
+      let index = *group_indices.entry(group_key).or_insert_with(|| {
+        groups.push(Group {
+          id: feature.id,
+          properties: feature.properties.clone(),
+          points: Vec::new(),
+          lines: Vec::new(),
+          rings: Vec::new(),
+        });
+        groups.len() - 1
+      });
+      collect_pieces(&mut groups[index], &feature.geometry, &transform);
+    }
+  }
+
+  let mut features = Vec::with_capacity(groups.len() + unkeyed.len());
+  for group in groups {
+    let geometry = if !group.points.is_empty() {
+      // points on a tile boundary are contained in both tiles
+      let mut seen = HashSet::new();
+      let mut points = group.points;
+      points.retain(|point| seen.insert(*point));
+      Some(
+        MultiPoint(
+          points
+            .into_iter()
+            .map(|p| Point::new(p.0 as f64, p.1 as f64))
+            .collect(),
+        )
+        .into(),
+      )
+    } else if !group.lines.is_empty() {
+      lines_to_geometry(join_lines(group.lines))
+    } else {
+      polygons_to_geometry(union_rings(group.rings, &boxes))
+    };
+    if let Some(geometry) = geometry {
+      features.push(Feature {
+        geometry,
+        id: group.id,
+        properties: group.properties,
+      });
+    }
+  }
+  features.extend(unkeyed);
+  Ok(features)
+}
+
+fn geometry_kind(geometry: &Geometry<i32>) -> &'static str {
+  match geometry {
+    Geometry::Point(_) | Geometry::MultiPoint(_) => "point",
+    Geometry::LineString(_) | Geometry::MultiLineString(_) | Geometry::Line(_) => "line",
+    _ => "polygon",
+  }
+}
+
+fn collect_pieces<F: Fn(Coord<i32>) -> Point64>(
+  group: &mut Group,
+  geometry: &Geometry<i32>,
+  transform: &F,
+) {
+  let open_ring = |ring: &LineString<i32>| -> Vec<Point64> {
+    let mut points: Vec<Point64> = ring.0.iter().map(|coord| transform(*coord)).collect();
+    if points.len() > 1 && points.first() == points.last() {
+      points.pop();
+    }
+    points
+  };
+  match geometry {
+    Geometry::Point(point) => group.points.push(transform(point.0)),
+    Geometry::MultiPoint(points) => group
+      .points
+      .extend(points.iter().map(|point| transform(point.0))),
+    Geometry::Line(line) => group
+      .lines
+      .push(vec![transform(line.start), transform(line.end)]),
+    Geometry::LineString(linestring) => group
+      .lines
+      .push(linestring.0.iter().map(|coord| transform(*coord)).collect()),
+    Geometry::MultiLineString(linestrings) => group.lines.extend(
+      linestrings
+        .iter()
+        .map(|linestring| linestring.0.iter().map(|coord| transform(*coord)).collect()),
+    ),
+    Geometry::Polygon(polygon) => {
+      group.rings.push(open_ring(polygon.exterior()));
+      group
+        .rings
+        .extend(polygon.interiors().iter().map(open_ring));
+    }
+    Geometry::MultiPolygon(polygons) => {
+      for polygon in polygons {
+        group.rings.push(open_ring(polygon.exterior()));
+        group
+          .rings
+          .extend(polygon.interiors().iter().map(open_ring));
+      }
+    }
+    _ => (),
+  }
+}
+
+fn to_coord(point: Point64) -> Coord<f64> {
+  Coord {
+    x: point.0 as f64,
+    y: point.1 as f64,
+  }
+}
+
+fn lines_to_geometry(lines: Vec<Vec<Point64>>) -> Option<Geometry<f64>> {
+  let mut linestrings: Vec<LineString<f64>> = lines
+    .into_iter()
+    .map(|line| LineString::new(line.into_iter().map(to_coord).collect()))
+    .collect();
+  match linestrings.len() {
+    0 => None,
+    1 => Some(linestrings.remove(0).into()),
+    _ => Some(MultiLineString::new(linestrings).into()),
+  }
+}
+
+/// Joins lines end-to-end, reversing parts where necessary.
+fn join_lines(mut lines: Vec<Vec<Point64>>) -> Vec<Vec<Point64>> {
+  lines.retain(|line| !line.is_empty());
+  // popping from the back keeps the direction of the first piece
+  lines.reverse();
+  let mut joined: Vec<Vec<Point64>> = Vec::new();
+  while let Some(mut line) = lines.pop() {
+    loop {
+      let start = line[0];
+      let end = line[line.len() - 1];
+      let Some(index) = lines.iter().position(|other| {
+        let (other_start, other_end) = (other[0], other[other.len() - 1]);
+        other_start == end || other_end == end || other_end == start || other_start == start
+      }) else {
+        break;
+      };
+      let mut other = lines.swap_remove(index);
+      let (other_start, other_end) = (other[0], other[other.len() - 1]);
+      if other_start == end {
+        line.extend_from_slice(&other[1..]);
+      } else if other_end == end {
+        other.reverse();
+        line.extend_from_slice(&other[1..]);
+      } else if other_end == start {
+        other.extend_from_slice(&line[1..]);
+        line = other;
+      } else {
+        other.reverse();
+        other.extend_from_slice(&line[1..]);
+        line = other;
+      }
+    }
+    joined.push(line);
+  }
+  joined
+}
+
+/// Signed area with the same orientation convention as the decoder: exterior rings are positive.
+fn signed_area(ring: &[Point64]) -> f64 {
+  let mut area = 0.0;
+  let mut previous = ring[ring.len() - 1];
+  for current in ring {
+    area += (current.1 - previous.1) as f64 * (current.0 + previous.0) as f64;
+    previous = *current;
+  }
+  area * 0.5
+}
+
+fn cross(a: Point64, b: Point64, c: Point64) -> i128 {
+  (b.0 - a.0) as i128 * (c.1 - a.1) as i128 - (b.1 - a.1) as i128 * (c.0 - a.0) as i128
+}
+
+fn ring_contains(ring: &[Point64], point: Point64) -> bool {
+  let mut inside = false;
+  let mut previous = ring[ring.len() - 1];
+  for current in ring {
+    let (x0, y0) = (previous.0 as f64, previous.1 as f64);
+    let (x1, y1) = (current.0 as f64, current.1 as f64);
+    if (y1 > point.1 as f64) != (y0 > point.1 as f64)
+      && (point.0 as f64) < (x0 - x1) * (point.1 as f64 - y1) / (y0 - y1) + x1
+    {
+      inside = !inside;
+    }
+    previous = *current;
+  }
+  inside
+}
+
+/// Unions polygon pieces by cancelling the edges they share along tile boundaries.
+fn union_rings(
+  rings: Vec<Vec<Point64>>,
+  boxes: &[TileBox],
+) -> Vec<(Vec<Point64>, Vec<Vec<Point64>>)> {
+  let on_seam = |point: Point64| boxes.iter().any(|tile_box| tile_box.on_boundary(point));
+
+  let seam_vertices: Vec<Point64> = {
+    let mut vertices: Vec<Point64> = rings
+      .iter()
+      .flatten()
+      .copied()
+      .filter(|point| on_seam(*point))
+      .collect();
+    vertices.sort_unstable();
+    vertices.dedup();
+    vertices
+  };
+
+  // split edges along the seams at the vertices of the neighbouring pieces
+  let mut edges: Vec<(Point64, Point64)> = Vec::new();
+  for ring in rings.iter().filter(|ring| ring.len() >= 3) {
+    for (index, start) in ring.iter().enumerate() {
+      let start = *start;
+      let end = ring[(index + 1) % ring.len()];
+      if start == end {
+        continue;
+      }
+      if !(on_seam(start) && on_seam(end)) {
+        edges.push((start, end));
+        continue;
+      }
+      let mut splits: Vec<Point64> = seam_vertices
+        .iter()
+        .copied()
+        .filter(|vertex| {
+          *vertex != start
+            && *vertex != end
+            && cross(start, end, *vertex) == 0
+            && vertex.0 >= start.0.min(end.0)
+            && vertex.0 <= start.0.max(end.0)
+            && vertex.1 >= start.1.min(end.1)
+            && vertex.1 <= start.1.max(end.1)
+        })
+        .collect();
+      splits.sort_by_key(|vertex| (vertex.0 - start.0).abs() + (vertex.1 - start.1).abs());
+      let mut previous = start;
+      for vertex in splits.into_iter().chain(std::iter::once(end)) {
+        edges.push((previous, vertex));
+        previous = vertex;
+      }
+    }
+  }
+
+  // cancel edges that occur in both directions
+  let mut counts: HashMap<(Point64, Point64), usize> = HashMap::new();
+  for (start, end) in edges.iter() {
+    match counts.get_mut(&(*end, *start)) {
+      Some(count) if *count > 0 => *count -= 1,
+      _ => *counts.entry((*start, *end)).or_default() += 1,
+    }
+  }
+  let mut outgoing: HashMap<Point64, Vec<Point64>> = HashMap::new();
+  for (start, end) in edges {
+    if let Some(count) = counts.get_mut(&(start, end))
+      && *count > 0
+    {
+      *count -= 1;
+      outgoing.entry(start).or_default().push(end);
+    }
+  }
+
+  // link the remaining edges into rings
+  let mut merged: Vec<Vec<Point64>> = Vec::new();
+  let mut starts: Vec<Point64> = outgoing.keys().copied().collect();
+  starts.sort_unstable();
+  for first in starts {
+    while let Some(next) = outgoing.get_mut(&first).and_then(|ends| ends.pop()) {
+      let mut ring = vec![first];
+      let mut current = next;
+      while current != first {
+        ring.push(current);
+        match outgoing.get_mut(&current).and_then(|ends| ends.pop()) {
+          Some(next) => current = next,
+          None => break,
+        }
+      }
+      // drop the vertices that only existed because of the tile boundary
+      let mut index = 0;
+      while ring.len() > 3 && index < ring.len() {
+        let previous = ring[(index + ring.len() - 1) % ring.len()];
+        let next = ring[(index + 1) % ring.len()];
+        if on_seam(ring[index]) && cross(previous, ring[index], next) == 0 {
+          ring.remove(index);
+        } else {
+          index += 1;
+        }
+      }
+      if ring.len() >= 3 {
+        merged.push(ring);
+      }
+    }
+  }
+
+  // assign holes to the smallest exterior containing them
+  let (exteriors, holes): (Vec<_>, Vec<_>) =
+    merged.into_iter().partition(|ring| signed_area(ring) > 0.0);
+  let mut polygons: Vec<(Vec<Point64>, Vec<Vec<Point64>>)> = exteriors
+    .into_iter()
+    .map(|exterior| (exterior, Vec::new()))
+    .collect();
+  let mut orphans = Vec::new();
+  for hole in holes {
+    let container = polygons
+      .iter_mut()
+      .filter(|(exterior, _)| hole.iter().any(|point| ring_contains(exterior, *point)))
+      .min_by(|a, b| signed_area(&a.0).total_cmp(&signed_area(&b.0)));
+    match container {
+      Some((_, interiors)) => interiors.push(hole),
+      None => orphans.push((hole.into_iter().rev().collect(), Vec::new())),
+    }
+  }
+  polygons.extend(orphans);
+  polygons
+}
+
+fn polygons_to_geometry(polygons: Vec<(Vec<Point64>, Vec<Vec<Point64>>)>) -> Option<Geometry<f64>> {
+  let ring = |ring: Vec<Point64>| LineString::new(ring.into_iter().map(to_coord).collect());
+  let mut polygons: Vec<Polygon<f64>> = polygons
+    .into_iter()
+    .map(|(exterior, interiors)| {
+      Polygon::new(ring(exterior), interiors.into_iter().map(ring).collect())
+    })
+    .collect();
+  match polygons.len() {
+    0 => None,
+    1 => Some(polygons.remove(0).into()),
+    _ => Some(MultiPolygon::new(polygons).into()),
+  }
+}
@@ -0,0 +1,513 @@
+//! This module provides the triangulation of features into flat vertex and index buffers for rendering.
+//!
+//! Polygons are triangulated with `earcutr`, a port of Mapbox's earcut, taking their holes into account.
+//! The result is an indexed triangle list. Lines are extruded to the given width into an indexed
+//! triangle strip, every segment becomes a quad and the vertices between two segments are covered by a
+//! join, the ends of a line by a cap. Separate lines are connected by degenerate triangles. Both can be
+//! uploaded to a GPU without further processing and drawn with the primitive of their `Topology`.
+//!
+//! # Types
+//!
+//! The `tessellate` module defines the following types:
+//!
+//! - `Mesh`: Represents triangles as flat vertex and index buffers.
+//! - `Topology`: Represents how the indices of a mesh form triangles.
+//! - `LineStyle`: Represents the width, join and cap used to extrude lines.
+//! - `LineJoin`: Represents the shape at the vertices between two line segments.
+//! - `LineCap`: Represents the shape at the ends of a line.
+
+use std::f32::consts::PI;
+
+use geo_types::{Coord, CoordNum, Geometry, LineString, Polygon};
+use num_traits::NumCast;
+
+use crate::feature::Feature;
+
+/// An enumeration of the ways the indices of a mesh form triangles.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Topology {
+  /// Every three indices form a separate triangle, like `gl.TRIANGLES`.
+  #[default]
+  Triangles,
+
+  /// Every index forms a triangle with the two indices before it, like `gl.TRIANGLE_STRIP`.
+  TriangleStrip,
+}
+
+/// A structure representing triangles as flat vertex and index buffers.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Mesh {
+  /// The vertex positions as consecutive `x, y` pairs in tile coordinates.
+  pub vertices: Vec<f32>,
+
+  /// The vertex indices, forming triangles according to the topology.
+  pub indices: Vec<u32>,
+
+  /// How the indices form triangles.
+  pub topology: Topology,
+}
+
+impl Mesh {
+  /// Creates a new empty `Mesh` of a triangle list.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Creates a new empty `Mesh` of a triangle strip.
+  pub fn strip() -> Self {
+    Self {
+      topology: Topology::TriangleStrip,
+      ..Self::default()
+    }
+  }
+
+  /// Returns the number of vertices.
+  pub fn vertex_count(&self) -> usize {
+    self.vertices.len() / 2
+  }
+
+  /// Returns the number of triangles.
+  ///
+  /// The degenerate triangles that connect the parts of a triangle strip are not counted.
+  pub fn triangle_count(&self) -> usize {
+    match self.topology {
+      Topology::Triangles => self.indices.len() / 3,
+      Topology::TriangleStrip => self
+        .indices
+        .windows(3)
+        .filter(|triangle| !is_degenerate(triangle))
+        .count(),
+    }
+  }
+
+  /// Returns `true` if the mesh has no triangles.
+  pub fn is_empty(&self) -> bool {
+    self.triangle_count() == 0
+  }
+
+  /// Converts the mesh into a triangle list.
+  ///
+  /// The triangles of a strip keep their winding order, degenerate triangles are dropped.
+  ///
+  /// # Returns
+  ///
+  /// A `Mesh` with the same vertices and the `Triangles` topology.
+  pub fn to_triangles(&self) -> Mesh {
+    let indices = match self.topology {
+      Topology::Triangles => self.indices.clone(),
+      Topology::TriangleStrip => self
+        .indices
+        .windows(3)
+        .enumerate()
+        .filter(|(_, triangle)| !is_degenerate(triangle))
+        .flat_map(|(index, triangle)| match index % 2 {
+          0 => [triangle[0], triangle[1], triangle[2]],
+          _ => [triangle[1], triangle[0], triangle[2]],
+        })
+        .collect(),
+    };
+    Mesh {
+      vertices: self.vertices.clone(),
+      indices,
+      topology: Topology::Triangles,
+    }
+  }
+
+  /// Appends the triangles of another mesh, shifting its indices behind the vertices of this mesh.
+  ///
+  /// Triangle strips are connected by degenerate triangles. If the topologies of both meshes differ, the
+  /// result is a triangle list. An empty mesh takes the topology of the appended mesh.
+  ///
+  /// # Arguments
+  ///
+  /// * `other` - The mesh to append.
+  pub fn append(&mut self, other: &Mesh) {
+    if self.indices.is_empty() {
+      self.topology = other.topology;
+    } else if self.topology != other.topology {
+      if self.topology == Topology::TriangleStrip {
+        *self = self.to_triangles();
+      }
+      if other.topology == Topology::TriangleStrip {
+        self.append(&other.to_triangles());
+        return;
+      }
+    }
+    let offset = self.vertex_count() as u32;
+    self.vertices.extend_from_slice(&other.vertices);
+    let indices = other.indices.iter().map(|index| index + offset);
+    if self.topology == Topology::TriangleStrip
+      && let (Some(&last), Some(&first)) = (self.indices.last(), other.indices.first())
+    {
+      // repeat the last index once more for an odd length to keep the winding order of `other`
+      let repeat = if self.indices.len().is_multiple_of(2) {
+        1
+      } else {
+        2
+      };
+      self.indices.extend(std::iter::repeat_n(last, repeat));
+      self.indices.push(first + offset);
+    }
+    self.indices.extend(indices);
+  }
+
+  fn push_vertex(&mut self, (x, y): (f32, f32)) -> u32 {
+    let index = self.vertex_count() as u32;
+    self.vertices.push(x);
+    self.vertices.push(y);
+    index
+  }
+
+  /// Continues a triangle strip with a vertex on the left and on the right side of a line.
+  fn push_pair(&mut self, left: u32, right: u32) {
+    self.indices.extend_from_slice(&[left, right]);
+  }
+}
+
+fn is_degenerate(triangle: &[u32]) -> bool {
+  triangle[0] == triangle[1] || triangle[1] == triangle[2] || triangle[0] == triangle[2]
+}
+
+/// An enumeration of the shapes at the vertices between two line segments.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineJoin {
+  /// Extends the outer edges of both segments until they meet, falling back to `Bevel` beyond the miter limit.
+  #[default]
+  Miter,
+
+  /// Connects the outer corners of both segments with a straight edge.
+  Bevel,
+
+  /// Connects the outer corners of both segments with a circular arc.
+  Round,
+}
+
+/// An enumeration of the shapes at the ends of a line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineCap {
+  /// Ends the line exactly at its end points.
+  #[default]
+  Butt,
+
+  /// Extends the line by half of its width beyond its end points.
+  Square,
+
+  /// Ends the line with a half circle around its end points.
+  Round,
+}
+
+/// A structure representing how lines are extruded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineStyle {
+  /// The width of the line in tile coordinates.
+  pub width: f32,
+
+  /// The shape at the vertices between two segments.
+  pub join: LineJoin,
+
+  /// The shape at the ends of the line.
+  pub cap: LineCap,
+
+  /// The maximum ratio of the miter length to half of the line width before a miter join is beveled.
+  pub miter_limit: f32,
+}
+
+impl Default for LineStyle {
+  fn default() -> Self {
+    Self {
+      width: 1.0,
+      join: LineJoin::default(),
+      cap: LineCap::default(),
+      miter_limit: 2.0,
+    }
+  }
+}
+
+impl<T: CoordNum> Feature<T> {
+  /// Triangulates the polygons of the feature.
+  ///
+  /// Other geometries result in an empty mesh, as do polygons that cannot be triangulated.
+  ///
+  /// # Returns
+  ///
+  /// A `Mesh` covering the polygons of the feature.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::Reader;
+  ///
+  /// let data = vec![/* Vector tile data */];
+  /// let reader = Reader::new(data).unwrap();
+  ///
+  /// for feature in reader.get_features(0).unwrap_or_default() {
+  ///   let mesh = feature.tessellate();
+  ///   println!("{} triangles", mesh.triangle_count());
+  /// }
+  /// ```
+  pub fn tessellate(&self) -> Mesh {
+    let mut mesh = Mesh::new();
+    visit_geometry(&self.geometry, &mut |part| {
+      if let Part::Polygon(polygon) = part {
+        triangulate_polygon(polygon, &mut mesh);
+      }
+    });
+    mesh
+  }
+
+  /// Extrudes the lines of the feature to a triangle strip.
+  ///
+  /// Other geometries result in an empty mesh.
+  ///
+  /// # Arguments
+  ///
+  /// * `style` - The width, join and cap of the lines.
+  ///
+  /// # Returns
+  ///
+  /// A `Mesh` of the `TriangleStrip` topology covering the extruded lines of the feature.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mvt_reader::{Reader, tessellate::{LineJoin, LineStyle}};
+  ///
+  /// let data = vec![/* Vector tile data */];
+  /// let reader = Reader::new(data).unwrap();
+  ///
+  /// let style = LineStyle { width: 16.0, join: LineJoin::Round, ..Default::default() };
+  /// for feature in reader.get_features(0).unwrap_or_default() {
+  ///   let mesh = feature.extrude(&style);
+  ///   println!("{} triangles", mesh.triangle_count());
+  /// }
+  /// ```
+  pub fn extrude(&self, style: &LineStyle) -> Mesh {
+    let mut mesh = Mesh::strip();
+    visit_geometry(&self.geometry, &mut |part| {
+      if let Part::Line(linestring) = part {
+        let mut line = Mesh::strip();
+        extrude_line(linestring, style, &mut line);
+        mesh.append(&line);
+      }
+    });
+    mesh
+  }
+}
+
+enum Part<'a, T: CoordNum> {
+  Line(&'a LineString<T>),
+  Polygon(&'a Polygon<T>),
+}
+
+fn visit_geometry<T: CoordNum, F: FnMut(Part<T>)>(geometry: &Geometry<T>, visit: &mut F) {
+  match geometry {
+    Geometry::LineString(linestring) => visit(Part::Line(linestring)),
+    Geometry::MultiLineString(linestrings) => {
+      for linestring in linestrings {
+        visit(Part::Line(linestring));
+      }
+    }
+    Geometry::Polygon(polygon) => visit(Part::Polygon(polygon)),
+    Geometry::MultiPolygon(polygons) => {
+      for polygon in polygons {
+        visit(Part::Polygon(polygon));
+      }
+    }
+    Geometry::GeometryCollection(collection) => {
+      for geometry in collection {
+        visit_geometry(geometry, visit);
+      }
+    }
+    _ => (),
+  }
+}
+
+fn to_f32<T: CoordNum>(coord: Coord<T>) -> (f32, f32) {
+  (
+    NumCast::from(coord.x).unwrap_or(0.0),
+    NumCast::from(coord.y).unwrap_or(0.0),
+  )
+}
+
+/// Returns the vertices of a ring without the closing vertex.
+fn ring_vertices<T: CoordNum>(ring: &LineString<T>) -> &[Coord<T>] {
+  match ring.0.split_last() {
+    Some((last, rest)) if ring.0.first() == Some(last) => rest,
+    _ => &ring.0,
+  }
+}
+
+fn triangulate_polygon<T: CoordNum>(polygon: &Polygon<T>, mesh: &mut Mesh) {
+  let exterior = ring_vertices(polygon.exterior());
+  if exterior.len() < 3 {
+    return;
+  }
+  let mut vertices: Vec<f64> = Vec::with_capacity(exterior.len() * 2);
+  let mut hole_indices: Vec<usize> = Vec::with_capacity(polygon.interiors().len());
+  let push_ring = |ring: &[Coord<T>], vertices: &mut Vec<f64>| {
+    for coord in ring {
+      let (x, y) = to_f32(*coord);
+      vertices.push(x as f64);
+      vertices.push(y as f64);
+    }
+  };
+  push_ring(exterior, &mut vertices);
+  for interior in polygon.interiors() {
+    let interior = ring_vertices(interior);
+    if interior.len() >= 3 {
+      hole_indices.push(vertices.len() / 2);
+      push_ring(interior, &mut vertices);
+    }
+  }
+
+  let Ok(triangles) = earcutr::earcut(&vertices, &hole_indices, 2) else {
+    return;
+  };
+  let offset = mesh.vertex_count() as u32;
+  mesh
+    .vertices
+    .extend(vertices.iter().map(|value| *value as f32));
+  mesh
+    .indices
+    .extend(triangles.into_iter().map(|index| index as u32 + offset));
+}
+
+fn add(a: (f32, f32), b: (f32, f32)) -> (f32, f32) {
+  (a.0 + b.0, a.1 + b.1)
+}
+
+fn scale(a: (f32, f32), factor: f32) -> (f32, f32) {
+  (a.0 * factor, a.1 * factor)
+}
+
+fn normalize(a: (f32, f32)) -> Option<(f32, f32)> {
+  let length = a.0.hypot(a.1);
+  (length > 0.0).then(|| (a.0 / length, a.1 / length))
+}
+
+/// Returns the points of an arc around `center` from the direction `from` rotating by `angle`.
+fn arc_points(center: (f32, f32), from: (f32, f32), angle: f32, radius: f32) -> Vec<(f32, f32)> {
+  let steps = ((angle.abs() / (PI / 8.0)).ceil() as usize).max(1);
+  let start = from.1.atan2(from.0);
+  (0..=steps)
+    .map(|step| {
+      let theta = start + angle * step as f32 / steps as f32;
+      add(center, scale((theta.cos(), theta.sin()), radius))
+    })
+    .collect()
+}
+
+/// Extrudes a line into a triangle strip of pairs of vertices on its left and right side.
+///
+/// Joins and round caps are fans around the vertex of the line, which are part of the strip by pairing
+/// every point of the fan with the vertex. The triangles this adds along the line are degenerate.
+fn extrude_line<T: CoordNum>(linestring: &LineString<T>, style: &LineStyle, mesh: &mut Mesh) {
+  let half = style.width / 2.0;
+  let mut points: Vec<(f32, f32)> = Vec::with_capacity(linestring.0.len());
+  for coord in &linestring.0 {
+    let point = to_f32(*coord);
+    if points.last() != Some(&point) {
+      points.push(point);
+    }
+  }
+  if points.len() < 2 || half <= 0.0 {
+    return;
+  }
+
+  // unit directions of the segments
+  let directions: Vec<(f32, f32)> = points
+    .windows(2)
+    .filter_map(|pair| normalize((pair[1].0 - pair[0].0, pair[1].1 - pair[0].1)))
+    .collect();
+  let normal = |direction: (f32, f32)| (-direction.1, direction.0);
+
+  let last = points.len() - 1;
+  if style.cap == LineCap::Square {
+    points[0] = add(points[0], scale(directions[0], -half));
+    points[last] = add(points[last], scale(directions[last - 1], half));
+  }
+
+  let push_side_pair = |mesh: &mut Mesh, point: (f32, f32), direction: (f32, f32)| {
+    let offset = scale(normal(direction), half);
+    let left = mesh.push_vertex(add(point, offset));
+    let right = mesh.push_vertex(add(point, scale(offset, -1.0)));
+    mesh.push_pair(left, right);
+    (left, right)
+  };
+
+  if style.cap == LineCap::Round {
+    // from the left side around the start to the right side
+    let center = mesh.push_vertex(points[0]);
+    for point in arc_points(points[0], normal(directions[0]), PI, half) {
+      let point = mesh.push_vertex(point);
+      mesh.push_pair(point, center);
+    }
+  }
+  push_side_pair(mesh, points[0], directions[0]);
+
+  for index in 1..last {
+    let (incoming, outgoing) = (directions[index - 1], directions[index]);
+    let cross = incoming.0 * outgoing.1 - incoming.1 * outgoing.0;
+    let center = points[index];
+    if cross == 0.0 {
+      push_side_pair(mesh, center, incoming);
+      if incoming.0 * outgoing.0 + incoming.1 * outgoing.1 < 0.0 {
+        push_side_pair(mesh, center, outgoing);
+      }
+      continue;
+    }
+    // the join fills the gap on the outer side of the turn
+    let outer_left = cross < 0.0;
+    let side = if outer_left { 1.0 } else { -1.0 };
+    let (from, to) = (scale(normal(incoming), side), scale(normal(outgoing), side));
+    let fan: Vec<(f32, f32)> = match style.join {
+      LineJoin::Round => {
+        let angle = (from.0 * to.1 - from.1 * to.0).atan2(from.0 * to.0 + from.1 * to.1);
+        arc_points(center, from, angle, half)
+      }
+      join => {
+        let outer_from = add(center, scale(from, half));
+        let outer_to = add(center, scale(to, half));
+        let miter = normalize(add(from, to))
+          .map(|miter| (miter, 1.0 / (miter.0 * from.0 + miter.1 * from.1)));
+        match miter {
+          Some((miter, ratio)) if join == LineJoin::Miter && ratio <= style.miter_limit => {
+            vec![
+              outer_from,
+              add(center, scale(miter, half * ratio)),
+              outer_to,
+            ]
+          }
+          _ => vec![outer_from, outer_to],
+        }
+      }
+    };
+
+    let (left, right) = push_side_pair(mesh, center, incoming);
+    let center = mesh.push_vertex(center);
+    let outer = if outer_left { left } else { right };
+    for (position, point) in fan.iter().enumerate() {
+      let point = match position {
+        0 => outer,
+        _ => mesh.push_vertex(*point),
+      };
+      if outer_left {
+        mesh.push_pair(point, center);
+      } else {
+        mesh.push_pair(center, point);
+      }
+    }
+    push_side_pair(mesh, points[index], outgoing);
+  }
+  push_side_pair(mesh, points[last], directions[last - 1]);
+
+  if style.cap == LineCap::Round {
+    // from the right side around the end to the left side
+    let center = mesh.push_vertex(points[last]);
+    let from = scale(normal(directions[last - 1]), -1.0);
+    for point in arc_points(points[last], from, PI, half) {
+      let point = mesh.push_vertex(point);
+      mesh.push_pair(center, point);
+    }
+  }
+}
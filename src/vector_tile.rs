@@ -1,5 +1,8 @@
 #[cfg(feature = "protoc")]
 include!(concat!(env!("OUT_DIR"), "/vector_tile.rs"));
 
-#[cfg(not(feature = "protoc"))]
+#[cfg(all(not(feature = "protoc"), feature = "mvt3"))]
+include!("draft/vector_tile.rs");
+
+#[cfg(not(any(feature = "protoc", feature = "mvt3")))]
 include!("generated/vector_tile.rs");
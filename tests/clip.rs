@@ -0,0 +1,123 @@
+mod support;
+
+use geo_types::{Geometry, LineString, MultiLineString, MultiPoint, MultiPolygon, Polygon};
+use mvt_reader::{Reader, options::FeatureOptions};
+use support::{GeomType, TestFeature, TestLayer, linestrings, points, rings, tile};
+
+fn buffered_tile() -> Vec<u8> {
+  tile(vec![TestLayer::new(
+    "buffered",
+    vec![
+      TestFeature::new(
+        Some(1),
+        GeomType::Point,
+        points(&[(-10, 5), (100, 100), (4100, 50)]),
+      ),
+      TestFeature::new(
+        Some(2),
+        GeomType::Linestring,
+        linestrings(&[&[
+          (-100, 100),
+          (100, 100),
+          (100, -100),
+          (200, -100),
+          (200, 100),
+          (300, 100),
+        ]]),
+      ),
+      TestFeature::new(
+        Some(3),
+        GeomType::Polygon,
+        rings(&[
+          &[(-100, 100), (100, 100), (100, 300), (-100, 300)],
+          &[(20, 150), (20, 250), (80, 250), (80, 150)],
+        ]),
+      ),
+      TestFeature::new(
+        Some(4),
+        GeomType::Polygon,
+        rings(&[&[(-300, 100), (-200, 100), (-200, 300), (-300, 300)]]),
+      ),
+    ],
+  )])
+}
+
+fn clipped(buffer: u32) -> Vec<mvt_reader::feature::Feature<i32>> {
+  let reader = Reader::new(buffered_tile()).unwrap();
+  let options = FeatureOptions {
+    clip_buffer: Some(buffer),
+    ..Default::default()
+  };
+  reader
+    .get_features_with_options::<i32>(0, &options)
+    .unwrap()
+}
+
+#[test]
+fn clip_points() {
+  let features = clipped(0);
+  assert_eq!(
+    features[0].geometry,
+    Geometry::MultiPoint(MultiPoint::from(vec![(100, 100)]))
+  );
+
+  let features = clipped(16);
+  assert_eq!(
+    features[0].geometry,
+    Geometry::MultiPoint(MultiPoint::from(vec![(-10, 5), (100, 100), (4100, 50)]))
+  );
+}
+
+#[test]
+fn clip_lines_into_parts() {
+  let features = clipped(0);
+  assert_eq!(
+    features[1].geometry,
+    Geometry::MultiLineString(MultiLineString::new(vec![
+      LineString::from(vec![(0, 100), (100, 100), (100, 0)]),
+      LineString::from(vec![(200, 0), (200, 100), (300, 100)]),
+    ]))
+  );
+}
+
+#[test]
+fn clip_polygons_with_holes() {
+  let features = clipped(0);
+  assert_eq!(features.len(), 3, "polygon outside of the tile is dropped");
+  assert_eq!(
+    features[2].geometry,
+    Geometry::MultiPolygon(MultiPolygon::new(vec![Polygon::new(
+      LineString::from(vec![(0, 100), (100, 100), (100, 300), (0, 300), (0, 100)]),
+      vec![LineString::from(vec![
+        (20, 150),
+        (20, 250),
+        (80, 250),
+        (80, 150),
+        (20, 150)
+      ])],
+    )]))
+  );
+
+  let features = clipped(250);
+  assert_eq!(features.len(), 4);
+  assert_eq!(features[3].id, Some(4));
+  let Geometry::MultiPolygon(polygons) = &features[3].geometry else {
+    panic!("Expected a polygon");
+  };
+  let polygon = &polygons.0[0];
+  assert_eq!(polygon.exterior().0[0].x, -250);
+  assert_eq!(polygon.exterior().0[1].x, -200);
+}
+
+#[test]
+fn clip_disabled_by_default() {
+  let reader = Reader::new(buffered_tile()).unwrap();
+  let features = reader
+    .get_features_with_options::<f32>(0, &FeatureOptions::default())
+    .unwrap();
+  assert_eq!(features.len(), 4);
+  assert_eq!(
+    features[0].geometry,
+    reader.get_features(0).unwrap()[0].geometry
+  );
+}
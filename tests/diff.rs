@@ -0,0 +1,216 @@
+mod support;
+
+use mvt_reader::{
+  Reader,
+  diff::{FeatureKey, GeometryChange, PropertyChange, diff_with_tolerance},
+  feature::Value,
+};
+use support::{GeomType, TestFeature, TestLayer, linestrings, points, tile};
+
+fn road(id: u64, class: &'static str, end: i32) -> TestFeature {
+  TestFeature::new(
+    Some(id),
+    GeomType::Linestring,
+    linestrings(&[&[(0, 0), (end, 10)]]),
+  )
+  .with("class", Value::String(String::from(class)))
+}
+
+fn old_tile() -> Vec<u8> {
+  tile(vec![
+    TestLayer::new(
+      "roads",
+      vec![
+        road(1, "primary", 10),
+        road(2, "secondary", 10),
+        road(3, "service", 10),
+        TestFeature::new(None, GeomType::Point, points(&[(5, 5)])),
+      ],
+    ),
+    TestLayer::new("landuse", vec![]),
+  ])
+}
+
+fn new_tile() -> Vec<u8> {
+  tile(vec![
+    TestLayer::new(
+      "roads",
+      vec![
+        TestFeature::new(None, GeomType::Point, points(&[(5, 5)])),
+        road(4, "track", 10),
+        road(3, "service", 11),
+        road(2, "secondary", 40).with("lanes", Value::UInt(2)),
+        road(1, "trunk", 10),
+      ],
+    ),
+    TestLayer::new("water", vec![]),
+  ])
+}
+
+#[test]
+fn identical_tiles() {
+  let a = Reader::new(old_tile()).unwrap();
+  let b = Reader::new(old_tile()).unwrap();
+
+  let diff = mvt_reader::diff(&a, &b).unwrap();
+  assert!(diff.is_empty());
+  assert_eq!(diff.to_string(), "");
+}
+
+#[test]
+fn changed_tiles() {
+  let a = Reader::new(old_tile()).unwrap();
+  let b = Reader::new(new_tile()).unwrap();
+
+  let diff = mvt_reader::diff(&a, &b).unwrap();
+  assert_eq!(diff.added_layers, vec![String::from("water")]);
+  assert_eq!(diff.removed_layers, vec![String::from("landuse")]);
+  assert_eq!(diff.layers.len(), 1);
+
+  let roads = &diff.layers[0];
+  assert_eq!(roads.extent, None);
+  assert_eq!(roads.added, vec![FeatureKey::Id(4)]);
+  assert!(roads.removed.is_empty());
+  assert_eq!(
+    roads
+      .modified
+      .iter()
+      .map(|change| change.key)
+      .collect::<Vec<_>>(),
+    vec![FeatureKey::Id(1), FeatureKey::Id(2), FeatureKey::Id(3)]
+  );
+  assert_eq!(
+    roads.modified[0].properties,
+    vec![PropertyChange {
+      key: String::from("class"),
+      before: Some(Value::String(String::from("primary"))),
+      after: Some(Value::String(String::from("trunk"))),
+    }]
+  );
+  assert_eq!(roads.modified[0].geometry, None);
+  assert_eq!(roads.modified[1].properties[0].before, None);
+  assert_eq!(
+    roads.modified[1].geometry,
+    Some(GeometryChange::Moved { max_distance: 30.0 })
+  );
+  assert_eq!(
+    roads.modified[2].geometry,
+    Some(GeometryChange::Moved { max_distance: 1.0 })
+  );
+
+  assert_eq!(
+    diff.to_string(),
+    "+ layer water\n\
+     - layer landuse\n\
+     ~ layer roads\n  \
+       + feature id 4\n  \
+       ~ feature id 1\n    \
+         class: primary -> trunk\n  \
+       ~ feature id 2\n    \
+         + lanes: 2\n    \
+         geometry moved by up to 30\n  \
+       ~ feature id 3\n    \
+         geometry moved by up to 1\n"
+  );
+}
+
+#[test]
+fn tolerance_and_extent() {
+  let a = Reader::new(old_tile()).unwrap();
+  let b = Reader::new(new_tile()).unwrap();
+
+  let diff = diff_with_tolerance(&a, &b, 1.0).unwrap();
+  let keys = diff.layers[0]
+    .modified
+    .iter()
+    .map(|change| change.key)
+    .collect::<Vec<_>>();
+  assert_eq!(keys, vec![FeatureKey::Id(1), FeatureKey::Id(2)]);
+
+  // the same features in a layer with twice the extent
+  let mut layer = TestLayer::new(
+    "roads",
+    vec![TestFeature::new(None, GeomType::Point, points(&[(10, 10)]))],
+  );
+  layer.extent = 8192;
+  let a = Reader::new(tile(vec![TestLayer::new(
+    "roads",
+    vec![TestFeature::new(None, GeomType::Point, points(&[(5, 5)]))],
+  )]))
+  .unwrap();
+  let b = Reader::new(tile(vec![layer])).unwrap();
+
+  let diff = mvt_reader::diff(&a, &b).unwrap();
+  assert_eq!(diff.layers[0].extent, Some((4096, 8192)));
+  assert!(diff.layers[0].added.is_empty());
+  assert!(diff.layers[0].removed.is_empty());
+  assert!(diff.layers[0].modified.is_empty());
+}
+
+#[test]
+fn features_without_id_are_matched_by_geometry() {
+  let a = Reader::new(tile(vec![TestLayer::new(
+    "poi",
+    vec![
+      TestFeature::new(None, GeomType::Point, points(&[(1, 1)])).with("rank", Value::UInt(1)),
+      TestFeature::new(None, GeomType::Point, points(&[(2, 2)])),
+    ],
+  )]))
+  .unwrap();
+  let b = Reader::new(tile(vec![TestLayer::new(
+    "poi",
+    vec![
+      TestFeature::new(None, GeomType::Point, points(&[(3, 3)])),
+      TestFeature::new(None, GeomType::Point, points(&[(1, 1)])),
+    ],
+  )]))
+  .unwrap();
+
+  let diff = mvt_reader::diff(&a, &b).unwrap();
+  let poi = &diff.layers[0];
+  assert_eq!(poi.added.len(), 1);
+  assert_eq!(poi.removed.len(), 1);
+  assert_ne!(poi.added[0], poi.removed[0]);
+  assert_eq!(poi.modified.len(), 1);
+  assert!(matches!(poi.modified[0].key, FeatureKey::Geometry(_)));
+  assert_eq!(
+    poi.modified[0].properties[0].after, None,
+    "the rank was removed"
+  );
+}
+
+#[test]
+fn numeric_variants_of_the_same_number_are_equal() {
+  let poi = |properties: [(&'static str, Value); 4]| {
+    let feature = properties.into_iter().fold(
+      TestFeature::new(Some(1), GeomType::Point, points(&[(1, 1)])),
+      |feature, (key, value)| feature.with(key, value),
+    );
+    Reader::new(tile(vec![TestLayer::new("poi", vec![feature])])).unwrap()
+  };
+  let a = poi([
+    ("rank", Value::Int(5)),
+    ("weight", Value::Float(1.0)),
+    ("ratio", Value::Double(f64::NAN)),
+    ("population", Value::UInt((1 << 53) + 1)),
+  ]);
+  let b = poi([
+    ("rank", Value::UInt(5)),
+    ("weight", Value::Double(1.0)),
+    ("ratio", Value::Double(f64::NAN)),
+    ("population", Value::UInt(1 << 53)),
+  ]);
+
+  let diff = mvt_reader::diff(&a, &b).unwrap();
+  let modified = &diff.layers[0].modified;
+  assert_eq!(modified.len(), 1);
+  assert_eq!(
+    modified[0].properties,
+    vec![PropertyChange {
+      key: String::from("population"),
+      before: Some(Value::UInt((1 << 53) + 1)),
+      after: Some(Value::UInt(1 << 53)),
+    }]
+  );
+  assert!(mvt_reader::diff(&a, &a).unwrap().is_empty());
+}
@@ -0,0 +1,503 @@
+#![cfg(feature = "mvt3")]
+
+mod support;
+
+use std::collections::BTreeMap;
+
+use geo_types::{Geometry, LineString, coord};
+use mvt_reader::{
+  Reader,
+  elevation::{Coord3D, Geometry3D},
+  error::ErrorKind,
+  feature::Value,
+  options::FeatureOptions,
+  spline::Spline,
+  stitch::TileCoordinates,
+};
+use prost::Message;
+use support::{GeomType, command, linestrings, rings, zigzag};
+
+#[derive(Clone, PartialEq, Message)]
+struct DraftTile {
+  #[prost(message, repeated, tag = "3")]
+  layers: Vec<DraftLayer>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct DraftLayer {
+  #[prost(uint32, tag = "15")]
+  version: u32,
+  #[prost(string, tag = "1")]
+  name: String,
+  #[prost(message, repeated, tag = "2")]
+  features: Vec<DraftFeature>,
+  #[prost(string, repeated, tag = "3")]
+  keys: Vec<String>,
+  #[prost(uint32, optional, tag = "5")]
+  extent: Option<u32>,
+  #[prost(string, repeated, tag = "9")]
+  string_values: Vec<String>,
+  #[prost(float, repeated, tag = "10")]
+  float_values: Vec<f32>,
+  #[prost(double, repeated, tag = "11")]
+  double_values: Vec<f64>,
+  #[prost(sint64, repeated, tag = "12")]
+  int_values: Vec<i64>,
+  #[prost(message, optional, tag = "13")]
+  elevation_scaling: Option<DraftScaling>,
+  #[prost(message, repeated, tag = "14")]
+  attribute_scalings: Vec<DraftScaling>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct DraftScaling {
+  #[prost(sint64, tag = "1")]
+  offset: i64,
+  #[prost(double, tag = "2")]
+  multiplier: f64,
+  #[prost(double, tag = "3")]
+  base: f64,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct DraftFeature {
+  #[prost(uint64, optional, tag = "1")]
+  id: Option<u64>,
+  #[prost(uint32, repeated, tag = "2")]
+  tags: Vec<u32>,
+  #[prost(int32, optional, tag = "3")]
+  r#type: Option<i32>,
+  #[prost(uint32, repeated, tag = "4")]
+  geometry: Vec<u32>,
+  #[prost(uint64, repeated, tag = "5")]
+  attributes: Vec<u64>,
+  #[prost(uint64, repeated, tag = "6")]
+  geometric_attributes: Vec<u64>,
+  #[prost(sint32, repeated, tag = "7")]
+  elevations: Vec<i32>,
+  #[prost(uint64, repeated, tag = "8")]
+  spline_knots: Vec<u64>,
+  #[prost(uint32, optional, tag = "9")]
+  spline_degree: Option<u32>,
+}
+
+fn complex(value_type: u64, parameter: u64) -> u64 {
+  value_type | (parameter << 4)
+}
+
+fn draft_tile(attributes: Vec<u64>) -> Vec<u8> {
+  draft_layer(vec![DraftFeature {
+    id: Some(1),
+    r#type: Some(GeomType::Point as i32),
+    geometry: vec![command(1, 1), zigzag(10), zigzag(20)],
+    attributes,
+    ..Default::default()
+  }])
+}
+
+fn draft_layer(features: Vec<DraftFeature>) -> Vec<u8> {
+  DraftTile {
+    layers: vec![DraftLayer {
+      version: 3,
+      name: String::from("draft"),
+      features,
+      keys: ["name", "levels", "address", "street", "number"]
+        .map(String::from)
+        .to_vec(),
+      extent: Some(4096),
+      string_values: vec![String::from("Main Street"), String::from("Hall")],
+      float_values: vec![1.5],
+      double_values: vec![2.25],
+      int_values: vec![-7],
+      elevation_scaling: Some(DraftScaling {
+        offset: 0,
+        multiplier: 0.5,
+        base: 100.0,
+      }),
+      attribute_scalings: vec![DraftScaling {
+        offset: 10,
+        multiplier: 2.0,
+        base: 0.0,
+      }],
+    }],
+  }
+  .encode_to_vec()
+}
+
+#[test]
+fn read_inlined_scalar_attributes() {
+  let data = draft_tile(vec![0, complex(0, 1), 1, complex(5, zigzag(-3) as u64)]);
+  let reader = Reader::new(data).unwrap();
+  let features = reader.get_features(0).unwrap();
+  let properties = features[0].properties.as_ref().unwrap();
+
+  assert_eq!(properties["name"], Value::String(String::from("Hall")));
+  assert_eq!(properties["levels"], Value::SInt(-3));
+}
+
+#[test]
+fn filter_on_inlined_attributes() {
+  let data = draft_tile(vec![0, complex(0, 1), 1, complex(5, zigzag(-3) as u64)]);
+  let reader = Reader::new(data).unwrap();
+
+  let matched = reader
+    .get_features_where(0, |props| {
+      props.get_str("name") == Some("Hall") && props.get("levels") == Some(Value::SInt(-3))
+    })
+    .unwrap();
+  assert_eq!(matched.len(), 1);
+  assert!(
+    reader
+      .get_features_where(0, |props| props.contains_key("address"))
+      .unwrap()
+      .is_empty()
+  );
+
+  #[cfg(feature = "filter")]
+  {
+    let count = |filter: &str| {
+      let filter = mvt_reader::filter::Filter::parse(filter).unwrap();
+      reader
+        .get_features_filtered(0, &filter, 14.0)
+        .unwrap()
+        .len()
+    };
+    assert_eq!(count(r#"["==", ["get", "name"], "Hall"]"#), 1);
+    assert_eq!(count(r#"["<", ["get", "levels"], 0]"#), 1);
+    assert_eq!(count(r#"["has", "address"]"#), 0);
+  }
+}
+
+#[test]
+fn read_list_and_map_attributes() {
+  let data = draft_tile(vec![
+    1,
+    complex(7, 5),
+    complex(1, 0),
+    complex(2, 0),
+    complex(3, 0),
+    complex(4, 42),
+    complex(6, 2),
+    2,
+    complex(8, 2),
+    3,
+    complex(0, 0),
+    4,
+    complex(6, 1),
+  ]);
+  let reader = Reader::new(data).unwrap();
+  let features = reader.get_features(0).unwrap();
+  let properties = features[0].properties.as_ref().unwrap();
+
+  assert_eq!(
+    properties["levels"],
+    Value::List(vec![
+      Value::Float(1.5),
+      Value::Double(2.25),
+      Value::Int(-7),
+      Value::UInt(42),
+      Value::Null,
+    ])
+  );
+  assert_eq!(
+    properties["address"],
+    Value::Map(BTreeMap::from([
+      (
+        String::from("street"),
+        Value::String(String::from("Main Street"))
+      ),
+      (String::from("number"), Value::Bool(true)),
+    ]))
+  );
+}
+
+#[test]
+fn reject_invalid_attributes() {
+  for attributes in [
+    vec![0],
+    vec![9, complex(4, 1)],
+    vec![0, complex(0, 5)],
+    vec![0, complex(7, 2), complex(4, 1)],
+    vec![0, complex(15, 0)],
+  ] {
+    let reader = Reader::new(draft_tile(attributes)).unwrap();
+    let error = reader.get_features(0).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Tags);
+    assert_eq!(error.layer_index(), Some(0));
+    assert_eq!(error.feature_index(), Some(0));
+  }
+}
+
+fn coord(x: f32, y: f32, z: f64) -> Coord3D {
+  Coord3D { x, y, z }
+}
+
+#[test]
+fn read_elevations() {
+  let data = draft_layer(vec![
+    DraftFeature {
+      r#type: Some(GeomType::Polygon as i32),
+      geometry: rings(&[&[(0, 0), (10, 0), (10, 10), (0, 10)]]),
+      elevations: vec![0, 2, 2, -4],
+      ..Default::default()
+    },
+    DraftFeature {
+      r#type: Some(GeomType::Linestring as i32),
+      geometry: linestrings(&[&[(0, 0), (5, 5)]]),
+      ..Default::default()
+    },
+  ]);
+  let reader = Reader::new(data).unwrap();
+  let features = reader.get_features_3d::<f32>(0).unwrap();
+
+  assert_eq!(
+    features[0].geometry,
+    Geometry3D::MultiPolygon(vec![vec![vec![
+      coord(0.0, 0.0, 100.0),
+      coord(10.0, 0.0, 101.0),
+      coord(10.0, 10.0, 102.0),
+      coord(0.0, 10.0, 100.0),
+      coord(0.0, 0.0, 100.0),
+    ]]])
+  );
+  assert_eq!(
+    features[1].geometry,
+    Geometry3D::LineString(vec![coord(0.0, 0.0, 0.0), coord(5.0, 5.0, 0.0)])
+  );
+}
+
+#[test]
+fn read_geometric_attributes() {
+  let data = draft_layer(vec![DraftFeature {
+    r#type: Some(GeomType::Linestring as i32),
+    geometry: linestrings(&[&[(0, 0), (5, 5), (10, 0)]]),
+    geometric_attributes: vec![
+      1,
+      complex(9, 3),
+      0,
+      zigzag(2) as u64 + 1,
+      0,
+      zigzag(-1) as u64 + 1,
+    ],
+    attributes: vec![0, complex(9, 2), 0, 1, zigzag(3) as u64 + 1],
+    ..Default::default()
+  }]);
+  let reader = Reader::new(data).unwrap();
+  let features = reader.get_features_3d::<f32>(0).unwrap();
+
+  assert_eq!(
+    features[0].geometric_attributes["levels"],
+    vec![Some(24.0), None, Some(22.0)]
+  );
+  assert_eq!(
+    features[0].properties.as_ref().unwrap()["name"],
+    Value::List(vec![Value::Double(20.0), Value::Double(26.0)])
+  );
+}
+
+#[test]
+fn reject_mismatched_vertex_values() {
+  let elevations = DraftFeature {
+    r#type: Some(GeomType::Linestring as i32),
+    geometry: linestrings(&[&[(0, 0), (5, 5)]]),
+    elevations: vec![1],
+    ..Default::default()
+  };
+  let error = Reader::new(draft_layer(vec![elevations]))
+    .unwrap()
+    .get_features_3d::<f32>(0)
+    .unwrap_err();
+  assert_eq!(error.kind(), ErrorKind::Geometry);
+
+  let attributes = DraftFeature {
+    r#type: Some(GeomType::Linestring as i32),
+    geometry: linestrings(&[&[(0, 0), (5, 5)]]),
+    geometric_attributes: vec![1, complex(9, 1), 0, 1],
+    ..Default::default()
+  };
+  let error = Reader::new(draft_layer(vec![attributes]))
+    .unwrap()
+    .get_features_3d::<f32>(0)
+    .unwrap_err();
+  assert_eq!(error.kind(), ErrorKind::Tags);
+}
+
+const SPLINE: i32 = 4;
+
+/// A quadratic Bézier curve from (0, 0) to (100, 0) through (50, 50), with knots scaled by 1/2.
+fn spline_feature(knots: &[i64]) -> DraftFeature {
+  let mut spline_knots = vec![complex(9, knots.len() as u64), 1];
+  let mut previous = 0;
+  for knot in knots {
+    spline_knots.push(zigzag((knot - previous) as i32) as u64 + 1);
+    previous = *knot;
+  }
+  DraftFeature {
+    id: Some(7),
+    r#type: Some(SPLINE),
+    geometry: linestrings(&[&[(0, 0), (50, 100), (100, 0)]]),
+    spline_knots,
+    spline_degree: Some(2),
+    ..Default::default()
+  }
+}
+
+fn spline_layer(features: Vec<DraftFeature>) -> Vec<u8> {
+  let mut tile = DraftTile::decode(&draft_layer(features)[..]).unwrap();
+  tile.layers[0].attribute_scalings.push(DraftScaling {
+    offset: 0,
+    multiplier: 0.5,
+    base: 0.0,
+  });
+  tile.encode_to_vec()
+}
+
+#[test]
+fn read_spline_control_points() {
+  let reader = Reader::new(spline_layer(vec![spline_feature(&[0, 0, 0, 2, 2, 2])])).unwrap();
+  let splines = reader.get_splines::<f32>(0).unwrap();
+
+  assert_eq!(splines.len(), 1);
+  assert_eq!(splines[0].id, Some(7));
+  let spline = &splines[0].spline;
+  assert_eq!(
+    spline.control_points,
+    LineString::from(vec![(0.0, 0.0), (50.0, 100.0), (100.0, 0.0)])
+  );
+  assert_eq!(spline.knots, vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0]);
+  assert_eq!(spline.degree, 2);
+  assert_eq!(spline.evaluate(0.5), Some(coord! { x: 50.0, y: 50.0 }));
+}
+
+#[test]
+fn flatten_splines_into_linestrings() {
+  let reader = Reader::new(spline_layer(vec![spline_feature(&[0, 0, 0, 2, 2, 2])])).unwrap();
+
+  let coarse = reader.get_features(0).unwrap();
+  let options = FeatureOptions {
+    spline_tolerance: Some(0.1),
+    ..Default::default()
+  };
+  let fine = reader
+    .get_features_with_options::<f32>(0, &options)
+    .unwrap();
+
+  for features in [&coarse, &fine] {
+    let Geometry::LineString(linestring) = &features[0].geometry else {
+      panic!("expected a linestring, got {:?}", features[0].geometry);
+    };
+    assert_eq!(linestring.0.first(), Some(&coord! { x: 0.0, y: 0.0 }));
+    assert_eq!(linestring.0.last(), Some(&coord! { x: 100.0, y: 0.0 }));
+    assert!(linestring.0.contains(&coord! { x: 50.0, y: 50.0 }));
+  }
+  let vertex_count = |features: &[mvt_reader::feature::Feature]| match &features[0].geometry {
+    Geometry::LineString(linestring) => linestring.0.len(),
+    _ => 0,
+  };
+  assert!(vertex_count(&fine) > vertex_count(&coarse));
+
+  // only integer coordinates are rounded
+  let Geometry::LineString(linestring) = &coarse[0].geometry else {
+    unreachable!();
+  };
+  assert!(linestring.0.iter().any(|coord| coord.y.fract() != 0.0));
+  let features = reader.get_features_as::<i32>(0).unwrap();
+  let Geometry::LineString(rounded) = &features[0].geometry else {
+    panic!("expected a linestring, got {:?}", features[0].geometry);
+  };
+  assert_eq!(rounded.0.len(), linestring.0.len());
+  assert_eq!(rounded.0[1].y, linestring.0[1].y.round() as i32);
+}
+
+#[test]
+fn reject_splines_with_too_many_vertices() {
+  // a single quadratic span with an amplitude of 2^30 would need thousands of vertices
+  let feature = DraftFeature {
+    geometry: linestrings(&[&[(0, 0), (1 << 29, 1 << 30), (1 << 30, 0)]]),
+    ..spline_feature(&[0, 0, 0, 2, 2, 2])
+  };
+  let reader = Reader::new(spline_layer(vec![feature])).unwrap();
+
+  assert_eq!(
+    reader.get_features(0).unwrap_err().kind(),
+    ErrorKind::Geometry
+  );
+  let splines = reader.get_splines::<f64>(0).unwrap();
+  assert_eq!(
+    splines[0].spline.flatten(1.0).unwrap_err().kind(),
+    ErrorKind::Geometry
+  );
+  assert!(splines[0].spline.flatten(f64::from(1 << 20)).is_ok());
+}
+
+#[test]
+fn overzoom_flattens_splines_in_child_coordinates() {
+  let reader = Reader::new(spline_layer(vec![spline_feature(&[0, 0, 0, 2, 2, 2])])).unwrap();
+  let child = reader
+    .overzoom(
+      TileCoordinates::new(0, 0, 0),
+      TileCoordinates::new(4, 0, 0),
+      0,
+    )
+    .unwrap();
+
+  let parent = reader.get_features_as::<i32>(0).unwrap();
+  let features = child.get_features_as::<i32>(0).unwrap();
+  let (Geometry::LineString(parent), Geometry::LineString(linestring)) =
+    (&parent[0].geometry, &features[0].geometry)
+  else {
+    panic!("expected linestrings, got {:?}", features[0].geometry);
+  };
+  assert_eq!(linestring.0.first(), Some(&coord! { x: 0, y: 0 }));
+  assert_eq!(linestring.0.last(), Some(&coord! { x: 1600, y: 0 }));
+  assert!(linestring.0.contains(&coord! { x: 800, y: 800 }));
+  // the tolerance applies to the child, which needs more vertices for the same curve
+  assert!(linestring.0.len() > parent.0.len());
+}
+
+#[test]
+fn reject_invalid_splines() {
+  for knots in [&[0, 0, 2, 2][..], &[0, 0, 2, 1, 2, 2], &[]] {
+    let reader = Reader::new(spline_layer(vec![spline_feature(knots)])).unwrap();
+    assert_eq!(
+      reader.get_features(0).unwrap_err().kind(),
+      ErrorKind::Geometry
+    );
+    assert_eq!(
+      reader.get_splines::<f32>(0).unwrap_err().kind(),
+      ErrorKind::Geometry
+    );
+  }
+}
+
+#[test]
+fn flatten_large_splines() {
+  let count: usize = 50_000;
+  let degree = 3;
+  // a clamped uniform cubic spline along a zigzag
+  let control_points: LineString<f32> = (0..count)
+    .map(|index| (index as f32, (index % 2) as f32 * 100.0))
+    .collect::<Vec<_>>()
+    .into();
+  let spans = count - degree;
+  let knots: Vec<f64> = (0..count + degree + 1)
+    .map(|index| index.saturating_sub(degree).min(spans) as f64)
+    .collect();
+  let spline = Spline {
+    control_points,
+    knots,
+    degree: degree as u32,
+  };
+
+  let flattened = spline.flatten(1.0).unwrap();
+  assert!(flattened.0.len() > spans);
+  assert_eq!(flattened.0.first(), Some(&coord! { x: 0.0, y: 0.0 }));
+  assert_eq!(
+    flattened.0.last(),
+    Some(&coord! { x: (count - 1) as f64, y: 100.0 })
+  );
+  assert_eq!(
+    spline.evaluate(spans as f64),
+    Some(coord! { x: (count - 1) as f64, y: 100.0 })
+  );
+}
@@ -0,0 +1,57 @@
+mod support;
+
+use mvt_reader::{
+  Reader,
+  error::{ErrorKind, ParserError},
+};
+use support::{GeomType, TestFeature, TestLayer, fixture, points, tile};
+
+fn broken_tile() -> Vec<u8> {
+  tile(vec![
+    TestLayer::new(
+      "valid",
+      vec![TestFeature::new(
+        Some(1),
+        GeomType::Point,
+        points(&[(1, 1)]),
+      )],
+    ),
+    TestLayer::new(
+      "broken",
+      vec![
+        TestFeature::new(Some(1), GeomType::Point, points(&[(1, 1)])),
+        // a ClosePath command without a preceding MoveTo
+        TestFeature::new(Some(2), GeomType::Polygon, vec![15]),
+      ],
+    ),
+  ])
+}
+
+#[test]
+fn error_kind_and_context() {
+  let reader = Reader::new(broken_tile()).unwrap();
+  assert!(reader.get_features(0).is_ok());
+
+  let error = reader.get_features(1).unwrap_err();
+  assert_eq!(error.kind(), ErrorKind::Geometry);
+  assert_eq!(error.layer_index(), Some(1));
+  assert_eq!(error.feature_index(), Some(1));
+  assert!(error.to_string().ends_with("(layer 1, feature 1)"));
+}
+
+#[test]
+fn error_kind_decode() {
+  // fixture 013 has a key encoded as an integer
+  let error = Reader::new(fixture("013")).err().unwrap();
+  assert_eq!(error.kind(), ErrorKind::Decode);
+  assert!(error.to_string().contains("invalid wire type"));
+  assert_eq!(error.layer_index(), None);
+  assert_eq!(error.kind().as_str(), "decode");
+}
+
+#[test]
+fn error_kind_other() {
+  let error = ParserError::new(std::io::Error::other("custom"));
+  assert_eq!(error.kind(), ErrorKind::Other);
+  assert_eq!(error.to_string(), "custom");
+}
@@ -0,0 +1,197 @@
+#![cfg(feature = "filter")]
+
+mod support;
+
+use mvt_reader::{Reader, feature::Value, filter::Filter};
+use support::{GeomType, TestFeature, TestLayer, fixture, linestrings, points, rings, tile};
+
+fn roads_tile() -> Vec<u8> {
+  tile(vec![TestLayer::new(
+    "roads",
+    vec![
+      TestFeature::new(
+        Some(1),
+        GeomType::Linestring,
+        linestrings(&[&[(0, 0), (10, 10)]]),
+      )
+      .with("class", Value::String(String::from("primary")))
+      .with("lanes", Value::UInt(2)),
+      TestFeature::new(
+        Some(2),
+        GeomType::Linestring,
+        linestrings(&[&[(0, 0), (20, 0)]]),
+      )
+      .with("class", Value::String(String::from("secondary")))
+      .with("lanes", Value::Int(4)),
+      TestFeature::new(Some(3), GeomType::Point, points(&[(5, 5)]))
+        .with("class", Value::String(String::from("primary")))
+        .with("name", Value::String(String::from("Main Street"))),
+      TestFeature::new(
+        None,
+        GeomType::Polygon,
+        rings(&[&[(0, 0), (10, 0), (10, 10), (0, 10)]]),
+      )
+      .with("class", Value::String(String::from("park"))),
+    ],
+  )])
+}
+
+fn filtered_ids(filter: &str, zoom: f64) -> Vec<Option<u64>> {
+  let reader = Reader::new(roads_tile()).unwrap();
+  let filter = Filter::parse(filter).unwrap();
+  reader
+    .get_features_filtered(0, &filter, zoom)
+    .unwrap()
+    .iter()
+    .map(|feature| feature.id)
+    .collect()
+}
+
+#[test]
+fn filter_expression_syntax() {
+  assert_eq!(
+    filtered_ids(r#"["==", ["get", "class"], "primary"]"#, 14.0),
+    vec![Some(1), Some(3)]
+  );
+  assert_eq!(
+    filtered_ids(
+      r#"["all", ["==", ["geometry-type"], "LineString"], [">", ["get", "lanes"], 2]]"#,
+      14.0
+    ),
+    vec![Some(2)]
+  );
+  assert_eq!(
+    filtered_ids(
+      r#"["in", ["get", "class"], ["literal", ["park", "secondary"]]]"#,
+      14.0
+    ),
+    vec![Some(2), None]
+  );
+  assert_eq!(filtered_ids(r#"["has", "name"]"#, 14.0), vec![Some(3)]);
+  assert_eq!(
+    filtered_ids(r#"["all", ["==", ["id"], 1], [">=", ["zoom"], 10]]"#, 9.0),
+    vec![]
+  );
+  assert_eq!(
+    filtered_ids(
+      r#"["match", ["get", "class"], ["primary", "secondary"], false, true]"#,
+      14.0
+    ),
+    vec![None]
+  );
+}
+
+#[test]
+fn filter_legacy_syntax() {
+  assert_eq!(
+    filtered_ids(r#"["==", "class", "primary"]"#, 14.0),
+    vec![Some(1), Some(3)]
+  );
+  assert_eq!(
+    filtered_ids(r#"["in", "class", "park", "secondary"]"#, 14.0),
+    vec![Some(2), None]
+  );
+  assert_eq!(
+    filtered_ids(r#"["!in", "$type", "Point", "Polygon"]"#, 14.0),
+    vec![Some(1), Some(2)]
+  );
+  assert_eq!(
+    filtered_ids(
+      r#"["all", ["==", "$type", "LineString"], ["!has", "name"], [">=", "lanes", 4]]"#,
+      14.0
+    ),
+    vec![Some(2)]
+  );
+  assert_eq!(
+    filtered_ids(r#"["none", ["has", "$id"]]"#, 14.0),
+    vec![None]
+  );
+}
+
+#[test]
+fn filter_value_types_of_fixture() {
+  // fixture 038 has a single feature with id 1 and a property of every value type
+  let reader = Reader::new(fixture("038")).unwrap();
+  let accepted = |filter: &str| {
+    let filter = Filter::parse(filter).unwrap();
+    reader
+      .get_features_filtered(0, &filter, 14.0)
+      .unwrap()
+      .len()
+      == 1
+  };
+
+  assert!(accepted(r#"["==", ["get", "string_value"], "ello"]"#));
+  assert!(accepted(r#"["==", ["get", "bool_value"], true]"#));
+  assert!(accepted(r#"["==", ["get", "int_value"], 6]"#));
+  assert!(accepted(r#"["==", ["get", "int_value"], 6.0]"#));
+  assert!(accepted(r#"["==", ["get", "uint_value"], 87948]"#));
+  assert!(accepted(r#"["<", ["get", "sint_value"], -87947.5]"#));
+  assert!(accepted(r#"[">", ["get", "double_value"], 1.2]"#));
+  assert!(accepted(r#"["<", ["get", "float_value"], 3.1]"#));
+  assert!(accepted(r#"["==", ["id"], 1]"#));
+  assert!(!accepted(r#"["==", ["id"], 1.5]"#));
+}
+
+#[test]
+fn filter_large_ids_exactly() {
+  let reader = Reader::new(tile(vec![TestLayer::new(
+    "poi",
+    vec![
+      TestFeature::new(Some(1 << 53), GeomType::Point, points(&[(1, 1)])),
+      TestFeature::new(Some((1 << 53) + 1), GeomType::Point, points(&[(2, 2)])),
+      TestFeature::new(Some(u64::MAX), GeomType::Point, points(&[(3, 3)])),
+    ],
+  )]))
+  .unwrap();
+  let ids = |filter: &str| {
+    let filter = Filter::parse(filter).unwrap();
+    reader
+      .get_features_filtered(0, &filter, 14.0)
+      .unwrap()
+      .iter()
+      .map(|feature| feature.id.unwrap())
+      .collect::<Vec<_>>()
+  };
+
+  assert_eq!(
+    ids(r#"["==", ["id"], 9007199254740993]"#),
+    vec![(1 << 53) + 1]
+  );
+  assert_eq!(ids(r#"["==", ["id"], 9007199254740992]"#), vec![1 << 53]);
+  assert_eq!(
+    ids(r#"[">", ["id"], 9007199254740992]"#),
+    vec![(1 << 53) + 1, u64::MAX]
+  );
+  assert_eq!(
+    ids(r#"["==", ["id"], 18446744073709551615]"#),
+    vec![u64::MAX]
+  );
+  // a float literal that rounds to 2^53 is compared as the float
+  assert_eq!(ids(r#"["==", ["id"], 9007199254740992.0]"#), vec![1 << 53]);
+}
+
+#[test]
+fn filter_evaluate_decoded_feature() {
+  let reader = Reader::new(roads_tile()).unwrap();
+  let features = reader.get_features(0).unwrap();
+  let filter = Filter::parse(r#"["==", ["geometry-type"], "Polygon"]"#).unwrap();
+
+  let accepted: Vec<_> = features
+    .iter()
+    .filter(|feature| filter.evaluate(feature, 14.0))
+    .collect();
+  assert_eq!(accepted.len(), 1);
+  assert_eq!(
+    accepted[0].properties.as_ref().unwrap().get("class"),
+    Some(&Value::String(String::from("park")))
+  );
+}
+
+#[test]
+fn filter_invalid_expressions() {
+  assert!(Filter::parse("not json").is_err());
+  assert!(Filter::parse(r#"{"class": "primary"}"#).is_err());
+  assert!(Filter::parse(r#"["unknown-operator", 1]"#).is_err());
+  assert!(Filter::parse(r#"["==", ["get", "class"]]"#).is_err());
+}
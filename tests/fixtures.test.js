@@ -127,4 +127,18 @@ describe('Test fixture', () => {
     expect(reader.getLayerNames()).toBeNull()
     expect(reader.getFeatures(0)).toBeNull()
   })
+
+  test('013 throws without error callback', async () => {
+    let error
+    try {
+      new Reader(readFileSync('mvt-fixtures/fixtures/013/tile.mvt'))
+    } catch (e) {
+      error = e
+    }
+    expect(error).toBeInstanceOf(Error)
+    expect(error.name).toBe('ParserError')
+    expect(error.kind).toBe('decode')
+    expect(error.message).toContain('invalid wire type')
+    expect(error.layerIndex).toBeUndefined()
+  })
 })
\ No newline at end of file
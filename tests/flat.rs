@@ -0,0 +1,80 @@
+mod support;
+
+use mvt_reader::{Reader, feature::Value};
+use support::{GeomType, TestFeature, TestLayer, linestrings, points, rings, tile};
+
+fn reader() -> Reader {
+  Reader::new(tile(vec![TestLayer::new(
+    "mixed",
+    vec![
+      TestFeature::new(Some(1), GeomType::Point, points(&[(1, 2), (3, 4)]))
+        .with("name", Value::String(String::from("a"))),
+      TestFeature::new(
+        None,
+        GeomType::Linestring,
+        linestrings(&[&[(0, 0), (10, 0)], &[(0, 5), (10, 5), (10, 10)]]),
+      )
+      .with("lanes", Value::UInt(2)),
+      TestFeature::new(
+        Some(3),
+        GeomType::Polygon,
+        rings(&[
+          &[(0, 0), (100, 0), (100, 100), (0, 100)],
+          &[(40, 40), (40, 60), (60, 60), (60, 40)],
+          &[(200, 0), (300, 0), (300, 100), (200, 100)],
+        ]),
+      )
+      .with("name", Value::String(String::from("c"))),
+    ],
+  )]))
+  .unwrap()
+}
+
+#[test]
+fn flat_layer_geometry() {
+  let layer = reader().get_flat_layer(0).unwrap();
+  assert_eq!(layer.len(), 3);
+  assert_eq!(layer.geometry_types, vec![1, 2, 3]);
+  assert_eq!(layer.ids, vec![Some(1), None, Some(3)]);
+
+  // one part of points, two lines and two polygons with three rings in total
+  assert_eq!(layer.feature_offsets, vec![0, 1, 3, 5]);
+  assert_eq!(layer.part_offsets, vec![0, 1, 2, 3, 5, 6]);
+  assert_eq!(layer.ring_offsets, vec![0, 2, 4, 7, 12, 17, 22]);
+  assert_eq!(layer.coordinates.len(), 22 * 2);
+
+  assert_eq!(&layer.coordinates[..4], &[1, 2, 3, 4]);
+  // polygon rings keep their closing vertex
+  let ring = &layer.coordinates[7 * 2..12 * 2];
+  assert_eq!(&ring[..2], &ring[8..]);
+}
+
+#[test]
+fn flat_layer_properties() {
+  let layer = reader().get_flat_layer(0).unwrap();
+  let names: Vec<&str> = layer
+    .properties
+    .iter()
+    .map(|column| column.name.as_str())
+    .collect();
+  assert_eq!(names, vec!["name", "lanes"]);
+  assert_eq!(
+    layer.properties[0].values,
+    vec![
+      Some(Value::String(String::from("a"))),
+      None,
+      Some(Value::String(String::from("c")))
+    ]
+  );
+  assert_eq!(
+    layer.properties[1].values,
+    vec![None, Some(Value::UInt(2)), None]
+  );
+}
+
+#[test]
+fn flat_layer_missing() {
+  let layer = reader().get_flat_layer(1).unwrap();
+  assert!(layer.is_empty());
+  assert_eq!(layer.feature_offsets, vec![0]);
+}
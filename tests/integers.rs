@@ -0,0 +1,41 @@
+mod support;
+
+use mvt_reader::{Reader, feature::Value};
+use support::{GeomType, TestFeature, TestLayer, points, tile};
+
+const FIXTURE: &str = "tests/data/max-id.mvt";
+
+fn max_id_tile() -> Vec<u8> {
+  tile(vec![TestLayer::new(
+    "integers",
+    vec![
+      TestFeature::new(Some(u64::MAX), GeomType::Point, points(&[(1, 2)]))
+        .with("uint", Value::UInt(u64::MAX))
+        .with("sint", Value::SInt(i64::MIN))
+        .with("int", Value::Int(i64::MAX)),
+    ],
+  )])
+}
+
+#[test]
+fn fixture_matches_builder() {
+  if std::env::var_os("WRITE_FIXTURES").is_some() {
+    std::fs::write(FIXTURE, max_id_tile()).unwrap();
+  }
+  assert_eq!(std::fs::read(FIXTURE).unwrap(), max_id_tile());
+}
+
+#[test]
+fn read_max_id_exactly() {
+  let reader = Reader::new(std::fs::read(FIXTURE).unwrap()).unwrap();
+  let features = reader.get_features(0).unwrap();
+  assert_eq!(features.len(), 1);
+
+  let feature = &features[0];
+  assert_eq!(feature.id, Some(u64::MAX));
+
+  let properties = feature.properties.as_ref().unwrap();
+  assert_eq!(properties["uint"], Value::UInt(u64::MAX));
+  assert_eq!(properties["sint"], Value::SInt(i64::MIN));
+  assert_eq!(properties["int"], Value::Int(i64::MAX));
+}
@@ -0,0 +1,44 @@
+const { Reader } = require('mvt-reader')
+const { readFileSync } = require('fs')
+
+describe('64-bit integers', () => {
+  test('max-id round-trips as BigInt', async () => {
+    const reader = new Reader(readFileSync('tests/data/max-id.mvt'))
+    reader.setIntegerMode('bigint')
+    const features = reader.getFeatures(0)
+
+    expect(features[0].id).toBe(18446744073709551615n)
+    expect(features[0].properties.uint).toBe(18446744073709551615n)
+    expect(features[0].properties.sint).toBe(-9223372036854775808n)
+    expect(features[0].properties.int).toBe(9223372036854775807n)
+
+    const layer = reader.getFlatLayer(0)
+    expect(layer.ids).toEqual([18446744073709551615n])
+  })
+
+  test('max-id round-trips as string', async () => {
+    const reader = new Reader(readFileSync('tests/data/max-id.mvt'))
+    reader.setIntegerMode('string')
+    const features = reader.getFeatures(0)
+
+    expect(features[0].id).toBe('18446744073709551615')
+    expect(features[0].properties.uint).toBe('18446744073709551615')
+    expect(features[0].properties.sint).toBe('-9223372036854775808')
+
+    const layer = reader.getFlatLayer(0)
+    expect(layer.ids).toEqual(['18446744073709551615'])
+  })
+
+  test('max-id loses precision as number', async () => {
+    const reader = new Reader(readFileSync('tests/data/max-id.mvt'))
+    const features = reader.getFeatures(0)
+
+    expect(features[0].id).toBe(18446744073709551615)
+    expect(typeof features[0].properties.uint).toBe('number')
+  })
+
+  test('unknown integer mode throws', async () => {
+    const reader = new Reader(readFileSync('tests/data/max-id.mvt'))
+    expect(() => reader.setIntegerMode('float')).toThrow(TypeError)
+  })
+})
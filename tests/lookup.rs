@@ -0,0 +1,71 @@
+mod support;
+
+use mvt_reader::{Reader, feature::Value};
+use support::{GeomType, TestFeature, TestLayer, points, tile};
+
+fn ids_tile() -> Vec<u8> {
+  tile(vec![
+    TestLayer::new(
+      "poi",
+      vec![
+        TestFeature::new(Some(7), GeomType::Point, points(&[(1, 1)]))
+          .with("name", Value::String(String::from("first"))),
+        TestFeature::new(None, GeomType::Point, points(&[(2, 2)]))
+          .with("name", Value::String(String::from("anonymous"))),
+        TestFeature::new(Some(9), GeomType::Point, points(&[(3, 3)]))
+          .with("name", Value::String(String::from("unique"))),
+        TestFeature::new(Some(7), GeomType::Point, points(&[(4, 4)]))
+          .with("name", Value::String(String::from("duplicate"))),
+      ],
+    ),
+    TestLayer::new(
+      "other",
+      vec![TestFeature::new(
+        Some(9),
+        GeomType::Point,
+        points(&[(5, 5)]),
+      )],
+    ),
+  ])
+}
+
+fn name(feature: &mvt_reader::feature::Feature) -> Option<&Value> {
+  feature.properties.as_ref().unwrap().get("name")
+}
+
+#[test]
+fn feature_by_id_returns_first_match() {
+  let reader = Reader::new(ids_tile()).unwrap();
+
+  let feature = reader.feature_by_id(0, 9).unwrap().unwrap();
+  assert_eq!(name(&feature), Some(&Value::String(String::from("unique"))));
+
+  let feature = reader.feature_by_id(0, 7).unwrap().unwrap();
+  assert_eq!(name(&feature), Some(&Value::String(String::from("first"))));
+
+  let feature = reader.feature_by_id(1, 9).unwrap().unwrap();
+  assert_eq!(feature.properties.unwrap().len(), 0);
+}
+
+#[test]
+fn feature_by_id_missing() {
+  let reader = Reader::new(ids_tile()).unwrap();
+
+  assert!(reader.feature_by_id(0, 0).unwrap().is_none());
+  assert!(reader.feature_by_id(1, 7).unwrap().is_none());
+  assert!(reader.feature_by_id(5, 7).unwrap().is_none());
+  assert!(reader.features_by_id(5, 7).unwrap().is_empty());
+}
+
+#[test]
+fn features_by_id_returns_duplicates() {
+  let reader = Reader::new(ids_tile()).unwrap();
+
+  assert_eq!(reader.feature_indices_by_id(0, 7), &[0, 3]);
+  let features = reader.features_by_id(0, 7).unwrap();
+  assert_eq!(features.len(), 2);
+  assert_eq!(
+    name(&features[1]),
+    Some(&Value::String(String::from("duplicate")))
+  );
+}
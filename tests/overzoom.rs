@@ -0,0 +1,217 @@
+mod support;
+
+use geo_types::{Geometry, LineString, MultiPoint};
+use mvt_reader::{Reader, feature::Value, stitch::TileCoordinates};
+use support::{GeomType, TestFeature, TestLayer, linestrings, points, rings, tile};
+
+fn parent() -> Reader {
+  Reader::new(tile(vec![TestLayer::new(
+    "roads",
+    vec![
+      TestFeature::new(
+        Some(1),
+        GeomType::Point,
+        points(&[(3000, 1000), (3500, 1500), (100, 100)]),
+      ),
+      TestFeature::new(
+        Some(2),
+        GeomType::Linestring,
+        linestrings(&[&[(1000, 500), (3000, 500)]]),
+      )
+      .with("name", Value::String(String::from("Main Street"))),
+      TestFeature::new(
+        Some(3),
+        GeomType::Polygon,
+        rings(&[&[(2000, 1000), (3000, 1000), (3000, 1500), (2000, 1500)]]),
+      ),
+      TestFeature::new(Some(4), GeomType::Point, points(&[(100, 3000)])),
+    ],
+  )]))
+  .unwrap()
+}
+
+/// Maps a parent coordinate into the child tile `z/x/y` below the parent tile `0/0/0`.
+fn expected(z: u8, x: i32, y: i32, coord: (i32, i32)) -> (i32, i32) {
+  let scale = 1 << z;
+  (coord.0 * scale - x * 4096, coord.1 * scale - y * 4096)
+}
+
+fn sorted_vertices(ring: &LineString<i32>) -> Vec<(i32, i32)> {
+  let mut vertices: Vec<(i32, i32)> = ring.0.iter().map(|c| (c.x, c.y)).collect();
+  vertices.sort_unstable();
+  vertices.dedup();
+  vertices
+}
+
+#[test]
+fn overzoom_one_level() {
+  let child = parent()
+    .overzoom(
+      TileCoordinates::new(0, 0, 0),
+      TileCoordinates::new(1, 1, 0),
+      64,
+    )
+    .unwrap();
+  let features = child.get_features_as::<i32>(0).unwrap();
+  assert_eq!(
+    features
+      .iter()
+      .map(|feature| feature.id)
+      .collect::<Vec<_>>(),
+    vec![Some(1), Some(2), Some(3)]
+  );
+
+  assert_eq!(
+    features[0].geometry,
+    Geometry::MultiPoint(MultiPoint::from(vec![
+      expected(1, 1, 0, (3000, 1000)),
+      expected(1, 1, 0, (3500, 1500)),
+    ]))
+  );
+
+  // the line starts outside of the buffer and is cut at its edge
+  assert_eq!(
+    features[1].geometry,
+    Geometry::LineString(LineString::from(vec![
+      (-64, 1000),
+      expected(1, 1, 0, (3000, 500))
+    ]))
+  );
+  assert_eq!(
+    features[1].properties.as_ref().unwrap().get("name"),
+    Some(&Value::String(String::from("Main Street")))
+  );
+
+  let Geometry::MultiPolygon(polygons) = &features[2].geometry else {
+    panic!("Expected a multi polygon, found {:?}", features[2].geometry);
+  };
+  assert_eq!(polygons.0.len(), 1);
+  assert_eq!(
+    sorted_vertices(polygons.0[0].exterior()),
+    vec![(-64, 2000), (-64, 3000), (1904, 2000), (1904, 3000)]
+  );
+}
+
+#[test]
+fn overzoom_several_levels() {
+  let child = parent()
+    .overzoom(
+      TileCoordinates::new(0, 0, 0),
+      TileCoordinates::new(2, 3, 1),
+      0,
+    )
+    .unwrap();
+  let features = child.get_features_as::<i32>(0).unwrap();
+  assert_eq!(features.len(), 1);
+  assert_eq!(
+    features[0].geometry,
+    Geometry::MultiPoint(MultiPoint::from(vec![expected(2, 3, 1, (3500, 1500))]))
+  );
+}
+
+#[test]
+fn overzoom_round_trip() {
+  let child = parent()
+    .overzoom(
+      TileCoordinates::new(0, 0, 0),
+      TileCoordinates::new(1, 1, 0),
+      64,
+    )
+    .unwrap();
+  let decoded = Reader::new(child.to_bytes()).unwrap();
+  assert_eq!(decoded.get_layer_names().unwrap(), vec!["roads"]);
+  let (decoded, child) = (
+    decoded.get_features(0).unwrap(),
+    child.get_features(0).unwrap(),
+  );
+  assert_eq!(decoded.len(), child.len());
+  for (decoded, child) in decoded.iter().zip(&child) {
+    assert_eq!(decoded.id, child.id);
+    assert_eq!(decoded.geometry, child.geometry);
+    assert_eq!(decoded.properties, child.properties);
+  }
+}
+
+#[test]
+fn overzoom_rejects_non_descendant() {
+  let reader = parent();
+  assert!(
+    reader
+      .overzoom(
+        TileCoordinates::new(1, 0, 0),
+        TileCoordinates::new(2, 2, 0),
+        0
+      )
+      .is_err()
+  );
+  assert!(
+    reader
+      .overzoom(
+        TileCoordinates::new(2, 0, 0),
+        TileCoordinates::new(1, 0, 0),
+        0
+      )
+      .is_err()
+  );
+}
+
+#[test]
+fn overzoom_keeps_direction_of_long_segments() {
+  // both ends of the line are far outside of the 32-bit range of the child
+  let reader = Reader::new(tile(vec![TestLayer::new(
+    "roads",
+    vec![TestFeature::new(
+      Some(1),
+      GeomType::Linestring,
+      linestrings(&[&[(0, 0), (4096, 1024)]]),
+    )],
+  )]))
+  .unwrap();
+  let child = reader
+    .overzoom(
+      TileCoordinates::new(0, 0, 0),
+      TileCoordinates::new(24, 1 << 23, 1 << 21),
+      0,
+    )
+    .unwrap();
+  let features = child.get_features_as::<i32>(0).unwrap();
+  assert_eq!(
+    features[0].geometry,
+    Geometry::LineString(LineString::from(vec![(0, 0), (4096, 1024)]))
+  );
+}
+
+#[test]
+fn overzoom_large_extent() {
+  let mut layer = TestLayer::new(
+    "roads",
+    vec![TestFeature::new(
+      Some(1),
+      GeomType::Linestring,
+      linestrings(&[&[(i32::MIN + 1, 0), (0, 0), (i32::MAX, 0)]]),
+    )],
+  );
+  layer.extent = u32::MAX;
+  let reader = Reader::new(tile(vec![layer])).unwrap();
+
+  let child = reader
+    .overzoom(
+      TileCoordinates::new(0, 0, 0),
+      TileCoordinates::new(31, u32::MAX >> 1, 0),
+      0,
+    )
+    .unwrap();
+  assert!(child.get_features(0).unwrap().is_empty());
+
+  let child = reader
+    .overzoom(
+      TileCoordinates::new(0, 0, 0),
+      TileCoordinates::new(31, 1 << 29, 0),
+      0,
+    )
+    .unwrap();
+  assert_eq!(
+    child.get_features_as::<i32>(0).unwrap()[0].geometry,
+    Geometry::LineString(LineString::from(vec![(0, 0), (i32::MAX, 0)]))
+  );
+}
@@ -0,0 +1,163 @@
+mod support;
+
+use std::sync::Arc;
+
+use mvt_reader::{Reader, feature::Value};
+use support::{GeomType, TestFeature, TestLayer, command, points, tile};
+
+fn poi_tile() -> Vec<u8> {
+  tile(vec![TestLayer::new(
+    "poi",
+    vec![
+      TestFeature::new(Some(1), GeomType::Point, points(&[(1, 1)]))
+        .with("class", Value::String(String::from("cafe")))
+        .with("rank", Value::UInt(3)),
+      TestFeature::new(Some(2), GeomType::Point, points(&[(2, 2)]))
+        .with("class", Value::String(String::from("shop")))
+        .with("rank", Value::UInt(1)),
+      // a MoveTo without parameters cannot be decoded
+      TestFeature::new(Some(3), GeomType::Polygon, vec![command(1, 1)])
+        .with("class", Value::String(String::from("broken"))),
+    ],
+  )])
+}
+
+#[test]
+fn predicate_skips_rejected_features() {
+  let reader = Reader::new(poi_tile()).unwrap();
+
+  let features = reader
+    .get_features_where(0, |props| props.get_str("class") == Some("cafe"))
+    .unwrap();
+  assert_eq!(features.len(), 1);
+  assert_eq!(features[0].id, Some(1));
+  assert_eq!(
+    features[0].properties.as_ref().unwrap().get("rank"),
+    Some(&Value::UInt(3))
+  );
+
+  // the broken geometry is never decoded
+  let features = reader
+    .get_features_as_where::<i32, _>(0, |props| props.get("rank").is_some())
+    .unwrap();
+  assert_eq!(features.len(), 2);
+  assert!(reader.get_features(0).is_err());
+}
+
+#[test]
+fn property_view_accessors() {
+  let reader = Reader::new(poi_tile()).unwrap();
+  let mut seen = Vec::new();
+
+  reader
+    .get_features_where(0, |props| {
+      seen.push((
+        props.len(),
+        props.contains_key("rank"),
+        props
+          .iter()
+          .map(|(key, _)| key.to_string())
+          .collect::<Vec<_>>(),
+      ));
+      false
+    })
+    .unwrap();
+
+  assert_eq!(
+    seen,
+    vec![
+      (2, true, vec![String::from("class"), String::from("rank")]),
+      (2, true, vec![String::from("class"), String::from("rank")]),
+      (1, false, vec![String::from("class")]),
+    ]
+  );
+}
+
+fn duplicate_tile() -> Vec<u8> {
+  tile(vec![TestLayer::new(
+    "roads",
+    vec![
+      TestFeature::new(Some(1), GeomType::Point, points(&[(1, 1)]))
+        .with("name", Value::String(String::from("first")))
+        .with("ref", Value::UInt(7))
+        .with("name", Value::String(String::from("second"))),
+    ],
+  )])
+}
+
+#[test]
+fn ordered_properties_keep_duplicates() {
+  let reader = Reader::new(duplicate_tile()).unwrap();
+
+  let features = reader.get_features_ordered::<f32>(0).unwrap();
+  assert_eq!(
+    features[0].properties,
+    Some(vec![
+      (String::from("name"), Value::String(String::from("first"))),
+      (String::from("ref"), Value::UInt(7)),
+      (String::from("name"), Value::String(String::from("second"))),
+    ])
+  );
+  assert_eq!(features[0].duplicate_keys(), vec!["name"]);
+
+  // the map keeps the last value
+  assert_eq!(
+    reader.get_features(0).unwrap()[0]
+      .properties
+      .as_ref()
+      .unwrap()["name"],
+    Value::String(String::from("second"))
+  );
+}
+
+#[test]
+fn property_view_duplicate_keys() {
+  let reader = Reader::new(duplicate_tile()).unwrap();
+  let mut duplicates = Vec::new();
+
+  reader
+    .get_features_where(0, |props| {
+      duplicates.extend(props.duplicate_keys().into_iter().map(String::from));
+      true
+    })
+    .unwrap();
+  assert_eq!(duplicates, vec![String::from("name")]);
+}
+
+#[test]
+fn shared_properties_reference_layer_table() {
+  let reader = Reader::new(poi_tile()).unwrap();
+  let features = reader.get_features_shared::<f32>(0);
+  // the broken geometry fails like for owned properties
+  assert!(features.is_err());
+
+  let reader = Reader::new(duplicate_tile()).unwrap();
+  let features = reader.get_features_shared::<f32>(0).unwrap();
+  let properties = features[0].properties.as_ref().unwrap();
+
+  assert_eq!(properties.len(), 3);
+  assert_eq!(
+    properties.get("name"),
+    Some(&Value::String(String::from("second")))
+  );
+  assert!(properties.contains_key("ref"));
+  assert!(!properties.contains_key("missing"));
+  assert_eq!(
+    properties.iter().map(|(key, _)| key).collect::<Vec<_>>(),
+    vec!["name", "ref", "name"]
+  );
+  assert_eq!(properties.table().keys().len(), 2);
+
+  // every feature shares the table decoded on first use
+  let again = reader.get_features_shared::<f32>(0).unwrap();
+  assert!(Arc::ptr_eq(
+    properties.table(),
+    again[0].properties.as_ref().unwrap().table()
+  ));
+
+  let owned = features[0].clone().into_owned();
+  assert_eq!(
+    owned.properties,
+    reader.get_features(0).unwrap()[0].properties
+  );
+}
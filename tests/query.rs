@@ -0,0 +1,173 @@
+mod support;
+
+use geo_types::Rect;
+use mvt_reader::Reader;
+use support::{GeomType, TestFeature, TestLayer, linestrings, points, rings, tile};
+
+fn query_tile() -> Vec<u8> {
+  tile(vec![
+    TestLayer::new(
+      "landuse",
+      vec![TestFeature::new(
+        Some(1),
+        GeomType::Polygon,
+        rings(&[
+          &[(0, 0), (100, 0), (100, 100), (0, 100)],
+          &[(40, 40), (40, 60), (60, 60), (60, 40)],
+        ]),
+      )],
+    ),
+    TestLayer::new(
+      "roads",
+      vec![TestFeature::new(
+        Some(2),
+        GeomType::Linestring,
+        linestrings(&[&[(0, 120), (200, 120)]]),
+      )],
+    ),
+    TestLayer::new(
+      "poi",
+      vec![
+        TestFeature::new(Some(3), GeomType::Point, points(&[(20, 20)])),
+        TestFeature::new(Some(4), GeomType::Point, points(&[(90, 115)])),
+      ],
+    ),
+    TestLayer::new(
+      "buildings",
+      vec![
+        // an L shape with a notch in its upper right corner
+        TestFeature::new(
+          Some(5),
+          GeomType::Polygon,
+          rings(&[&[
+            (200, 0),
+            (300, 0),
+            (300, 50),
+            (250, 50),
+            (250, 100),
+            (200, 100),
+          ]]),
+        ),
+        TestFeature::new(
+          Some(6),
+          GeomType::Linestring,
+          linestrings(&[&[(400, 0), (500, 100)]]),
+        ),
+      ],
+    ),
+  ])
+}
+
+fn matched_ids(
+  reader: &Reader,
+  layer_index: Option<usize>,
+  x: f64,
+  y: f64,
+  tolerance: f64,
+) -> Vec<u64> {
+  reader
+    .query_point(layer_index, x, y, tolerance)
+    .unwrap()
+    .iter()
+    .map(|result| result.feature.id.unwrap())
+    .collect()
+}
+
+#[test]
+fn query_point_in_polygon_respects_holes() {
+  let reader = Reader::new(query_tile()).unwrap();
+
+  assert_eq!(matched_ids(&reader, Some(0), 10.0, 80.0, 0.0), vec![1]);
+  assert!(matched_ids(&reader, Some(0), 50.0, 50.0, 0.0).is_empty());
+  // the hole outline is within tolerance
+  assert_eq!(matched_ids(&reader, Some(0), 50.0, 50.0, 10.0), vec![1]);
+  assert!(matched_ids(&reader, Some(0), 150.0, 50.0, 10.0).is_empty());
+}
+
+#[test]
+fn query_point_lines_and_points() {
+  let reader = Reader::new(query_tile()).unwrap();
+
+  assert_eq!(matched_ids(&reader, Some(1), 150.0, 123.0, 5.0), vec![2]);
+  assert!(matched_ids(&reader, Some(1), 150.0, 130.0, 5.0).is_empty());
+  assert_eq!(matched_ids(&reader, Some(2), 23.0, 24.0, 5.0), vec![3]);
+  assert!(matched_ids(&reader, Some(2), 23.0, 24.0, 4.0).is_empty());
+  assert!(matched_ids(&reader, Some(5), 23.0, 24.0, 4.0).is_empty());
+  assert!(matched_ids(&reader, Some(usize::MAX), 23.0, 24.0, 4.0).is_empty());
+}
+
+#[test]
+fn query_point_all_layers_sorted_by_distance() {
+  let reader = Reader::new(query_tile()).unwrap();
+
+  let results = reader.query_point(None, 90.0, 112.0, 15.0).unwrap();
+  let ids: Vec<_> = results
+    .iter()
+    .map(|result| result.feature.id.unwrap())
+    .collect();
+  assert_eq!(ids, vec![4, 2, 1]);
+  assert_eq!(results[0].layer_index, 2);
+  assert_eq!(results[0].feature_index, 1);
+  assert_eq!(results[1].distance, 8.0);
+  assert_eq!(results[2].distance, 12.0);
+}
+
+fn bbox_ids(reader: &Reader, layer_index: usize, min: (f64, f64), max: (f64, f64)) -> Vec<u64> {
+  reader
+    .query_bbox(layer_index, Rect::new(min, max))
+    .unwrap()
+    .iter()
+    .map(|feature| feature.id.unwrap())
+    .collect()
+}
+
+#[test]
+fn query_bbox_intersecting_features() {
+  let reader = Reader::new(query_tile()).unwrap();
+
+  assert_eq!(bbox_ids(&reader, 2, (0.0, 0.0), (100.0, 120.0)), vec![3, 4]);
+  assert_eq!(bbox_ids(&reader, 2, (85.0, 110.0), (95.0, 120.0)), vec![4]);
+  assert_eq!(bbox_ids(&reader, 1, (50.0, 100.0), (60.0, 130.0)), vec![2]);
+  assert!(bbox_ids(&reader, 1, (50.0, 100.0), (60.0, 110.0)).is_empty());
+  assert!(bbox_ids(&reader, 7, (0.0, 0.0), (4096.0, 4096.0)).is_empty());
+}
+
+#[test]
+fn query_bbox_polygons() {
+  let reader = Reader::new(query_tile()).unwrap();
+
+  // crosses the outline
+  assert_eq!(bbox_ids(&reader, 0, (90.0, 90.0), (110.0, 110.0)), vec![1]);
+  // completely inside the polygon
+  assert_eq!(bbox_ids(&reader, 0, (10.0, 10.0), (20.0, 20.0)), vec![1]);
+  // completely inside the hole
+  assert!(bbox_ids(&reader, 0, (45.0, 45.0), (55.0, 55.0)).is_empty());
+  // outside of the envelope
+  assert!(bbox_ids(&reader, 0, (101.0, 0.0), (110.0, 10.0)).is_empty());
+  // inside the envelope, but in the notch outside of the polygon
+  assert!(bbox_ids(&reader, 3, (260.0, 60.0), (290.0, 90.0)).is_empty());
+  assert_eq!(bbox_ids(&reader, 3, (240.0, 60.0), (260.0, 70.0)), vec![5]);
+}
+
+#[test]
+fn query_bbox_lines() {
+  let reader = Reader::new(query_tile()).unwrap();
+
+  // inside the envelope of the diagonal, but below the segment
+  assert!(bbox_ids(&reader, 3, (460.0, 0.0), (500.0, 30.0)).is_empty());
+  assert_eq!(bbox_ids(&reader, 3, (440.0, 30.0), (460.0, 50.0)), vec![6]);
+}
+
+#[test]
+fn feature_bbox() {
+  let reader = Reader::new(query_tile()).unwrap();
+
+  let features = reader.get_features_as::<i32>(0).unwrap();
+  let bbox = features[0].bbox().unwrap();
+  assert_eq!((bbox.min().x, bbox.min().y), (0, 0));
+  assert_eq!((bbox.max().x, bbox.max().y), (100, 100));
+
+  let features = reader.get_features(2).unwrap();
+  let bbox = features[1].bbox().unwrap();
+  assert_eq!(bbox.min(), bbox.max());
+}
@@ -0,0 +1,135 @@
+mod support;
+
+use std::collections::BTreeMap;
+
+use mvt_reader::{
+  Reader,
+  feature::Value,
+  schema::{TileSchema, ValueKind},
+};
+use support::{GeomType, TestFeature, TestLayer, points, tile};
+
+fn poi(class: &'static str, rank: Value) -> TestFeature {
+  TestFeature::new(None, GeomType::Point, points(&[(1, 1)]))
+    .with("class", Value::String(String::from(class)))
+    .with("rank", rank)
+}
+
+fn poi_tile() -> Vec<u8> {
+  tile(vec![
+    TestLayer::new(
+      "poi",
+      vec![
+        poi("cafe", Value::UInt(3)),
+        poi("shop", Value::Double(-1.5)),
+        poi("cafe", Value::Null),
+        poi("bar", Value::String(String::from("high"))).with("open", Value::Bool(true)),
+      ],
+    ),
+    TestLayer::new("water", vec![]),
+  ])
+}
+
+#[test]
+fn infer_schema_of_layers() {
+  let schema = Reader::new(poi_tile())
+    .unwrap()
+    .infer_schema_with_samples(2)
+    .unwrap();
+
+  assert_eq!(schema.layers.len(), 2);
+  let poi = schema.layer("poi").unwrap();
+  assert_eq!(poi.feature_count, 4);
+  assert_eq!(
+    poi.fields.keys().collect::<Vec<_>>(),
+    vec!["class", "open", "rank"]
+  );
+
+  let class = &poi.fields["class"];
+  assert_eq!(class.count, 4);
+  assert_eq!(class.kinds, BTreeMap::from([(ValueKind::String, 4)]));
+  assert_eq!(class.samples, vec!["cafe", "shop"]);
+  assert_eq!(class.min, None);
+  assert_eq!(class.field_type(), "String");
+
+  let rank = &poi.fields["rank"];
+  assert_eq!(rank.null_count(), 1);
+  assert_eq!(poi.null_ratio("rank"), 0.25);
+  assert_eq!(rank.min, Some(-1.5));
+  assert_eq!(rank.max, Some(3.0));
+  assert_eq!(rank.samples, vec!["high"]);
+  assert_eq!(rank.field_type(), "Mixed");
+
+  assert_eq!(poi.fields["open"].field_type(), "Boolean");
+  assert!(schema.layer("water").unwrap().fields.is_empty());
+}
+
+#[test]
+fn merge_schemas() {
+  let reader = Reader::new(poi_tile()).unwrap();
+  let other = Reader::new(tile(vec![TestLayer::new(
+    "poi",
+    vec![poi("hotel", Value::SInt(-7))],
+  )]))
+  .unwrap();
+
+  let mut schema = TileSchema::new(3);
+  schema.merge(&reader.infer_schema().unwrap());
+  schema.merge(&other.infer_schema().unwrap());
+
+  let poi = schema.layer("poi").unwrap();
+  assert_eq!(poi.feature_count, 5);
+  assert_eq!(poi.fields["class"].samples, vec!["cafe", "shop", "bar"]);
+  let rank = &poi.fields["rank"];
+  assert_eq!(rank.count, 5);
+  assert_eq!(rank.kinds[&ValueKind::SInt], 1);
+  assert_eq!(rank.min, Some(-7.0));
+  assert_eq!(rank.max, Some(3.0));
+  assert_eq!(poi.fields["open"].count, 1);
+}
+
+#[test]
+fn missing_keys_count_as_null() {
+  let reader = Reader::new(poi_tile()).unwrap();
+  let other = Reader::new(tile(vec![TestLayer::new(
+    "poi",
+    vec![TestFeature::new(None, GeomType::Point, points(&[(1, 1)]))],
+  )]))
+  .unwrap();
+
+  let schema = reader.infer_schema().unwrap();
+  let poi = schema.layer("poi").unwrap();
+  // only the bar has the key
+  assert_eq!(poi.fields["open"].null_count(), 0);
+  assert_eq!(poi.missing_count("open"), 3);
+  assert_eq!(poi.null_ratio("open"), 0.75);
+  assert_eq!(poi.missing_count("name"), 4);
+  assert_eq!(poi.null_ratio("name"), 1.0);
+  assert_eq!(schema.layer("water").unwrap().null_ratio("name"), 0.0);
+
+  let mut schema = TileSchema::default();
+  schema.merge(&reader.infer_schema().unwrap());
+  schema.merge(&other.infer_schema().unwrap());
+  let poi = schema.layer("poi").unwrap();
+  assert_eq!(poi.missing_count("rank"), 1);
+  // one explicit null and one feature without the key
+  assert_eq!(poi.null_ratio("rank"), 0.4);
+  assert_eq!(poi.null_ratio("open"), 0.8);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serialize_as_vector_layers() {
+  let schema = Reader::new(poi_tile()).unwrap().infer_schema().unwrap();
+
+  assert_eq!(
+    serde_json::to_value(&schema).unwrap(),
+    serde_json::json!([
+      {
+        "id": "poi",
+        "fields": { "class": "String", "open": "Boolean", "rank": "Mixed" }
+      },
+      { "id": "water", "fields": {} }
+    ])
+  );
+}
@@ -0,0 +1,215 @@
+#![cfg(feature = "serde")]
+
+mod support;
+
+use std::collections::BTreeMap;
+
+use geo_types::{Geometry, MultiPoint, Point};
+use mvt_reader::{
+  Reader,
+  de::{from_properties, from_value},
+  error::ErrorKind,
+  feature::Value,
+};
+use serde::Deserialize;
+use support::{GeomType, TestFeature, TestLayer, fixture, points, tile};
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Road {
+  class: String,
+  lanes: Option<u8>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum Surface {
+  Paved,
+  Unpaved,
+}
+
+fn road_tile(lanes: Value) -> Vec<u8> {
+  tile(vec![TestLayer::new(
+    "roads",
+    vec![
+      TestFeature::new(Some(1), GeomType::Point, points(&[(1, 2)]))
+        .with("class", Value::String(String::from("primary")))
+        .with("lanes", Value::UInt(2))
+        .with("oneway", Value::Bool(true)),
+      TestFeature::new(Some(7), GeomType::Point, points(&[(3, 4)]))
+        .with("class", Value::String(String::from("service")))
+        .with("lanes", lanes),
+    ],
+  )])
+}
+
+#[test]
+fn deserialize_features_of_layer() {
+  let reader = Reader::new(road_tile(Value::Null)).unwrap();
+
+  let roads = reader.deserialize_features::<Road>(0).unwrap();
+  assert_eq!(
+    roads,
+    vec![
+      (
+        Geometry::MultiPoint(MultiPoint::new(vec![Point::new(1.0, 2.0)])),
+        Road {
+          class: String::from("primary"),
+          lanes: Some(2),
+        }
+      ),
+      (
+        Geometry::MultiPoint(MultiPoint::new(vec![Point::new(3.0, 4.0)])),
+        Road {
+          class: String::from("service"),
+          lanes: None,
+        }
+      ),
+    ]
+  );
+  assert!(reader.deserialize_features::<Road>(1).unwrap().is_empty());
+}
+
+#[test]
+fn deserialize_value_types_of_fixture() {
+  #[derive(Debug, Deserialize, PartialEq)]
+  struct ValueTypes {
+    string_value: String,
+    bool_value: bool,
+    int_value: i8,
+    uint_value: u32,
+    sint_value: i32,
+    double_value: f64,
+    float_value: f32,
+  }
+
+  // fixture 038 has a single feature with a property of every value type
+  let reader = Reader::new(fixture("038")).unwrap();
+  let features = reader.deserialize_features::<ValueTypes>(0).unwrap();
+  assert_eq!(
+    features,
+    vec![(
+      Geometry::MultiPoint(MultiPoint::new(vec![Point::new(25.0, 17.0)])),
+      ValueTypes {
+        string_value: String::from("ello"),
+        bool_value: true,
+        int_value: 6,
+        uint_value: 87948,
+        sint_value: -87948,
+        double_value: 1.23,
+        float_value: 3.1,
+      }
+    )]
+  );
+}
+
+#[test]
+fn mismatch_names_layer_feature_and_property() {
+  let reader = Reader::new(road_tile(Value::UInt(300))).unwrap();
+
+  let error = reader.deserialize_features::<Road>(0).unwrap_err();
+  assert_eq!(error.kind(), ErrorKind::Deserialize);
+  assert_eq!(error.layer_index(), Some(0));
+  assert_eq!(error.feature_index(), Some(1));
+  let message = error.to_string();
+  assert!(
+    message.contains("feature 7 in layer `roads`"),
+    "{}",
+    message
+  );
+  assert!(message.contains("property `lanes`"), "{}", message);
+  assert!(message.contains("300"), "{}", message);
+
+  #[derive(Debug, Deserialize)]
+  #[allow(dead_code)]
+  struct Bridge {
+    bridge: bool,
+  }
+  let error = reader.deserialize_features::<Bridge>(0).unwrap_err();
+  assert!(error.to_string().contains("missing field `bridge`"));
+}
+
+#[test]
+fn deserialize_values() {
+  assert_eq!(from_value::<f64>(Value::SInt(-3)).unwrap(), -3.0);
+  assert_eq!(from_value::<f32>(Value::Double(0.5)).unwrap(), 0.5);
+  assert_eq!(from_value::<Option<String>>(Value::Null).unwrap(), None);
+  assert_eq!(
+    from_value::<Surface>(Value::String(String::from("paved"))).unwrap(),
+    Surface::Paved
+  );
+  assert!(from_value::<Surface>(Value::String(String::from("gravel"))).is_err());
+  assert!(from_value::<Surface>(Value::UInt(1)).is_err());
+  assert!(from_value::<u8>(Value::Int(-1)).is_err());
+  assert_eq!(
+    from_value::<Vec<u32>>(Value::List(vec![Value::UInt(1), Value::Int(2)])).unwrap(),
+    vec![1, 2]
+  );
+  assert_eq!(
+    from_value::<BTreeMap<String, bool>>(Value::Map(BTreeMap::from([(
+      String::from("lit"),
+      Value::Bool(true)
+    )])))
+    .unwrap(),
+    BTreeMap::from([(String::from("lit"), true)])
+  );
+
+  #[derive(Debug, Deserialize, PartialEq)]
+  struct Tagged {
+    surface: Surface,
+    #[serde(default)]
+    width: f64,
+  }
+  let tagged: Tagged = from_properties(vec![(
+    String::from("surface"),
+    Value::String(String::from("unpaved")),
+  )])
+  .unwrap();
+  assert_eq!(
+    tagged,
+    Tagged {
+      surface: Surface::Unpaved,
+      width: 0.0,
+    }
+  );
+}
+
+#[test]
+fn serialize_features_and_layers() {
+  let reader = Reader::new(road_tile(Value::Null)).unwrap();
+
+  let layers = serde_json::to_value(reader.get_layer_metadata().unwrap()).unwrap();
+  assert_eq!(
+    layers,
+    serde_json::json!([{
+      "layer_index": 0,
+      "version": 2,
+      "name": "roads",
+      "feature_count": 2,
+      "extent": 4096,
+    }])
+  );
+
+  let features = reader.get_features(0).unwrap();
+  let feature = serde_json::to_value(&features[1]).unwrap();
+  assert_eq!(feature["id"], serde_json::json!(7));
+  assert_eq!(
+    feature["properties"],
+    serde_json::json!({ "class": "service", "lanes": null })
+  );
+  assert!(feature["geometry"].is_object());
+
+  let shared = reader.get_features_shared::<f32>(0).unwrap();
+  assert_eq!(
+    serde_json::to_value(shared[0].properties.as_ref().unwrap()).unwrap(),
+    serde_json::json!({ "class": "primary", "lanes": 2, "oneway": true })
+  );
+
+  let value = Value::List(vec![
+    Value::Float(1.5),
+    Value::Map(BTreeMap::from([(String::from("a"), Value::SInt(-1))])),
+  ]);
+  assert_eq!(
+    serde_json::to_value(value).unwrap(),
+    serde_json::json!([1.5, { "a": -1 }])
+  );
+}
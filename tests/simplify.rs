@@ -0,0 +1,126 @@
+mod support;
+
+use geo_types::{Geometry, LineString, MultiPoint};
+use mvt_reader::{
+  Reader,
+  feature::Feature,
+  options::{FeatureOptions, Simplification},
+};
+use support::{GeomType, TestFeature, TestLayer, linestrings, points, rings, tile};
+
+fn detailed_tile() -> Vec<u8> {
+  let zigzag: Vec<(i32, i32)> = (0..=100).map(|x| (x, x % 2)).collect();
+  tile(vec![TestLayer::new(
+    "detailed",
+    vec![
+      TestFeature::new(Some(1), GeomType::Linestring, linestrings(&[&zigzag])),
+      TestFeature::new(
+        Some(2),
+        GeomType::Polygon,
+        rings(&[&[(10, 10), (12, 10), (12, 12), (10, 12)]]),
+      ),
+      TestFeature::new(
+        Some(3),
+        GeomType::Polygon,
+        rings(&[
+          &[(0, 0), (1000, 0), (1000, 1000), (0, 1000)],
+          &[(500, 500), (500, 503), (503, 503), (503, 500)],
+        ]),
+      ),
+      TestFeature::new(
+        Some(4),
+        GeomType::Linestring,
+        linestrings(&[&[(0, 0), (50, 3), (100, 0), (100, 100)]]),
+      ),
+      TestFeature::new(Some(5), GeomType::Point, points(&[(7, 9), (30, 41)])),
+    ],
+  )])
+}
+
+fn reduced(options: FeatureOptions) -> Vec<Feature<i32>> {
+  let reader = Reader::new(detailed_tile()).unwrap();
+  reader
+    .get_features_with_options::<i32>(0, &options)
+    .unwrap()
+}
+
+fn ids(features: &[Feature<i32>]) -> Vec<Option<u64>> {
+  features.iter().map(|feature| feature.id).collect()
+}
+
+#[test]
+fn quantize_to_resolution() {
+  let features = reduced(FeatureOptions {
+    resolution: Some(256),
+    ..Default::default()
+  });
+  // the small polygon collapses to a single grid point
+  assert_eq!(ids(&features), vec![Some(1), Some(3), Some(4), Some(5)]);
+
+  assert_eq!(
+    features[0].geometry,
+    Geometry::LineString(LineString::from(
+      (0..=6).map(|x| (x * 16, 0)).collect::<Vec<_>>()
+    ))
+  );
+
+  let Geometry::MultiPolygon(polygons) = &features[1].geometry else {
+    panic!("Expected a multi polygon, found {:?}", features[1].geometry);
+  };
+  assert_eq!(polygons.0.len(), 1);
+  assert!(polygons.0[0].interiors().is_empty());
+  assert_eq!(
+    polygons.0[0].exterior(),
+    &LineString::from(vec![(0, 0), (1008, 0), (1008, 1008), (0, 1008), (0, 0)])
+  );
+
+  assert_eq!(
+    features[3].geometry,
+    Geometry::MultiPoint(MultiPoint::from(vec![(0, 16), (32, 48)]))
+  );
+}
+
+#[test]
+fn simplify_douglas_peucker() {
+  let features = reduced(FeatureOptions {
+    simplification: Some(Simplification::DouglasPeucker(5.0)),
+    ..Default::default()
+  });
+  assert_eq!(
+    features[0].geometry,
+    Geometry::LineString(LineString::from(vec![(0, 0), (100, 0)]))
+  );
+  // the small polygon collapses to a line
+  assert_eq!(ids(&features), vec![Some(1), Some(3), Some(4), Some(5)]);
+  assert_eq!(
+    features[2].geometry,
+    Geometry::LineString(LineString::from(vec![(0, 0), (100, 0), (100, 100)]))
+  );
+}
+
+#[test]
+fn simplify_visvalingam() {
+  let features = reduced(FeatureOptions {
+    simplification: Some(Simplification::Visvalingam(200.0)),
+    ..Default::default()
+  });
+  assert_eq!(
+    features[2].geometry,
+    Geometry::LineString(LineString::from(vec![(0, 0), (100, 0), (100, 100)]))
+  );
+  // the small polygon has an area below the tolerance
+  assert_eq!(ids(&features), vec![Some(1), Some(3), Some(4), Some(5)]);
+}
+
+#[test]
+fn quantize_and_simplify() {
+  let features = reduced(FeatureOptions {
+    resolution: Some(256),
+    simplification: Some(Simplification::DouglasPeucker(1.0)),
+    ..Default::default()
+  });
+  assert_eq!(
+    features[0].geometry,
+    Geometry::LineString(LineString::from(vec![(0, 0), (96, 0)]))
+  );
+}
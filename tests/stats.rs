@@ -0,0 +1,193 @@
+mod support;
+
+use mvt_reader::{Reader, feature::Value};
+use support::{GeomType, TestFeature, TestLayer, points, tile};
+
+// The tiles are written by hand, since the test encoder does not pack repeated fields.
+
+fn varint(mut value: u64, out: &mut Vec<u8>) {
+  while value >= 0x80 {
+    out.push((value as u8) | 0x80);
+    value >>= 7;
+  }
+  out.push(value as u8);
+}
+
+fn varint_field(tag: u32, value: u64) -> Vec<u8> {
+  let mut out = Vec::new();
+  varint((tag as u64) << 3, &mut out);
+  varint(value, &mut out);
+  out
+}
+
+fn bytes_field(tag: u32, payload: &[u8]) -> Vec<u8> {
+  let mut out = Vec::new();
+  varint(((tag as u64) << 3) | 2, &mut out);
+  varint(payload.len() as u64, &mut out);
+  out.extend_from_slice(payload);
+  out
+}
+
+fn packed_field(tag: u32, values: &[u32]) -> Vec<u8> {
+  let mut payload = Vec::new();
+  for value in values {
+    varint(*value as u64, &mut payload);
+  }
+  bytes_field(tag, &payload)
+}
+
+fn feature(id: Option<u64>, tags: &[u32], geom_type: Option<u64>, geometry: &[u32]) -> Vec<u8> {
+  let mut out = Vec::new();
+  if let Some(id) = id {
+    out.extend(varint_field(1, id));
+  }
+  if !tags.is_empty() {
+    out.extend(packed_field(2, tags));
+  }
+  if let Some(geom_type) = geom_type {
+    out.extend(varint_field(3, geom_type));
+  }
+  if !geometry.is_empty() {
+    out.extend(packed_field(4, geometry));
+  }
+  bytes_field(2, &out)
+}
+
+fn string_value(value: &str) -> Vec<u8> {
+  bytes_field(4, &bytes_field(1, value.as_bytes()))
+}
+
+fn roads_tile() -> Vec<u8> {
+  let mut layer = Vec::new();
+  layer.extend(varint_field(15, 2));
+  layer.extend(bytes_field(1, b"roads"));
+  layer.extend(feature(Some(1), &[0, 0], Some(1), &[9, 4, 6]));
+  layer.extend(feature(
+    None,
+    &[1, 1, 2, 2],
+    Some(2),
+    &[9, 0, 0, 18, 2, 2, 4, 4],
+  ));
+  layer.extend(feature(None, &[], Some(3), &[9, 0, 0, 26, 4, 0, 3, 4, 15]));
+  layer.extend(feature(None, &[], None, &[]));
+  for key in ["class", "class", "lanes", "spare"] {
+    layer.extend(bytes_field(3, key.as_bytes()));
+  }
+  layer.extend(string_value("primary"));
+  layer.extend(string_value("primary"));
+  layer.extend(bytes_field(4, &varint_field(5, 2)));
+  layer.extend(string_value("unused"));
+  layer.extend(varint_field(5, 4096));
+  bytes_field(3, &layer)
+}
+
+#[test]
+fn size_breakdown() {
+  let data = roads_tile();
+  let stats = Reader::new(data.clone()).unwrap().stats();
+
+  assert_eq!(stats.encoded_size, data.len());
+  let layer = &stats.layers[0];
+  assert_eq!(layer.name, "roads");
+  assert_eq!(layer.encoded_size, data.len());
+  assert_eq!(layer.geometry_size, 5 + 10 + 11);
+  assert_eq!(layer.tags_size, 4 + 6);
+  assert_eq!(layer.keys_size, 3 * 7 + 7);
+  assert_eq!(layer.values_size, 2 * 11 + 10 + 4);
+  // the field header of the layer, version, name, extent, the id, the geometry types and the field
+  // headers of the features
+  assert_eq!(layer.other_size(), 3 + 2 + 7 + 3 + 2 + 3 * 2 + 4 * 2);
+}
+
+#[test]
+fn non_canonical_tile() {
+  let mut feature = Vec::new();
+  // an id with a non-minimal varint
+  feature.extend([0x08, 0x81, 0x00]);
+  // unpacked tags and geometry
+  for tag in [0, 0] {
+    feature.extend(varint_field(2, tag));
+  }
+  feature.extend(varint_field(3, 1));
+  for command in [9, 4, 6] {
+    feature.extend(varint_field(4, command));
+  }
+  feature.extend(bytes_field(16, b"x"));
+
+  let mut layer = Vec::new();
+  layer.extend(varint_field(15, 2));
+  layer.extend(bytes_field(1, b"roads"));
+  layer.extend(varint_field(20, 7));
+  // a feature with a non-minimal length
+  layer.extend([0x12, feature.len() as u8 | 0x80, 0x00]);
+  layer.extend(feature);
+  layer.extend(bytes_field(3, b"class"));
+  layer.extend(string_value("primary"));
+  layer.extend(varint_field(5, 4096));
+  let mut data = bytes_field(3, &layer);
+  data.extend(varint_field(7, 1));
+
+  let stats = Reader::new(data.clone()).unwrap().stats();
+  assert_eq!(stats.encoded_size, data.len());
+  let layer = &stats.layers[0];
+  assert_eq!(layer.encoded_size, data.len() - 2);
+  assert_eq!(layer.geometry_size, 3 * 2);
+  assert_eq!(layer.tags_size, 2 * 2);
+  assert_eq!(layer.keys_size, 7);
+  assert_eq!(layer.values_size, 11);
+  // the field header of the layer, version, name, the unknown field, the field header of the feature,
+  // the id, the geometry type, the unknown field of the feature and extent
+  assert_eq!(layer.other_size(), 2 + 2 + 7 + 3 + 3 + 3 + 2 + 4 + 3);
+  assert_eq!(layer.vertex_count, 1);
+  assert!(layer.unused_keys.is_empty());
+}
+
+#[test]
+fn contents() {
+  let stats = Reader::new(roads_tile()).unwrap().stats();
+  let layer = &stats.layers[0];
+
+  assert_eq!(layer.feature_count, 4);
+  assert_eq!(layer.geometry_types.point, 1);
+  assert_eq!(layer.geometry_types.linestring, 1);
+  assert_eq!(layer.geometry_types.polygon, 1);
+  assert_eq!(layer.geometry_types.unknown, 1);
+  assert_eq!(layer.vertex_count, 1 + 3 + 3);
+  assert_eq!(layer.command_count, 1 + 2 + 3);
+  assert_eq!(layer.average_commands_per_feature(), 1.5);
+  assert_eq!(layer.unused_keys, vec![String::from("spare")]);
+  assert_eq!(
+    layer.unused_values,
+    vec![Value::String(String::from("unused"))]
+  );
+  assert_eq!(layer.duplicate_keys, vec![String::from("class")]);
+  assert_eq!(
+    layer.duplicate_values,
+    vec![Value::String(String::from("primary"))]
+  );
+}
+
+#[test]
+fn tiles_of_the_test_encoder() {
+  let reader = Reader::new(tile(vec![
+    TestLayer::new(
+      "poi",
+      vec![
+        TestFeature::new(Some(1), GeomType::Point, points(&[(1, 1)]))
+          .with("class", Value::String(String::from("cafe"))),
+      ],
+    ),
+    TestLayer::new("water", vec![]),
+  ]))
+  .unwrap();
+
+  let stats = reader.stats();
+  assert_eq!(stats.layers.len(), 2);
+  assert_eq!(
+    stats.encoded_size,
+    stats.layers[0].encoded_size + stats.layers[1].encoded_size
+  );
+  assert!(stats.layers[0].unused_keys.is_empty());
+  assert_eq!(stats.layers[1].feature_count, 0);
+  assert_eq!(stats.layers[1].average_commands_per_feature(), 0.0);
+}
@@ -0,0 +1,280 @@
+mod support;
+
+use geo_types::{Geometry, LineString, Polygon};
+use mvt_reader::{
+  Reader,
+  feature::{Feature, Value},
+  stitch::{MergeKey, TileCoordinates, stitch},
+};
+use support::{GeomType, TestFeature, TestLayer, linestrings, points, rings, tile};
+
+fn left_tile() -> Reader {
+  Reader::new(tile(vec![TestLayer::new(
+    "landuse",
+    vec![
+      TestFeature::new(
+        Some(1),
+        GeomType::Linestring,
+        linestrings(&[&[(3000, 100), (4160, 100)]]),
+      )
+      .with("class", Value::String(String::from("river"))),
+      TestFeature::new(
+        Some(2),
+        GeomType::Polygon,
+        rings(&[&[(3000, 1000), (4160, 1000), (4160, 2000), (3000, 2000)]]),
+      ),
+      TestFeature::new(
+        Some(3),
+        GeomType::Polygon,
+        rings(&[
+          &[(3000, 3000), (4160, 3000), (4160, 4000), (3000, 4000)],
+          &[(4000, 3200), (4000, 3800), (4160, 3800), (4160, 3200)],
+        ]),
+      ),
+      TestFeature::new(None, GeomType::Point, points(&[(10, 20)])),
+    ],
+  )]))
+  .unwrap()
+}
+
+fn right_tile() -> Reader {
+  Reader::new(tile(vec![TestLayer::new(
+    "landuse",
+    vec![
+      TestFeature::new(
+        Some(1),
+        GeomType::Linestring,
+        linestrings(&[&[(1000, 100), (-64, 100)]]),
+      )
+      .with("class", Value::String(String::from("river"))),
+      TestFeature::new(
+        Some(2),
+        GeomType::Polygon,
+        rings(&[&[(-64, 1000), (1000, 1000), (1000, 2000), (-64, 2000)]]),
+      ),
+      TestFeature::new(
+        Some(3),
+        GeomType::Polygon,
+        rings(&[
+          &[(-64, 3000), (904, 3000), (904, 4000), (-64, 4000)],
+          &[(-64, 3200), (-64, 3800), (104, 3800), (104, 3200)],
+        ]),
+      ),
+    ],
+  )]))
+  .unwrap()
+}
+
+fn stitched(key: &MergeKey) -> Vec<Feature<f64>> {
+  let (left, right) = (left_tile(), right_tile());
+  stitch(
+    &[
+      (&left, TileCoordinates::new(1, 0, 0)),
+      (&right, TileCoordinates::new(1, 1, 0)),
+    ],
+    "landuse",
+    key,
+  )
+  .unwrap()
+}
+
+fn sorted_vertices(ring: &LineString<f64>) -> Vec<(i64, i64)> {
+  let mut vertices: Vec<(i64, i64)> = ring.0.iter().map(|c| (c.x as i64, c.y as i64)).collect();
+  vertices.sort_unstable();
+  vertices.dedup();
+  vertices
+}
+
+fn polygon(feature: &Feature<f64>) -> &Polygon<f64> {
+  match &feature.geometry {
+    Geometry::Polygon(polygon) => polygon,
+    geometry => panic!("Expected a polygon, found {:?}", geometry),
+  }
+}
+
+#[test]
+fn stitch_joins_lines() {
+  let features = stitched(&MergeKey::Id);
+  assert_eq!(features.len(), 4);
+  assert_eq!(features[0].id, Some(1));
+  assert_eq!(
+    features[0].geometry,
+    Geometry::LineString(LineString::from(vec![
+      (3000.0, 100.0),
+      (4096.0, 100.0),
+      (5096.0, 100.0)
+    ]))
+  );
+}
+
+#[test]
+fn stitch_unions_polygons() {
+  let features = stitched(&MergeKey::Id);
+
+  let merged = polygon(&features[1]);
+  assert!(merged.interiors().is_empty());
+  assert_eq!(
+    sorted_vertices(merged.exterior()),
+    vec![(3000, 1000), (3000, 2000), (5096, 1000), (5096, 2000)]
+  );
+
+  let merged = polygon(&features[2]);
+  assert_eq!(
+    sorted_vertices(merged.exterior()),
+    vec![(3000, 3000), (3000, 4000), (5000, 3000), (5000, 4000)]
+  );
+  assert_eq!(merged.interiors().len(), 1);
+  assert_eq!(
+    sorted_vertices(&merged.interiors()[0]),
+    vec![(4000, 3200), (4000, 3800), (4200, 3200), (4200, 3800)]
+  );
+}
+
+#[test]
+fn stitch_keeps_unkeyed_features() {
+  let features = stitched(&MergeKey::Property(String::from("class")));
+  // only the river has the property, the other pieces are passed through
+  assert_eq!(features.len(), 6);
+  assert_eq!(features[0].id, Some(1));
+  assert!(matches!(features[0].geometry, Geometry::LineString(_)));
+  assert!(
+    features[1..]
+      .iter()
+      .all(|feature| feature.properties.is_some())
+  );
+}
+
+#[test]
+fn stitch_merges_equal_numbers_of_different_types() {
+  let piece = |start: i32, end: i32, rank: Value| {
+    Reader::new(tile(vec![TestLayer::new(
+      "roads",
+      vec![
+        TestFeature::new(
+          None,
+          GeomType::Linestring,
+          linestrings(&[&[(start, 100), (end, 100)]]),
+        )
+        .with("rank", rank),
+      ],
+    )]))
+    .unwrap()
+  };
+  let left = piece(3000, 4096, Value::Int(1));
+  let middle = piece(0, 4096, Value::UInt(1));
+  let right = piece(0, 1000, Value::Double(1.0));
+
+  let features = stitch(
+    &[
+      (&left, TileCoordinates::new(2, 0, 0)),
+      (&middle, TileCoordinates::new(2, 1, 0)),
+      (&right, TileCoordinates::new(2, 2, 0)),
+    ],
+    "roads",
+    &MergeKey::Property(String::from("rank")),
+  )
+  .unwrap();
+  assert_eq!(features.len(), 1);
+  assert_eq!(
+    features[0].geometry,
+    Geometry::LineString(LineString::from(vec![
+      (3000.0, 100.0),
+      (4096.0, 100.0),
+      (8192.0, 100.0),
+      (9192.0, 100.0)
+    ]))
+  );
+}
+
+#[test]
+fn stitch_hole_across_seam() {
+  // two C shapes that enclose a hole across the seam once they are merged
+  let piece = |name: &str, ring: &[(i32, i32)]| {
+    Reader::new(tile(vec![TestLayer::new(
+      "landuse",
+      vec![
+        TestFeature::new(Some(1), GeomType::Polygon, rings(&[ring]))
+          .with("name", Value::String(String::from(name))),
+      ],
+    )]))
+    .unwrap()
+  };
+  let left = piece(
+    "left",
+    &[
+      (3000, 3000),
+      (4096, 3000),
+      (4096, 3200),
+      (4000, 3200),
+      (4000, 3800),
+      (4096, 3800),
+      (4096, 4000),
+      (3000, 4000),
+    ],
+  );
+  let right = piece(
+    "right",
+    &[
+      (0, 3000),
+      (1000, 3000),
+      (1000, 4000),
+      (0, 4000),
+      (0, 3800),
+      (104, 3800),
+      (104, 3200),
+      (0, 3200),
+    ],
+  );
+
+  let features = stitch(
+    &[
+      (&left, TileCoordinates::new(1, 0, 0)),
+      (&right, TileCoordinates::new(1, 1, 0)),
+    ],
+    "landuse",
+    &MergeKey::Id,
+  )
+  .unwrap();
+  assert_eq!(features.len(), 1);
+  let merged = polygon(&features[0]);
+  assert_eq!(
+    sorted_vertices(merged.exterior()),
+    vec![(3000, 3000), (3000, 4000), (5096, 3000), (5096, 4000)]
+  );
+  assert_eq!(merged.interiors().len(), 1);
+  assert_eq!(
+    sorted_vertices(&merged.interiors()[0]),
+    vec![(4000, 3200), (4000, 3800), (4200, 3200), (4200, 3800)]
+  );
+  // the properties of the first piece win
+  assert_eq!(
+    features[0].properties.as_ref().unwrap()["name"],
+    Value::String(String::from("left"))
+  );
+}
+
+#[test]
+fn stitch_keeps_orphan_holes() {
+  // a ring wound like a hole, without an exterior in any tile
+  let hole = Reader::new(tile(vec![TestLayer::new(
+    "landuse",
+    vec![TestFeature::new(
+      Some(1),
+      GeomType::Polygon,
+      rings(&[&[(100, 100), (100, 200), (200, 200), (200, 100)]]),
+    )],
+  )]))
+  .unwrap();
+
+  let features = stitch(
+    &[(&hole, TileCoordinates::new(0, 0, 0))],
+    "landuse",
+    &MergeKey::Id,
+  )
+  .unwrap();
+  assert_eq!(features.len(), 1);
+  assert_eq!(
+    sorted_vertices(polygon(&features[0]).exterior()),
+    vec![(100, 100), (100, 200), (200, 100), (200, 200)]
+  );
+}
@@ -0,0 +1,168 @@
+//! Helpers for reading the tiles of mvt-fixtures and for building the tiles the fixtures can't express.
+
+#![allow(dead_code)]
+
+use mvt_reader::feature::Value;
+use prost::Message;
+
+mod vector_tile {
+  include!("../../src/generated/vector_tile.rs");
+}
+
+pub use vector_tile::tile::GeomType;
+
+/// Reads the tile of a fixture of the mvt-fixtures submodule, e.g. `"038"`.
+pub fn fixture(name: &str) -> Vec<u8> {
+  std::fs::read(format!("mvt-fixtures/fixtures/{}/tile.mvt", name)).unwrap()
+}
+
+/// A feature description used to build a test layer.
+pub struct TestFeature {
+  pub id: Option<u64>,
+  pub geom_type: GeomType,
+  pub geometry: Vec<u32>,
+  pub properties: Vec<(&'static str, Value)>,
+}
+
+impl TestFeature {
+  pub fn new(id: Option<u64>, geom_type: GeomType, geometry: Vec<u32>) -> Self {
+    Self {
+      id,
+      geom_type,
+      geometry,
+      properties: Vec::new(),
+    }
+  }
+
+  pub fn with(mut self, key: &'static str, value: Value) -> Self {
+    self.properties.push((key, value));
+    self
+  }
+}
+
+/// A layer description used to build a test tile.
+pub struct TestLayer {
+  pub name: &'static str,
+  pub extent: u32,
+  pub features: Vec<TestFeature>,
+}
+
+impl TestLayer {
+  pub fn new(name: &'static str, features: Vec<TestFeature>) -> Self {
+    Self {
+      name,
+      extent: 4096,
+      features,
+    }
+  }
+}
+
+pub fn command(id: u32, count: u32) -> u32 {
+  (id & 0x7) | (count << 3)
+}
+
+pub fn zigzag(value: i32) -> u32 {
+  ((value << 1) ^ (value >> 31)) as u32
+}
+
+fn push_coordinates(geometry: &mut Vec<u32>, cursor: &mut (i32, i32), coordinates: &[(i32, i32)]) {
+  for (x, y) in coordinates {
+    geometry.push(zigzag(x - cursor.0));
+    geometry.push(zigzag(y - cursor.1));
+    *cursor = (*x, *y);
+  }
+}
+
+/// Encodes one or more points.
+pub fn points(coordinates: &[(i32, i32)]) -> Vec<u32> {
+  let mut geometry = vec![command(1, coordinates.len() as u32)];
+  push_coordinates(&mut geometry, &mut (0, 0), coordinates);
+  geometry
+}
+
+/// Encodes one or more linestrings.
+pub fn linestrings(lines: &[&[(i32, i32)]]) -> Vec<u32> {
+  let mut geometry = Vec::new();
+  let mut cursor = (0, 0);
+  for line in lines {
+    geometry.push(command(1, 1));
+    push_coordinates(&mut geometry, &mut cursor, &line[..1]);
+    geometry.push(command(2, line.len() as u32 - 1));
+    push_coordinates(&mut geometry, &mut cursor, &line[1..]);
+  }
+  geometry
+}
+
+/// Encodes polygon rings without their closing coordinate. Exterior rings must be clockwise in
+/// screen coordinates (positive area), interior rings counter-clockwise.
+pub fn rings(rings: &[&[(i32, i32)]]) -> Vec<u32> {
+  let mut geometry = Vec::new();
+  let mut cursor = (0, 0);
+  for ring in rings {
+    geometry.push(command(1, 1));
+    push_coordinates(&mut geometry, &mut cursor, &ring[..1]);
+    geometry.push(command(2, ring.len() as u32 - 1));
+    push_coordinates(&mut geometry, &mut cursor, &ring[1..]);
+    geometry.push(command(7, 1));
+  }
+  geometry
+}
+
+fn encode_value(value: &Value) -> vector_tile::tile::Value {
+  let mut encoded = vector_tile::tile::Value::default();
+  match value {
+    Value::String(s) => encoded.string_value = Some(s.clone()),
+    Value::Float(f) => encoded.float_value = Some(*f),
+    Value::Double(d) => encoded.double_value = Some(*d),
+    Value::Int(i) => encoded.int_value = Some(*i),
+    Value::UInt(u) => encoded.uint_value = Some(*u),
+    Value::SInt(s) => encoded.sint_value = Some(*s),
+    Value::Bool(b) => encoded.bool_value = Some(*b),
+    Value::Null => (),
+    Value::List(_) | Value::Map(_) => panic!("lists and maps cannot be encoded as tags"),
+  }
+  encoded
+}
+
+/// Encodes the given layers into vector tile bytes.
+pub fn tile(layers: Vec<TestLayer>) -> Vec<u8> {
+  let mut tile = vector_tile::Tile::default();
+  for test_layer in layers {
+    let mut layer = vector_tile::tile::Layer {
+      version: 2,
+      name: test_layer.name.to_string(),
+      extent: Some(test_layer.extent),
+      ..Default::default()
+    };
+    for test_feature in test_layer.features {
+      let mut tags = Vec::new();
+      for (key, value) in test_feature.properties {
+        let key_index = match layer.keys.iter().position(|k| k == key) {
+          Some(index) => index,
+          None => {
+            layer.keys.push(key.to_string());
+            layer.keys.len() - 1
+          }
+        };
+        let encoded = encode_value(&value);
+        let value_index = match layer.values.iter().position(|v| *v == encoded) {
+          Some(index) => index,
+          None => {
+            layer.values.push(encoded);
+            layer.values.len() - 1
+          }
+        };
+        tags.push(key_index as u32);
+        tags.push(value_index as u32);
+      }
+      layer.features.push(vector_tile::tile::Feature {
+        id: test_feature.id,
+        tags,
+        r#type: Some(test_feature.geom_type as i32),
+        geometry: test_feature.geometry,
+      });
+    }
+    tile.layers.push(layer);
+  }
+  tile.encode_to_vec()
+}
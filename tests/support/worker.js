@@ -0,0 +1,11 @@
+const { parentPort } = require('worker_threads')
+const { decodeTile } = require('mvt-reader')
+
+parentPort.on('message', ({ buffer, layers, integerMode }) => {
+  try {
+    const result = decodeTile(buffer, layers, false, integerMode)
+    parentPort.postMessage({ layers: result.layers }, result.transfer)
+  } catch (error) {
+    parentPort.postMessage({ error: { name: error.name, kind: error.kind, message: error.message } })
+  }
+})
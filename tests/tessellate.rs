@@ -0,0 +1,193 @@
+#![cfg(feature = "tessellate")]
+
+mod support;
+
+use mvt_reader::{
+  Reader,
+  tessellate::{LineCap, LineJoin, LineStyle, Mesh, Topology},
+};
+use support::{GeomType, TestFeature, TestLayer, linestrings, points, rings, tile};
+
+fn reader() -> Reader {
+  Reader::new(tile(vec![TestLayer::new(
+    "shapes",
+    vec![
+      TestFeature::new(
+        Some(1),
+        GeomType::Polygon,
+        rings(&[
+          &[(0, 0), (100, 0), (100, 100), (0, 100)],
+          &[(40, 40), (40, 60), (60, 60), (60, 40)],
+        ]),
+      ),
+      TestFeature::new(
+        Some(2),
+        GeomType::Linestring,
+        linestrings(&[&[(0, 200), (100, 200)]]),
+      ),
+      TestFeature::new(
+        Some(3),
+        GeomType::Linestring,
+        linestrings(&[&[(0, 300), (100, 300), (100, 400)]]),
+      ),
+      TestFeature::new(Some(4), GeomType::Point, points(&[(10, 10)])),
+    ],
+  )]))
+  .unwrap()
+}
+
+/// Sums the areas of all triangles, which equals the covered area if the triangles do not overlap.
+fn area(mesh: &Mesh) -> f32 {
+  let mesh = mesh.to_triangles();
+  mesh
+    .indices
+    .chunks(3)
+    .map(|triangle| {
+      let [a, b, c] = [0, 1, 2].map(|corner| {
+        let index = triangle[corner] as usize * 2;
+        (mesh.vertices[index], mesh.vertices[index + 1])
+      });
+      ((b.0 - a.0) * (c.1 - a.1) - (c.0 - a.0) * (b.1 - a.1)).abs() / 2.0
+    })
+    .sum()
+}
+
+fn extruded(feature_index: usize, style: LineStyle) -> Mesh {
+  reader().get_features(0).unwrap()[feature_index].extrude(&style)
+}
+
+#[test]
+fn tessellate_polygon_with_hole() {
+  let features = reader().get_features(0).unwrap();
+  let mesh = features[0].tessellate();
+  assert_eq!(mesh.vertex_count(), 8);
+  assert_eq!(mesh.triangle_count(), 8);
+  assert_eq!(area(&mesh), 100.0 * 100.0 - 20.0 * 20.0);
+
+  // only polygons are triangulated
+  assert!(features[1].tessellate().is_empty());
+  assert!(features[3].tessellate().is_empty());
+}
+
+#[test]
+fn extrude_line_caps() {
+  let style = LineStyle {
+    width: 10.0,
+    ..Default::default()
+  };
+  let mesh = extruded(1, style);
+  assert_eq!(mesh.topology, Topology::TriangleStrip);
+  assert_eq!(mesh.indices, vec![0, 1, 2, 3]);
+  assert_eq!(mesh.triangle_count(), 2);
+  assert_eq!(area(&mesh), 1000.0);
+
+  let mesh = extruded(
+    1,
+    LineStyle {
+      cap: LineCap::Square,
+      ..style
+    },
+  );
+  assert_eq!(area(&mesh), 1100.0);
+
+  let mesh = extruded(
+    1,
+    LineStyle {
+      cap: LineCap::Round,
+      ..style
+    },
+  );
+  let circle = std::f32::consts::PI * 25.0;
+  assert!(area(&mesh) > 1000.0 + circle * 0.95 && area(&mesh) < 1000.0 + circle);
+}
+
+#[test]
+fn extrude_line_joins() {
+  let style = LineStyle {
+    width: 10.0,
+    ..Default::default()
+  };
+  // the outer corner of the turn is filled by a square for miter joins
+  assert_eq!(area(&extruded(2, style)), 2025.0);
+
+  let mesh = extruded(
+    2,
+    LineStyle {
+      join: LineJoin::Bevel,
+      ..style
+    },
+  );
+  assert_eq!(area(&mesh), 2012.5);
+
+  // the miter is beveled when it exceeds the limit
+  let mesh = extruded(
+    2,
+    LineStyle {
+      miter_limit: 1.0,
+      ..style
+    },
+  );
+  assert_eq!(area(&mesh), 2012.5);
+
+  let mesh = extruded(
+    2,
+    LineStyle {
+      join: LineJoin::Round,
+      ..style
+    },
+  );
+  let quarter = std::f32::consts::PI * 25.0 / 4.0;
+  assert!(area(&mesh) > 2012.5 && area(&mesh) < 2000.0 + quarter);
+
+  // points and polygons are not extruded
+  assert!(extruded(0, style).is_empty());
+  assert!(extruded(3, style).is_empty());
+}
+
+#[test]
+fn tessellate_layer() {
+  let reader = reader();
+  let polygons = reader.tessellate_layer(0).unwrap();
+  assert_eq!(polygons.topology, Topology::Triangles);
+  assert_eq!(polygons.triangle_count(), 8);
+
+  let style = LineStyle {
+    width: 10.0,
+    ..Default::default()
+  };
+  let lines = reader.extrude_layer(0, &style).unwrap();
+  assert_eq!(lines.topology, Topology::TriangleStrip);
+  assert_eq!(area(&lines), 1000.0 + 2025.0);
+  // both lines are connected by repeating the last index of the first and the first index of the second
+  assert_eq!(lines.indices[2..6], [2, 3, 3, 4]);
+
+  let mut mesh = polygons.clone();
+  mesh.append(&lines);
+  assert_eq!(mesh.topology, Topology::Triangles);
+  assert_eq!(area(&mesh), 9600.0 + 1000.0 + 2025.0);
+  assert!(
+    mesh
+      .indices
+      .iter()
+      .all(|index| (*index as usize) < mesh.vertex_count())
+  );
+}
+
+#[test]
+fn strip_winding_order() {
+  let mut mesh = Mesh::strip();
+  mesh.vertices = vec![0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0, 2.0, 0.0];
+  mesh.indices = vec![0, 1, 2];
+  let mut other = Mesh::strip();
+  other.vertices = vec![0.0, 0.0, 0.0, 1.0, 1.0, 0.0];
+  other.indices = vec![0, 1, 2];
+
+  // an odd length is padded to keep the winding order of the appended strip
+  mesh.append(&other);
+  assert_eq!(mesh.indices, vec![0, 1, 2, 2, 2, 5, 5, 6, 7]);
+  assert_eq!(mesh.triangle_count(), 2);
+  assert_eq!(mesh.to_triangles().indices, vec![0, 1, 2, 5, 6, 7]);
+
+  mesh.indices = vec![0, 1, 2, 3, 4];
+  assert_eq!(mesh.to_triangles().indices, vec![0, 1, 2, 2, 1, 3, 2, 3, 4]);
+}
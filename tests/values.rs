@@ -0,0 +1,119 @@
+mod support;
+
+use std::collections::BTreeMap;
+
+use mvt_reader::{
+  Reader,
+  feature::{PropertyLookup, Value},
+};
+use support::{GeomType, TestFeature, TestLayer, fixture, points, tile};
+
+#[test]
+fn typed_accessors_coerce_numeric_variants() {
+  // fixture 038 has a single feature with a property of every value type
+  let reader = Reader::new(fixture("038")).unwrap();
+  let feature = &reader.get_features(0).unwrap()[0];
+
+  assert_eq!(feature.get_f64("int_value"), Some(6.0));
+  assert_eq!(feature.get_f64("uint_value"), Some(87948.0));
+  assert_eq!(feature.get_f64("sint_value"), Some(-87948.0));
+  assert_eq!(feature.get_f64("double_value"), Some(1.23));
+  assert_eq!(feature.get_f64("float_value"), Some(3.1f32 as f64));
+  assert_eq!(feature.get_i64("int_value"), Some(6));
+  assert_eq!(feature.get_i64("uint_value"), Some(87948));
+  assert_eq!(feature.get_i64("sint_value"), Some(-87948));
+  assert_eq!(feature.get_i64("double_value"), None);
+
+  assert_eq!(feature.get_str("string_value"), Some("ello"));
+  assert_eq!(feature.get_str("int_value"), None);
+  assert_eq!(feature.get_bool("bool_value"), Some(true));
+  assert_eq!(feature.get_bool("missing"), None);
+  assert_eq!(feature.get_f64("string_value"), None);
+}
+
+#[test]
+fn typed_accessors_for_all_property_containers() {
+  let data = tile(vec![TestLayer::new(
+    "buildings",
+    vec![
+      TestFeature::new(Some(1), GeomType::Point, points(&[(1, 1)]))
+        .with("height", Value::Int(12))
+        .with("name", Value::String(String::from("Hall")))
+        .with("height", Value::Double(12.5)),
+    ],
+  )]);
+  let reader = Reader::new(data).unwrap();
+
+  let ordered = &reader.get_features_ordered::<f32>(0).unwrap()[0];
+  let shared = &reader.get_features_shared::<f32>(0).unwrap()[0];
+  let owned = &reader.get_features(0).unwrap()[0];
+  let lookups: [&dyn PropertyLookup; 6] = [
+    owned,
+    owned.properties.as_ref().unwrap(),
+    ordered,
+    ordered.properties.as_ref().unwrap(),
+    shared,
+    shared.properties.as_ref().unwrap(),
+  ];
+  for lookup in lookups {
+    // the last value of the duplicate key wins
+    assert_eq!(lookup.get_f64("height"), Some(12.5));
+    assert_eq!(lookup.get_i64("height"), None);
+    assert_eq!(lookup.get_str("name"), Some("Hall"));
+    assert_eq!(lookup.get_value("missing"), None);
+  }
+}
+
+#[test]
+fn value_conversions() {
+  assert_eq!(Value::Int(3).as_u64(), Some(3));
+  assert_eq!(Value::SInt(-1).as_u64(), None);
+  assert_eq!(Value::UInt(u64::MAX).as_i64(), None);
+  assert_eq!(Value::UInt(u64::MAX).as_u64(), Some(u64::MAX));
+  assert_eq!(Value::Double(1e19).as_i64(), None);
+  assert_eq!(Value::Double(f64::NAN).as_i64(), None);
+  assert_eq!(Value::Double(-2.0).as_i64(), Some(-2));
+
+  assert_eq!(i64::try_from(Value::UInt(5)), Ok(5));
+  assert_eq!(u32::try_from(Value::Double(6.0)), Ok(6));
+  assert_eq!(f32::try_from(Value::Int(2)), Ok(2.0));
+  assert_eq!(bool::try_from(Value::Bool(false)), Ok(false));
+  assert_eq!(
+    String::try_from(Value::String(String::from("a"))),
+    Ok(String::from("a"))
+  );
+
+  let error = i32::try_from(Value::UInt(u64::MAX)).unwrap_err();
+  assert_eq!(error.expected(), "i32");
+  assert_eq!(
+    error.to_string(),
+    "Value 18446744073709551615 cannot be converted to i32"
+  );
+  assert_eq!(error.into_value(), Value::UInt(u64::MAX));
+  assert!(bool::try_from(Value::UInt(1)).is_err());
+  assert!(String::try_from(Value::Null).is_err());
+}
+
+#[test]
+fn value_display() {
+  assert_eq!(Value::String(String::from("cafe")).to_string(), "cafe");
+  assert_eq!(Value::Float(1.5).to_string(), "1.5");
+  assert_eq!(Value::Double(2.0).to_string(), "2");
+  assert_eq!(Value::Int(-4).to_string(), "-4");
+  assert_eq!(Value::UInt(4).to_string(), "4");
+  assert_eq!(Value::SInt(-5).to_string(), "-5");
+  assert_eq!(Value::Bool(true).to_string(), "true");
+  assert_eq!(Value::Null.to_string(), "null");
+  assert_eq!(
+    Value::List(vec![Value::String(String::from("a")), Value::UInt(1)]).to_string(),
+    r#"["a", 1]"#
+  );
+  assert_eq!(
+    Value::Map(BTreeMap::from([
+      (String::from("b"), Value::Null),
+      (String::from("a"), Value::String(String::from("x"))),
+    ]))
+    .to_string(),
+    r#"{"a": "x", "b": null}"#
+  );
+}
@@ -0,0 +1,46 @@
+const { Worker } = require('worker_threads')
+const { readFileSync } = require('fs')
+const { join } = require('path')
+
+function decodeInWorker(data, layers, integerMode) {
+  const worker = new Worker(join(__dirname, 'support', 'worker.js'))
+  const buffer = data.buffer.slice(data.byteOffset, data.byteOffset + data.byteLength)
+  return new Promise((resolve, reject) => {
+    worker.once('message', message => resolve({ message, buffer }))
+    worker.once('error', reject)
+    worker.postMessage({ buffer, layers, integerMode }, [buffer])
+  }).finally(() => worker.terminate())
+}
+
+describe('Worker', () => {
+  test('decodes layers off the main thread', async () => {
+    const { message, buffer } = await decodeInWorker(readFileSync('mvt-fixtures/fixtures/038/tile.mvt'))
+
+    // the input was transferred, not copied
+    expect(buffer.byteLength).toBe(0)
+
+    expect(message.layers.map(layer => [layer.name, layer.index])).toEqual([['hello', 0]])
+    const layer = message.layers[0]
+    expect(layer.coordinates).toBeInstanceOf(Int32Array)
+    expect(layer.geometryTypes).toBeInstanceOf(Uint8Array)
+    expect(layer.featureOffsets.length).toBe(layer.geometryTypes.length + 1)
+  })
+
+  test('decodes selected layers only', async () => {
+    const { message } = await decodeInWorker(readFileSync('mvt-fixtures/fixtures/038/tile.mvt'), ['missing'])
+    expect(message.layers).toEqual([])
+  })
+
+  test('decodes ids in the given integer mode', async () => {
+    const data = readFileSync('mvt-fixtures/fixtures/038/tile.mvt')
+    expect((await decodeInWorker(data)).message.layers[0].ids).toEqual(new Float64Array([1]))
+    expect((await decodeInWorker(data, undefined, 'bigint')).message.layers[0].ids).toEqual([1n])
+    expect((await decodeInWorker(data, undefined, 'string')).message.layers[0].ids).toEqual(['1'])
+  })
+
+  test('reports decode errors', async () => {
+    const { message } = await decodeInWorker(readFileSync('mvt-fixtures/fixtures/013/tile.mvt'))
+    expect(message.error.name).toBe('ParserError')
+    expect(message.error.kind).toBe('decode')
+  })
+})